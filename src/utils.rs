@@ -1,5 +1,4 @@
 use crate::Domain;
-use quick_xml::{de::from_reader, se::to_string};
 use std::fs::File;
 use std::io::{Read, Write};
 
@@ -9,11 +8,11 @@ pub fn read_vm_config(path: &str) -> Result<Domain, Box<dyn std::error::Error>>
     let mut contents = String::new();
     file.read_to_string(&mut contents)
         .map_err(|e| format!("Failed to read file: {}", e))?;
-    from_reader(contents.as_bytes()).map_err(|e| format!("XML parsing error: {:#?}", e).into())
+    Domain::from_xml(&contents).map_err(|e| format!("XML parsing error: {}", e).into())
 }
 
 pub fn write_vm_config(domain: &Domain, path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let xml_string = to_string(domain)?;
+    let xml_string = domain.to_xml()?;
 
     let mut file = File::create(path)?;
     file.write_all(xml_string.as_bytes())?;
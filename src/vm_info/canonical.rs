@@ -0,0 +1,185 @@
+use super::cpu::CpuCheck;
+use super::numatune::NodeSet;
+use super::{Domain, MemoryValue};
+use quick_xml::{de::from_str, se::to_string};
+use sha2::{Digest, Sha256};
+
+// 规范化 cpuset/nodeset，无法解析时保持原样，由 validate() 报告
+fn canonical_set(expression: &mut String) {
+    if let Ok(canonical) = NodeSet::new(expression).to_canonical() {
+        *expression = canonical;
+    }
+}
+
+fn canonical_memory(value: &mut Option<MemoryValue>) {
+    if let Some(v) = value {
+        *v = MemoryValue::from_kib(v.to_kib());
+    }
+}
+
+// 数字 id 按数值排序，其余排在后面按字符串排序
+fn id_key(id: &str) -> (u64, String) {
+    (id.parse().unwrap_or(u64::MAX), id.to_string())
+}
+
+/// 规范化选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CanonicalOptions {
+    /// 保留设备地址。默认去掉：libvirt 定义或启动域时会自动分配地址，
+    /// 只有期望配置中固定了地址、需要比较地址时才设置
+    pub keep_addresses: bool,
+}
+
+impl CanonicalOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_keep_addresses(mut self, keep: bool) -> Self {
+        self.keep_addresses = keep;
+        self
+    }
+}
+
+impl Domain {
+    /// 就地转换为规范形式，用于比较期望配置与 libvirt 返回的 XML
+    ///
+    /// - 内存单位统一为 KiB
+    /// - cpuset/nodeset 统一为升序范围
+    /// - 与顺序无关的集合排序（vcpu、vcpupin、iothread、CPU 特性）
+    /// - 去掉 libvirt 自动补充的默认值（如 `check='full'`、`placement='static'`）
+    /// - 去掉仅运行时存在的字段，见 [`Domain::strip_runtime`]
+    /// - 去掉设备地址，需要保留时使用 [`Domain::canonicalize_with`]
+    pub fn canonicalize(&mut self) {
+        self.canonicalize_with(CanonicalOptions::default());
+    }
+
+    /// 按选项转换为规范形式
+    pub fn canonicalize_with(&mut self, options: CanonicalOptions) {
+        self.strip_runtime();
+        if !options.keep_addresses {
+            for (_, _, address) in self.devices.aliases_and_addresses_mut() {
+                *address = None;
+            }
+        }
+
+        // 内存
+        self.memory.value = self.memory.to_kib();
+        self.memory.unit = "KiB".to_string();
+        if let Some(max) = &mut self.max_memory {
+            max.value = max.to_kib();
+            max.unit = "KiB".to_string();
+        }
+        if let Some(current) = &mut self.current_memory {
+            current.value = current.to_kib();
+            current.unit = "KiB".to_string();
+        }
+        if self
+            .current_memory
+            .as_ref()
+            .is_some_and(|c| c.value == self.memory.value)
+        {
+            self.current_memory = None;
+        }
+        if let Some(memtune) = &mut self.memtune {
+            canonical_memory(&mut memtune.hard_limit);
+            canonical_memory(&mut memtune.soft_limit);
+            canonical_memory(&mut memtune.swap_hard_limit);
+            canonical_memory(&mut memtune.min_guarantee);
+        }
+
+        // vCPU
+        if self.vcpu.placement.as_deref() == Some("static") {
+            self.vcpu.placement = None;
+        }
+        if self.vcpu.current == Some(self.vcpu.vcpu_count) {
+            self.vcpu.current = None;
+        }
+        if let Some(cpuset) = &mut self.vcpu.cpuset {
+            canonical_set(cpuset);
+        }
+        if let Some(vcpus) = &mut self.vcpus {
+            vcpus.vcpu.sort_by_key(|v| v.id);
+        }
+        if let Some(ids) = &mut self.iothread_ids {
+            ids.iothreads.sort_by_key(|t| t.id);
+            // 与 libvirt 相同，<iothreads> 至少为 <iothreadids> 的数量
+            let declared = ids.iothreads.len() as u32;
+            if declared > 0 && self.iothreads.is_none_or(|count| count < declared) {
+                self.iothreads = Some(declared);
+            }
+        }
+        if let Some(cputune) = &mut self.cputune {
+            for pin in &mut cputune.vcpu_pins {
+                canonical_set(&mut pin.cpuset);
+            }
+            cputune.vcpu_pins.sort_by_key(|p| id_key(&p.vcpu));
+            for pin in &mut cputune.iothread_pins {
+                canonical_set(&mut pin.cpuset);
+            }
+            cputune.iothread_pins.sort_by_key(|p| id_key(&p.iothread));
+            if let Some(pin) = &mut cputune.emulator_pin {
+                canonical_set(&mut pin.cpuset);
+            }
+        }
+
+        // NUMA
+        if let Some(numatune) = &mut self.numatune {
+            if let Some(nodeset) = numatune.memory.as_mut().and_then(|m| m.nodeset.as_mut()) {
+                canonical_set(&mut nodeset.expression);
+            }
+            if let Some(memnodes) = &mut numatune.memnodes {
+                for node in memnodes.iter_mut() {
+                    canonical_set(&mut node.nodeset.expression);
+                }
+                memnodes.sort_by_key(|n| n.cell_id);
+            }
+        }
+
+        // CPU
+        if let Some(cpu) = &mut self.cpu {
+            if cpu.check == Some(CpuCheck::Full) {
+                cpu.check = None;
+            }
+            if let Some(features) = &mut cpu.features {
+                features.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+        }
+    }
+
+    /// 规范形式的 XML，不修改自身
+    pub fn to_canonical_xml(&self) -> Result<String, String> {
+        self.to_canonical_xml_with(CanonicalOptions::default())
+    }
+
+    pub fn to_canonical_xml_with(&self, options: CanonicalOptions) -> Result<String, String> {
+        let xml = to_string(self).map_err(|e| format!("Failed to serialize domain: {}", e))?;
+        let mut copy: Domain =
+            from_str(&xml).map_err(|e| format!("Failed to reparse domain: {}", e))?;
+        copy.canonicalize_with(options);
+        to_string(&copy).map_err(|e| format!("Failed to serialize domain: {}", e))
+    }
+
+    /// 规范形式的 SHA-256（小写十六进制），语义相同的域得到相同的值
+    pub fn content_hash(&self) -> Result<String, String> {
+        self.content_hash_with(CanonicalOptions::default())
+    }
+
+    pub fn content_hash_with(&self, options: CanonicalOptions) -> Result<String, String> {
+        let digest = Sha256::digest(self.to_canonical_xml_with(options)?.as_bytes());
+        Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// 比较两个域的规范形式
+    pub fn semantically_eq(&self, other: &Domain) -> Result<bool, String> {
+        self.semantically_eq_with(other, CanonicalOptions::default())
+    }
+
+    pub fn semantically_eq_with(
+        &self,
+        other: &Domain,
+        options: CanonicalOptions,
+    ) -> Result<bool, String> {
+        Ok(self.to_canonical_xml_with(options)? == other.to_canonical_xml_with(options)?)
+    }
+}
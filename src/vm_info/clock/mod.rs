@@ -0,0 +1,219 @@
+mod timer;
+
+use crate::vm_info::features::Features;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+pub use timer::{TickPolicy, Timer, TimerCatchup, TimerName, TimerTrack, TscMode};
+
+/// 客户机时钟与宿主机时钟的同步方式
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ClockOffset {
+    #[default]
+    Utc,
+    Localtime,
+    Timezone,
+    Variable,
+    Absolute,
+}
+
+impl fmt::Display for ClockOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClockOffset::Utc => write!(f, "utc"),
+            ClockOffset::Localtime => write!(f, "localtime"),
+            ClockOffset::Timezone => write!(f, "timezone"),
+            ClockOffset::Variable => write!(f, "variable"),
+            ClockOffset::Absolute => write!(f, "absolute"),
+        }
+    }
+}
+
+/// variable 模式下调整量的基准
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ClockBasis {
+    Utc,
+    Localtime,
+}
+
+/// 时钟配置
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Clock {
+    #[serde(rename = "@offset")]
+    pub offset: ClockOffset,
+
+    /// utc/localtime 下为 reset 或秒数；variable 下为秒数
+    #[serde(rename = "@adjustment", skip_serializing_if = "Option::is_none")]
+    pub adjustment: Option<String>,
+
+    /// 仅 timezone
+    #[serde(rename = "@timezone", skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// 仅 variable
+    #[serde(rename = "@basis", skip_serializing_if = "Option::is_none")]
+    pub basis: Option<ClockBasis>,
+
+    /// 仅 absolute，Unix 时间戳
+    #[serde(rename = "@start", skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+
+    #[serde(rename = "timer", default, skip_serializing_if = "Vec::is_empty")]
+    pub timers: Vec<Timer>,
+}
+
+impl Clock {
+    pub fn utc() -> Self {
+        Self::default()
+    }
+
+    pub fn localtime() -> Self {
+        Self {
+            offset: ClockOffset::Localtime,
+            ..Self::default()
+        }
+    }
+
+    pub fn timezone(timezone: &str) -> Self {
+        Self {
+            offset: ClockOffset::Timezone,
+            timezone: Some(timezone.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// 相对 basis 偏移 adjustment 秒
+    pub fn variable(basis: ClockBasis, adjustment: i64) -> Self {
+        Self {
+            offset: ClockOffset::Variable,
+            basis: Some(basis),
+            adjustment: Some(adjustment.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// 客户机从固定时间点启动
+    pub fn absolute(start: u64) -> Self {
+        Self {
+            offset: ClockOffset::Absolute,
+            start: Some(start),
+            ..Self::default()
+        }
+    }
+
+    /// Windows 客户机常用配置：localtime + rtc catchup，禁用 hpet，启用 hypervclock
+    pub fn windows_recommended() -> Self {
+        Self::localtime()
+            .add_timer(Timer::new(TimerName::Rtc).with_tickpolicy(TickPolicy::Catchup))
+            .add_timer(Timer::new(TimerName::Pit).with_tickpolicy(TickPolicy::Delay))
+            .add_timer(Timer::new(TimerName::Hpet).with_present(false))
+            .add_timer(Timer::new(TimerName::Hypervclock).with_present(true))
+    }
+
+    /// 添加定时器，同名定时器会被替换
+    pub fn add_timer(mut self, timer: Timer) -> Self {
+        self.timers.retain(|t| t.name != timer.name);
+        self.timers.push(timer);
+        self
+    }
+
+    pub fn get_timer(&self, name: TimerName) -> Option<&Timer> {
+        self.timers.iter().find(|t| t.name == name)
+    }
+
+    /// 检查定时器是否被显式启用
+    pub fn timer_present(&self, name: TimerName) -> bool {
+        self.get_timer(name).is_some_and(Timer::is_present)
+    }
+
+    /// 验证配置
+    ///
+    /// `features` 用于检查 hypervclock 所需的 Hyper-V enlightenment。
+    pub fn validate(&self, features: Option<&Features>) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if self.offset == ClockOffset::Timezone {
+            if self.timezone.as_deref().is_none_or(str::is_empty) {
+                errors
+                    .push("Clock offset 'timezone' requires the 'timezone' attribute".to_string());
+            }
+        } else if self.timezone.is_some() {
+            errors.push(format!(
+                "Clock attribute 'timezone' is only valid with offset='timezone', got '{}'",
+                self.offset
+            ));
+        }
+
+        if self.basis.is_some() && self.offset != ClockOffset::Variable {
+            errors.push(format!(
+                "Clock attribute 'basis' is only valid with offset='variable', got '{}'",
+                self.offset
+            ));
+        }
+
+        if self.offset == ClockOffset::Absolute {
+            if self.start.is_none() {
+                errors.push("Clock offset 'absolute' requires the 'start' attribute".to_string());
+            }
+        } else if self.start.is_some() {
+            errors.push(format!(
+                "Clock attribute 'start' is only valid with offset='absolute', got '{}'",
+                self.offset
+            ));
+        }
+
+        if let Some(adjustment) = &self.adjustment {
+            match self.offset {
+                // utc/localtime 接受 'reset' 或秒数
+                ClockOffset::Utc | ClockOffset::Localtime
+                    if adjustment != "reset" && adjustment.parse::<i64>().is_err() =>
+                {
+                    errors.push(format!(
+                        "Clock offset '{}' accepts adjustment='reset' or a number of seconds, got '{}'",
+                        self.offset, adjustment
+                    ));
+                }
+                ClockOffset::Variable if adjustment.parse::<i64>().is_err() => {
+                    errors.push(format!(
+                        "Clock adjustment must be a number of seconds, got '{}'",
+                        adjustment
+                    ));
+                }
+                ClockOffset::Timezone | ClockOffset::Absolute => errors.push(format!(
+                    "Clock attribute 'adjustment' is not valid with offset='{}'",
+                    self.offset
+                )),
+                _ => {}
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for timer in &self.timers {
+            if !seen.insert(timer.name) {
+                errors.push(format!("Duplicate timer '{}'", timer.name));
+            }
+            if let Err(mut e) = timer.validate() {
+                errors.append(&mut e);
+            }
+        }
+
+        if self.timer_present(TimerName::Hypervclock) {
+            let hyperv_enabled = features.and_then(|f| f.hyperv.as_ref()).is_some();
+            if !hyperv_enabled {
+                errors.push(
+                    "Timer 'hypervclock' requires Hyper-V enlightenments (<features><hyperv>)"
+                        .to_string(),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 定时器名称
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerName {
+    Platform,
+    Pit,
+    Rtc,
+    Hpet,
+    Tsc,
+    Kvmclock,
+    Hypervclock,
+    Armvtimer,
+}
+
+impl fmt::Display for TimerName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerName::Platform => write!(f, "platform"),
+            TimerName::Pit => write!(f, "pit"),
+            TimerName::Rtc => write!(f, "rtc"),
+            TimerName::Hpet => write!(f, "hpet"),
+            TimerName::Tsc => write!(f, "tsc"),
+            TimerName::Kvmclock => write!(f, "kvmclock"),
+            TimerName::Hypervclock => write!(f, "hypervclock"),
+            TimerName::Armvtimer => write!(f, "armvtimer"),
+        }
+    }
+}
+
+/// RTC 跟踪的时间源
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerTrack {
+    Boot,
+    Guest,
+    Wall,
+    Realtime,
+}
+
+/// 错过时钟中断时的处理策略
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TickPolicy {
+    Delay,
+    Catchup,
+    Merge,
+    Discard,
+}
+
+impl fmt::Display for TickPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TickPolicy::Delay => write!(f, "delay"),
+            TickPolicy::Catchup => write!(f, "catchup"),
+            TickPolicy::Merge => write!(f, "merge"),
+            TickPolicy::Discard => write!(f, "discard"),
+        }
+    }
+}
+
+/// TSC 模式
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TscMode {
+    Auto,
+    Native,
+    Emulate,
+    Paravirt,
+    Smpsafe,
+}
+
+/// catchup 策略参数
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct TimerCatchup {
+    #[serde(rename = "@threshold", skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u64>,
+
+    #[serde(rename = "@slew", skip_serializing_if = "Option::is_none")]
+    pub slew: Option<u64>,
+
+    #[serde(rename = "@limit", skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+/// 定时器配置
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Timer {
+    #[serde(rename = "@name")]
+    pub name: TimerName,
+
+    /// 仅 rtc
+    #[serde(rename = "@track", skip_serializing_if = "Option::is_none")]
+    pub track: Option<TimerTrack>,
+
+    #[serde(rename = "@tickpolicy", skip_serializing_if = "Option::is_none")]
+    pub tickpolicy: Option<TickPolicy>,
+
+    /// 仅 tsc，单位 Hz
+    #[serde(rename = "@frequency", skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<u64>,
+
+    /// 仅 tsc
+    #[serde(rename = "@mode", skip_serializing_if = "Option::is_none")]
+    pub mode: Option<TscMode>,
+
+    /// yes 或 no
+    #[serde(rename = "@present", skip_serializing_if = "Option::is_none")]
+    pub present: Option<String>,
+
+    #[serde(rename = "catchup", skip_serializing_if = "Option::is_none")]
+    pub catchup: Option<TimerCatchup>,
+}
+
+impl Timer {
+    pub fn new(name: TimerName) -> Self {
+        Self {
+            name,
+            track: None,
+            tickpolicy: None,
+            frequency: None,
+            mode: None,
+            present: None,
+            catchup: None,
+        }
+    }
+
+    pub fn with_tickpolicy(mut self, tickpolicy: TickPolicy) -> Self {
+        self.tickpolicy = Some(tickpolicy);
+        self
+    }
+
+    pub fn with_track(mut self, track: TimerTrack) -> Self {
+        self.track = Some(track);
+        self
+    }
+
+    pub fn with_present(mut self, present: bool) -> Self {
+        self.present = Some(if present { "yes" } else { "no" }.to_string());
+        self
+    }
+
+    pub fn with_tsc(mut self, frequency: Option<u64>, mode: TscMode) -> Self {
+        self.frequency = frequency;
+        self.mode = Some(mode);
+        self
+    }
+
+    /// present 未设置时由 hypervisor 决定，这里视为启用
+    pub fn is_present(&self) -> bool {
+        self.present.as_deref() != Some("no")
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if self.track.is_some() && self.name != TimerName::Rtc {
+            errors.push(format!(
+                "Timer '{}': 'track' is only supported for the rtc timer",
+                self.name
+            ));
+        }
+
+        if (self.frequency.is_some() || self.mode.is_some()) && self.name != TimerName::Tsc {
+            errors.push(format!(
+                "Timer '{}': 'frequency' and 'mode' are only supported for the tsc timer",
+                self.name
+            ));
+        }
+
+        if self.frequency.is_some() && self.mode.is_none() {
+            errors.push(format!(
+                "Timer '{}': 'frequency' requires 'mode' to be set",
+                self.name
+            ));
+        }
+
+        if self.frequency == Some(0) {
+            errors.push(format!("Timer '{}': 'frequency' cannot be 0", self.name));
+        }
+
+        if let Some(present) = &self.present
+            && present != "yes"
+            && present != "no"
+        {
+            errors.push(format!(
+                "Timer '{}': 'present' must be 'yes' or 'no', got '{}'",
+                self.name, present
+            ));
+        }
+
+        if self.catchup.is_some() && self.tickpolicy != Some(TickPolicy::Catchup) {
+            errors.push(format!(
+                "Timer '{}': <catchup> requires tickpolicy='catchup'",
+                self.name
+            ));
+        }
+
+        if let Some(tickpolicy) = self.tickpolicy
+            && matches!(
+                self.name,
+                TimerName::Tsc | TimerName::Kvmclock | TimerName::Hypervclock
+            )
+        {
+            errors.push(format!(
+                "Timer '{}' does not support tickpolicy '{}'",
+                self.name, tickpolicy
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,500 @@
+use super::CpuArch;
+
+/// 内置模型：(体系结构, 名称, 供应商, 父模型, 新增特性, 移除特性)
+///
+/// 同一体系结构内按代际从旧到新排列，特性名与 libvirt cpu_map 保持一致。
+pub(super) type ModelRow = (
+    CpuArch,
+    &'static str,
+    Option<&'static str>,
+    Option<&'static str>,
+    &'static [&'static str],
+    &'static [&'static str],
+);
+
+const X86_BASE: &[&str] = &[
+    "fpu", "de", "pse", "tsc", "msr", "pae", "mce", "cx8", "apic", "sep", "mtrr", "pge", "mca",
+    "cmov", "pat", "pse36", "clflush", "mmx", "fxsr", "sse", "sse2", "pni", "cx16", "syscall",
+    "nx", "lm", "lahf_lm",
+];
+
+const TSX: &[&str] = &["hle", "rtm"];
+
+pub(super) const MODELS: &[ModelRow] = &[
+    // x86 通用 / Intel
+    (CpuArch::X86, "qemu64", None, None, X86_BASE, &[]),
+    (CpuArch::X86, "kvm64", None, Some("qemu64"), &[], &[]),
+    (
+        CpuArch::X86,
+        "core2duo",
+        Some("Intel"),
+        Some("qemu64"),
+        &["ssse3"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Conroe",
+        Some("Intel"),
+        Some("qemu64"),
+        &["ssse3"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Penryn",
+        Some("Intel"),
+        Some("Conroe"),
+        &["sse4.1"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Nehalem",
+        Some("Intel"),
+        Some("Penryn"),
+        &["sse4.2", "popcnt"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Westmere",
+        Some("Intel"),
+        Some("Nehalem"),
+        &["aes", "pclmuldq"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "SandyBridge",
+        Some("Intel"),
+        Some("Westmere"),
+        &[
+            "avx",
+            "xsave",
+            "xsaveopt",
+            "tsc-deadline",
+            "x2apic",
+            "rdtscp",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "IvyBridge",
+        Some("Intel"),
+        Some("SandyBridge"),
+        &["f16c", "rdrand", "fsgsbase", "smep", "erms"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Haswell",
+        Some("Intel"),
+        Some("IvyBridge"),
+        &[
+            "fma", "pcid", "movbe", "bmi1", "bmi2", "avx2", "invpcid", "abm", "hle", "rtm",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Haswell-noTSX",
+        Some("Intel"),
+        Some("Haswell"),
+        &[],
+        TSX,
+    ),
+    (
+        CpuArch::X86,
+        "Broadwell",
+        Some("Intel"),
+        Some("Haswell"),
+        &["rdseed", "adx", "smap", "3dnowprefetch"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Broadwell-noTSX",
+        Some("Intel"),
+        Some("Broadwell"),
+        &[],
+        TSX,
+    ),
+    (
+        CpuArch::X86,
+        "Skylake-Client",
+        Some("Intel"),
+        Some("Broadwell"),
+        &["clflushopt", "xsavec", "xgetbv1"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Skylake-Client-noTSX",
+        Some("Intel"),
+        Some("Skylake-Client"),
+        &[],
+        TSX,
+    ),
+    (
+        CpuArch::X86,
+        "Skylake-Server",
+        Some("Intel"),
+        Some("Skylake-Client"),
+        &[
+            "pku", "clwb", "pdpe1gb", "avx512f", "avx512dq", "avx512cd", "avx512bw", "avx512vl",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Skylake-Server-noTSX",
+        Some("Intel"),
+        Some("Skylake-Server"),
+        &[],
+        TSX,
+    ),
+    (
+        CpuArch::X86,
+        "Cascadelake-Server",
+        Some("Intel"),
+        Some("Skylake-Server"),
+        &["avx512vnni"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Cascadelake-Server-noTSX",
+        Some("Intel"),
+        Some("Cascadelake-Server"),
+        &[],
+        TSX,
+    ),
+    (
+        CpuArch::X86,
+        "Cooperlake",
+        Some("Intel"),
+        Some("Cascadelake-Server"),
+        &["avx512-bf16"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Icelake-Server",
+        Some("Intel"),
+        Some("Cascadelake-Server"),
+        &[
+            "avx512vbmi",
+            "avx512vbmi2",
+            "avx512bitalg",
+            "avx512-vpopcntdq",
+            "umip",
+            "gfni",
+            "vaes",
+            "vpclmulqdq",
+            "la57",
+            "rdpid",
+            "wbnoinvd",
+            "sha-ni",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "Icelake-Server-noTSX",
+        Some("Intel"),
+        Some("Icelake-Server"),
+        &[],
+        TSX,
+    ),
+    (
+        CpuArch::X86,
+        "SapphireRapids",
+        Some("Intel"),
+        Some("Icelake-Server"),
+        &[
+            "avx512ifma",
+            "avx512-bf16",
+            "avx512-fp16",
+            "avx-vnni",
+            "amx-bf16",
+            "amx-tile",
+            "amx-int8",
+            "serialize",
+            "tsx-ldtrk",
+            "movdiri",
+            "movdir64b",
+            "fsrm",
+            "cldemote",
+        ],
+        &[],
+    ),
+    // x86 AMD
+    (
+        CpuArch::X86,
+        "EPYC",
+        Some("AMD"),
+        Some("qemu64"),
+        &[
+            "ssse3",
+            "sse4.1",
+            "sse4.2",
+            "popcnt",
+            "aes",
+            "pclmuldq",
+            "avx",
+            "avx2",
+            "fma",
+            "f16c",
+            "xsave",
+            "xsaveopt",
+            "xsavec",
+            "xgetbv1",
+            "rdrand",
+            "rdseed",
+            "fsgsbase",
+            "bmi1",
+            "bmi2",
+            "adx",
+            "smep",
+            "smap",
+            "movbe",
+            "abm",
+            "clflushopt",
+            "sha-ni",
+            "sse4a",
+            "misalignsse",
+            "3dnowprefetch",
+            "osvw",
+            "topoext",
+            "perfctr_core",
+            "pdpe1gb",
+            "rdtscp",
+            "cr8legacy",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "EPYC-Rome",
+        Some("AMD"),
+        Some("EPYC"),
+        &["clwb", "umip", "rdpid", "wbnoinvd", "clzero", "xsaveerptr"],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "EPYC-Milan",
+        Some("AMD"),
+        Some("EPYC-Rome"),
+        &[
+            "pku",
+            "erms",
+            "fsrm",
+            "pcid",
+            "invpcid",
+            "vaes",
+            "vpclmulqdq",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::X86,
+        "EPYC-Genoa",
+        Some("AMD"),
+        Some("EPYC-Milan"),
+        &[
+            "avx512f",
+            "avx512dq",
+            "avx512ifma",
+            "avx512cd",
+            "avx512bw",
+            "avx512vl",
+            "avx512vbmi",
+            "avx512vbmi2",
+            "avx512vnni",
+            "avx512bitalg",
+            "avx512-vpopcntdq",
+            "avx512-bf16",
+            "gfni",
+            "la57",
+        ],
+        &[],
+    ),
+    // aarch64
+    (
+        CpuArch::Arm,
+        "cortex-a53",
+        Some("ARM"),
+        None,
+        &[
+            "fp", "asimd", "evtstrm", "aes", "pmull", "sha1", "sha2", "crc32", "cpuid",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::Arm,
+        "cortex-a57",
+        Some("ARM"),
+        Some("cortex-a53"),
+        &[],
+        &[],
+    ),
+    (
+        CpuArch::Arm,
+        "cortex-a72",
+        Some("ARM"),
+        Some("cortex-a57"),
+        &[],
+        &[],
+    ),
+    (
+        CpuArch::Arm,
+        "Neoverse-N1",
+        Some("ARM"),
+        Some("cortex-a72"),
+        &[
+            "atomics", "fphp", "asimdhp", "asimdrdm", "lrcpc", "dcpop", "asimddp", "ssbs",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::Arm,
+        "Neoverse-V1",
+        Some("ARM"),
+        Some("Neoverse-N1"),
+        &[
+            "sha3", "sm3", "sm4", "sha512", "asimdfhm", "dit", "uscat", "ilrcpc", "flagm", "sb",
+            "paca", "pacg", "dcpodp", "sve", "i8mm", "bf16", "rng",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::Arm,
+        "Neoverse-N2",
+        Some("ARM"),
+        Some("Neoverse-V1"),
+        &["sve2", "svei8mm", "svebf16", "flagm2", "frint"],
+        &[],
+    ),
+    // ppc64：模型由 PVR 区分，没有独立的特性位
+    (CpuArch::Ppc64, "POWER8", Some("IBM"), None, &[], &[]),
+    (CpuArch::Ppc64, "POWER9", Some("IBM"), None, &[], &[]),
+    (CpuArch::Ppc64, "POWER10", Some("IBM"), None, &[], &[]),
+    // s390x
+    (
+        CpuArch::S390,
+        "z13",
+        Some("IBM"),
+        None,
+        &[
+            "esan3", "zarch", "stfle", "msa", "ldisp", "eimm", "dfp", "edat", "etf3eh", "highgprs",
+            "te", "vx",
+        ],
+        &[],
+    ),
+    (
+        CpuArch::S390,
+        "z14",
+        Some("IBM"),
+        Some("z13"),
+        &["vxd", "vxe", "gs"],
+        &[],
+    ),
+    (
+        CpuArch::S390,
+        "z15",
+        Some("IBM"),
+        Some("z14"),
+        &["vxe2", "vxp", "sort", "dflt"],
+        &[],
+    ),
+    (
+        CpuArch::S390,
+        "z16",
+        Some("IBM"),
+        Some("z15"),
+        &["nnpa", "pai"],
+        &[],
+    ),
+];
+
+/// 不属于任何内置模型、但可以在 `<feature>` 中使用的特性
+pub(super) const EXTRA_FEATURES: &[(CpuArch, &[&str])] = &[
+    (
+        CpuArch::X86,
+        &[
+            "vme",
+            "ds",
+            "acpi",
+            "ss",
+            "ht",
+            "tm",
+            "pbe",
+            "monitor",
+            "ds_cpl",
+            "vmx",
+            "svm",
+            "smx",
+            "est",
+            "tm2",
+            "xtpr",
+            "pdcm",
+            "dca",
+            "hypervisor",
+            "arat",
+            "invtsc",
+            "ibpb",
+            "ibrs",
+            "stibp",
+            "ssbd",
+            "virt-ssbd",
+            "amd-ssbd",
+            "amd-stibp",
+            "spec-ctrl",
+            "md-clear",
+            "arch-capabilities",
+            "cmp_legacy",
+            "extapic",
+            "npt",
+            "nrip-save",
+            "tsc_adjust",
+            "xsaves",
+            "avx512-4vnniw",
+            "avx512-4fmaps",
+            "kvmclock",
+            "kvm_pv_eoi",
+            "kvm_pv_unhalt",
+        ],
+    ),
+    (CpuArch::Arm, &["pmu", "sve128", "sve256", "sve512", "mte"]),
+    (CpuArch::S390, &["sie"]),
+];
+
+/// `/proc/cpuinfo` 中的标志名到 libvirt 特性名的映射
+pub(super) const PROC_ALIASES: &[(&str, &str)] = &[
+    ("sse4_1", "sse4.1"),
+    ("sse4_2", "sse4.2"),
+    ("pclmulqdq", "pclmuldq"),
+    ("sha_ni", "sha-ni"),
+    ("tsc_deadline_timer", "tsc-deadline"),
+    ("cr8_legacy", "cr8legacy"),
+    ("avx512_vnni", "avx512vnni"),
+    ("avx512_bf16", "avx512-bf16"),
+    ("avx512_fp16", "avx512-fp16"),
+    ("avx512_vbmi2", "avx512vbmi2"),
+    ("avx512_bitalg", "avx512bitalg"),
+    ("avx512_vpopcntdq", "avx512-vpopcntdq"),
+    ("avx_vnni", "avx-vnni"),
+    ("amx_bf16", "amx-bf16"),
+    ("amx_tile", "amx-tile"),
+    ("amx_int8", "amx-int8"),
+    ("tsxldtrk", "tsx-ldtrk"),
+    ("nonstop_tsc", "invtsc"),
+    ("md_clear", "md-clear"),
+    ("amd_ssbd", "amd-ssbd"),
+    ("amd_stibp", "amd-stibp"),
+    ("virt_ssbd", "virt-ssbd"),
+    ("arch_capabilities", "arch-capabilities"),
+];
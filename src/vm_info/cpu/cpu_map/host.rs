@@ -0,0 +1,127 @@
+use super::{CpuArch, CpuMap};
+use crate::vm_info::cpu::CpuConfig;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// 从 `/proc/cpuinfo` 读取的宿主机 CPU 信息
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct HostCpuInfo {
+    pub arch: Option<CpuArch>,
+    pub vendor: Option<String>,
+    /// x86 的 model name，或 ppc64 的 cpu 行（如 POWER9）
+    pub model_name: Option<String>,
+    /// 原始标志名
+    pub flags: BTreeSet<String>,
+}
+
+impl HostCpuInfo {
+    pub fn from_proc_cpuinfo<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Self::parse(&content))
+    }
+
+    /// 解析 cpuinfo 文本，只使用各字段第一次出现的值，标志取所有处理器的并集
+    pub fn parse(content: &str) -> Self {
+        let mut info = HostCpuInfo::default();
+
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim();
+
+            match key {
+                // x86
+                "flags" => {
+                    info.arch.get_or_insert(CpuArch::X86);
+                    info.flags
+                        .extend(value.split_whitespace().map(str::to_string));
+                }
+                // aarch64
+                "Features" => {
+                    info.arch.get_or_insert(CpuArch::Arm);
+                    info.flags
+                        .extend(value.split_whitespace().map(str::to_string));
+                }
+                // s390x
+                "features" => {
+                    info.arch.get_or_insert(CpuArch::S390);
+                    info.flags
+                        .extend(value.split_whitespace().map(str::to_string));
+                }
+                "vendor_id" if info.vendor.is_none() => {
+                    info.vendor = Some(value.to_string());
+                }
+                "model name" if info.model_name.is_none() => {
+                    info.model_name = Some(value.to_string());
+                }
+                // ppc64：cpu : POWER9 (architected), altivec supported
+                "cpu" if info.model_name.is_none() && value.starts_with("POWER") => {
+                    info.arch.get_or_insert(CpuArch::Ppc64);
+                    info.vendor.get_or_insert_with(|| "IBM".to_string());
+                    let model = value
+                        .split(|c: char| c.is_whitespace() || c == ',')
+                        .next()
+                        .unwrap_or(value);
+                    info.model_name = Some(model.to_string());
+                }
+                "CPU implementer" if info.vendor.is_none() => {
+                    info.vendor = Some(value.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        if info.arch == Some(CpuArch::S390) {
+            info.vendor.get_or_insert_with(|| "IBM".to_string());
+        }
+
+        info
+    }
+
+    /// 转换为 cpu_map 特性名后的标志集合
+    pub fn normalized_flags(&self, map: &CpuMap) -> BTreeSet<String> {
+        self.flags
+            .iter()
+            .map(|f| map.normalize_flag(f).to_string())
+            .collect()
+    }
+
+    /// 转换为等价的 custom CPU 定义，可用于集群基线计算
+    pub fn to_cpu_config(&self, map: &CpuMap) -> Result<CpuConfig, String> {
+        let arch = self
+            .arch
+            .ok_or_else(|| "Cannot determine host CPU architecture".to_string())?;
+
+        if arch == CpuArch::Ppc64 {
+            let name = self
+                .model_name
+                .as_deref()
+                .ok_or_else(|| "Host CPU model is unknown".to_string())?;
+            return Ok(CpuConfig::custom().with_model(crate::vm_info::cpu::CpuModel::new(name)));
+        }
+
+        Ok(CpuConfig::from_feature_set(
+            map,
+            arch,
+            self.vendor_name(),
+            &self.normalized_flags(map),
+        ))
+    }
+
+    /// 统一的供应商名（Intel、AMD、ARM、IBM）
+    pub fn vendor_name(&self) -> Option<&str> {
+        match self.vendor.as_deref()? {
+            "GenuineIntel" => Some("Intel"),
+            "AuthenticAMD" => Some("AMD"),
+            "HygonGenuine" => Some("Hygon"),
+            "0x41" => Some("ARM"),
+            "IBM/S390" | "IBM" => Some("IBM"),
+            other => Some(other),
+        }
+    }
+}
@@ -0,0 +1,335 @@
+mod builtin;
+mod host;
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub use host::HostCpuInfo;
+
+/// cpu_map 中的体系结构
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum CpuArch {
+    X86,
+    Arm,
+    Ppc64,
+    S390,
+}
+
+impl CpuArch {
+    /// 由 `os.type@arch` 或 cpu_map 中的 arch 名称解析
+    pub fn from_arch(arch: &str) -> Option<Self> {
+        match arch {
+            "x86" | "i386" | "i686" | "x86_64" => Some(CpuArch::X86),
+            "arm" | "aarch64" | "armv7l" | "armv6l" => Some(CpuArch::Arm),
+            "ppc64" | "ppc64le" => Some(CpuArch::Ppc64),
+            "s390" | "s390x" => Some(CpuArch::S390),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CpuArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpuArch::X86 => write!(f, "x86"),
+            CpuArch::Arm => write!(f, "arm"),
+            CpuArch::Ppc64 => write!(f, "ppc64"),
+            CpuArch::S390 => write!(f, "s390"),
+        }
+    }
+}
+
+/// CPU 比较结果（对应 virCPUCompareResult）
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CpuCompareResult {
+    /// 宿主机无法运行该 CPU 定义
+    Incompatible,
+    /// 宿主机与 CPU 定义完全一致
+    Identical,
+    /// 宿主机提供的特性多于 CPU 定义
+    Superset,
+}
+
+impl fmt::Display for CpuCompareResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpuCompareResult::Incompatible => write!(f, "incompatible"),
+            CpuCompareResult::Identical => write!(f, "identical"),
+            CpuCompareResult::Superset => write!(f, "superset"),
+        }
+    }
+}
+
+/// CPU 比较详情
+#[derive(Debug, PartialEq, Clone)]
+pub struct CpuComparison {
+    pub result: CpuCompareResult,
+    /// 客户机需要但宿主机缺少的特性
+    pub missing: Vec<String>,
+    /// policy='forbid' 但宿主机具备的特性
+    pub forbidden: Vec<String>,
+}
+
+/// cpu_map 中的一个 CPU 模型定义
+#[derive(Debug, PartialEq, Clone)]
+pub struct CpuModelDef {
+    pub name: String,
+    pub arch: CpuArch,
+    pub vendor: Option<String>,
+    /// 继承的模型
+    pub parent: Option<String>,
+    /// 在父模型基础上新增的特性
+    pub features: BTreeSet<String>,
+    /// 从父模型中移除的特性
+    pub removed: BTreeSet<String>,
+}
+
+/// CPU 模型与特性数据库
+#[derive(Debug, Clone, Default)]
+pub struct CpuMap {
+    /// 按加载顺序保存，同一体系结构内越靠后越新
+    models: Vec<CpuModelDef>,
+    features: BTreeMap<CpuArch, BTreeSet<String>>,
+    /// /proc/cpuinfo 标志名 -> cpu_map 特性名
+    aliases: BTreeMap<String, String>,
+}
+
+// libvirt cpu_map XML 的最小映射
+#[derive(Debug, Deserialize, Default)]
+struct RawCpus {
+    #[serde(rename = "arch", default)]
+    arches: Vec<RawArch>,
+    #[serde(rename = "feature", default)]
+    features: Vec<RawNamed>,
+    #[serde(rename = "model", default)]
+    models: Vec<RawModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawArch {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "feature", default)]
+    features: Vec<RawNamed>,
+    #[serde(rename = "model", default)]
+    models: Vec<RawModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawNamed {
+    #[serde(rename = "@name")]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawModel {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "vendor")]
+    vendor: Option<RawNamed>,
+    #[serde(rename = "model")]
+    parent: Option<RawNamed>,
+    #[serde(rename = "feature", default)]
+    features: Vec<RawNamed>,
+}
+
+impl CpuMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 内置数据库（x86、aarch64、ppc64、s390x）
+    pub fn builtin() -> &'static CpuMap {
+        static BUILTIN: OnceLock<CpuMap> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut map = CpuMap::new();
+            for (arch, name, vendor, parent, added, removed) in builtin::MODELS {
+                map.add_model(CpuModelDef {
+                    name: name.to_string(),
+                    arch: *arch,
+                    vendor: vendor.map(str::to_string),
+                    parent: parent.map(str::to_string),
+                    features: added.iter().map(|f| f.to_string()).collect(),
+                    removed: removed.iter().map(|f| f.to_string()).collect(),
+                });
+            }
+            for (arch, names) in builtin::EXTRA_FEATURES {
+                for name in *names {
+                    map.add_feature(*arch, name);
+                }
+            }
+            for (from, to) in builtin::PROC_ALIASES {
+                map.aliases.insert(from.to_string(), to.to_string());
+            }
+            map
+        })
+    }
+
+    /// 添加（或替换同名）模型，其特性自动登记为已知特性
+    pub fn add_model(&mut self, model: CpuModelDef) {
+        for feature in model.features.iter().chain(model.removed.iter()) {
+            self.add_feature(model.arch, feature);
+        }
+        self.models
+            .retain(|m| !(m.arch == model.arch && m.name == model.name));
+        self.models.push(model);
+    }
+
+    pub fn add_feature(&mut self, arch: CpuArch, name: &str) {
+        self.features
+            .entry(arch)
+            .or_default()
+            .insert(name.to_string());
+    }
+
+    /// 解析 libvirt cpu_map XML（旧版单文件 cpu_map.xml 或新版 x86_*.xml 等）
+    ///
+    /// 文件中没有 `<arch>` 包裹时使用 `default_arch`。
+    pub fn load_xml(&mut self, xml: &str, default_arch: Option<CpuArch>) -> Result<(), String> {
+        let raw: RawCpus = quick_xml::de::from_str(xml)
+            .map_err(|e| format!("Failed to parse cpu_map XML: {}", e))?;
+
+        for raw_arch in raw.arches {
+            let Some(arch) = CpuArch::from_arch(&raw_arch.name) else {
+                continue;
+            };
+            self.load_raw(arch, raw_arch.features, raw_arch.models);
+        }
+
+        if !raw.features.is_empty() || !raw.models.is_empty() {
+            let arch = default_arch
+                .ok_or_else(|| "cpu_map XML has no <arch> and no default arch given".to_string())?;
+            self.load_raw(arch, raw.features, raw.models);
+        }
+
+        Ok(())
+    }
+
+    /// 加载 libvirt cpu_map 目录（如 /usr/share/libvirt/cpu_map）
+    ///
+    /// 体系结构由文件名前缀推断（x86_、arm_、ppc64_），index.xml 会被跳过。
+    pub fn load_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), Vec<String>> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir)
+            .map_err(|e| vec![format!("Failed to read {}: {}", dir.display(), e)])?;
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "xml"))
+            .collect();
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            if file_name == "index.xml" {
+                continue;
+            }
+            let default_arch = file_name
+                .split_once('_')
+                .and_then(|(prefix, _)| CpuArch::from_arch(prefix));
+
+            let result = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|xml| self.load_xml(&xml, default_arch));
+            if let Err(e) = result {
+                errors.push(format!("{}: {}", path.display(), e));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn load_raw(&mut self, arch: CpuArch, features: Vec<RawNamed>, models: Vec<RawModel>) {
+        for feature in features {
+            self.add_feature(arch, &feature.name);
+        }
+        for model in models {
+            self.add_model(CpuModelDef {
+                name: model.name,
+                arch,
+                vendor: model.vendor.map(|v| v.name),
+                parent: model.parent.map(|p| p.name),
+                features: model.features.into_iter().map(|f| f.name).collect(),
+                removed: BTreeSet::new(),
+            });
+        }
+    }
+
+    /// 查找模型；`arch` 为 None 时在所有体系结构中查找
+    pub fn model(&self, name: &str, arch: Option<CpuArch>) -> Option<&CpuModelDef> {
+        self.models
+            .iter()
+            .rev()
+            .find(|m| m.name == name && arch.is_none_or(|a| a == m.arch))
+    }
+
+    /// 某体系结构下的全部模型（从旧到新）
+    pub fn models(&self, arch: CpuArch) -> impl Iterator<Item = &CpuModelDef> {
+        self.models.iter().filter(move |m| m.arch == arch)
+    }
+
+    /// 模型展开后的完整特性集合（包含继承）
+    pub fn model_features(&self, name: &str, arch: Option<CpuArch>) -> Option<BTreeSet<String>> {
+        let mut chain = Vec::new();
+        let mut current = self.model(name, arch)?;
+        loop {
+            // 防止循环继承
+            if chain.iter().any(|m: &&CpuModelDef| m.name == current.name) {
+                break;
+            }
+            chain.push(current);
+            match &current.parent {
+                Some(parent) => match self.model(parent, Some(current.arch)) {
+                    Some(next) => current = next,
+                    None => break,
+                },
+                None => break,
+            }
+        }
+
+        let mut features = BTreeSet::new();
+        for model in chain.iter().rev() {
+            features.extend(model.features.iter().cloned());
+            for removed in &model.removed {
+                features.remove(removed);
+            }
+        }
+        Some(features)
+    }
+
+    /// 模型的供应商（沿继承链查找）
+    pub fn model_vendor(&self, name: &str, arch: Option<CpuArch>) -> Option<&str> {
+        let mut current = self.model(name, arch)?;
+        for _ in 0..self.models.len() {
+            if let Some(vendor) = &current.vendor {
+                return Some(vendor);
+            }
+            current = self.model(current.parent.as_deref()?, Some(current.arch))?;
+        }
+        None
+    }
+
+    pub fn is_known_feature(&self, name: &str, arch: Option<CpuArch>) -> bool {
+        match arch {
+            Some(arch) => self.features.get(&arch).is_some_and(|f| f.contains(name)),
+            None => self.features.values().any(|f| f.contains(name)),
+        }
+    }
+
+    /// 将 /proc/cpuinfo 中的标志名转换为 cpu_map 特性名
+    pub fn normalize_flag<'a>(&'a self, flag: &'a str) -> &'a str {
+        self.aliases.get(flag).map(String::as_str).unwrap_or(flag)
+    }
+}
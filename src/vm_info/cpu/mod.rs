@@ -1,4 +1,5 @@
 mod cache;
+mod cpu_map;
 mod cpu_match;
 mod cpu_mode;
 mod feature;
@@ -7,16 +8,17 @@ mod model;
 mod numa;
 mod topology;
 
-use crate::vm_info::cpu::maxphysaddr::MaxPhysAddr;
-use cache::*;
-use cpu_match::*;
-use cpu_mode::*;
-use feature::*;
-use model::CpuModel;
+pub use cache::*;
+pub use cpu_map::{CpuArch, CpuCompareResult, CpuComparison, CpuMap, CpuModelDef, HostCpuInfo};
+pub use cpu_match::*;
+pub use cpu_mode::*;
+pub use feature::*;
+pub use maxphysaddr::{MaxPhysAddr, MaxPhysAddrMode};
+pub use model::CpuModel;
 pub use numa::NumaTopology;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use topology::CpuTopology;
+use std::collections::{BTreeSet, HashMap};
+pub use topology::CpuTopology;
 
 /// CPU 检查模式
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
@@ -178,6 +180,30 @@ impl CpuConfig {
         self
     }
 
+    /// 检查客户机是否能看到指定的 CPU 特性
+    ///
+    /// host-model/host-passthrough/maximum 视为暴露宿主机特性；
+    /// custom 模式下需要显式以 force/require/optional 策略声明。
+    pub fn provides_feature(&self, name: &str) -> bool {
+        if matches!(
+            self.mode,
+            Some(CpuMode::HostModel | CpuMode::HostPassthrough | CpuMode::Maximum)
+        ) {
+            let disabled = self.features.iter().flatten().any(|f| {
+                f.name == name && matches!(f.policy, FeaturePolicy::Disable | FeaturePolicy::Forbid)
+            });
+            return !disabled;
+        }
+
+        self.features.iter().flatten().any(|f| {
+            f.name == name
+                && matches!(
+                    f.policy,
+                    FeaturePolicy::Force | FeaturePolicy::Require | FeaturePolicy::Optional
+                )
+        })
+    }
+
     /// 设置缓存配置
     pub fn with_cache(mut self, cache: CpuCache) -> Self {
         self.cache = Some(cache);
@@ -416,3 +442,336 @@ impl CpuConfig {
         }
     }
 }
+
+impl CpuConfig {
+    /// 推断 CPU 定义所属的体系结构
+    fn resolve_arch(&self, map: &CpuMap, arch: Option<CpuArch>) -> Option<CpuArch> {
+        arch.or_else(|| {
+            let name = self.model.as_ref()?.name.as_str();
+            map.model(name, None).map(|m| m.arch)
+        })
+    }
+
+    fn is_custom(&self) -> bool {
+        matches!(self.mode, None | Some(CpuMode::Custom))
+    }
+
+    /// 按 cpu_map 检查模型与特性名称
+    pub fn validate_names(&self, map: &CpuMap, arch: Option<CpuArch>) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let arch = self.resolve_arch(map, arch);
+
+        if self.is_custom()
+            && let Some(model) = &self.model
+            && !model.name.is_empty()
+            && map.model(&model.name, arch).is_none()
+        {
+            match arch {
+                Some(arch) => errors.push(format!(
+                    "Unknown CPU model '{}' for architecture '{}'",
+                    model.name, arch
+                )),
+                None => errors.push(format!("Unknown CPU model '{}'", model.name)),
+            }
+        }
+
+        for feature in self.features.iter().flatten() {
+            if !map.is_known_feature(&feature.name, arch) {
+                errors.push(format!("Unknown CPU feature '{}'", feature.name));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// custom 模式下客户机可见的完整特性集合，包括宿主机支持时才启用的 optional 特性
+    pub fn effective_features(
+        &self,
+        map: &CpuMap,
+        arch: Option<CpuArch>,
+    ) -> Result<BTreeSet<String>, String> {
+        let (mut required, optional) = self.split_features(map, arch)?;
+        required.extend(optional);
+        Ok(required)
+    }
+
+    /// 拆分为必需特性与 optional 特性
+    ///
+    /// 模型自带但被声明为 optional 的特性同样视为 optional。
+    fn split_features(
+        &self,
+        map: &CpuMap,
+        arch: Option<CpuArch>,
+    ) -> Result<(BTreeSet<String>, BTreeSet<String>), String> {
+        if !self.is_custom() {
+            return Err(format!(
+                "CPU mode '{}' depends on the host CPU and cannot be expanded",
+                self.mode.unwrap_or_default()
+            ));
+        }
+
+        let arch = self.resolve_arch(map, arch);
+        let mut features = match &self.model {
+            Some(model) if !model.name.is_empty() => map
+                .model_features(&model.name, arch)
+                .ok_or_else(|| format!("Unknown CPU model '{}'", model.name))?,
+            _ => BTreeSet::new(),
+        };
+
+        let mut optional = BTreeSet::new();
+        for feature in self.features.iter().flatten() {
+            match feature.policy {
+                FeaturePolicy::Force | FeaturePolicy::Require => {
+                    optional.remove(&feature.name);
+                    features.insert(feature.name.clone());
+                }
+                FeaturePolicy::Optional => {
+                    features.remove(&feature.name);
+                    optional.insert(feature.name.clone());
+                }
+                FeaturePolicy::Disable | FeaturePolicy::Forbid => {
+                    features.remove(&feature.name);
+                    optional.remove(&feature.name);
+                }
+            }
+        }
+
+        Ok((features, optional))
+    }
+
+    /// 使用内置数据库展开模型为完整特性列表
+    pub fn expand(&self) -> Result<CpuConfig, String> {
+        self.expand_with(CpuMap::builtin(), None)
+    }
+
+    /// 展开模型：保留模型名，列出其全部特性（类似 virsh cpu-baseline --features）
+    pub fn expand_with(&self, map: &CpuMap, arch: Option<CpuArch>) -> Result<CpuConfig, String> {
+        let (required, _) = self.split_features(map, arch)?;
+        let mut features: Vec<CpuFeature> = required
+            .iter()
+            .map(|name| CpuFeature::new(name, FeaturePolicy::Require))
+            .collect();
+
+        // 保留 optional 与显式禁用的特性
+        for feature in self.features.iter().flatten() {
+            if matches!(
+                feature.policy,
+                FeaturePolicy::Optional | FeaturePolicy::Disable | FeaturePolicy::Forbid
+            ) {
+                features.push(feature.clone());
+            }
+        }
+
+        let mut expanded = self.clone();
+        expanded.features = if features.is_empty() {
+            None
+        } else {
+            Some(features)
+        };
+        Ok(expanded)
+    }
+
+    /// 使用内置数据库计算集群的基线 CPU
+    pub fn baseline(cpus: &[CpuConfig]) -> Result<CpuConfig, String> {
+        Self::baseline_with(CpuMap::builtin(), cpus)
+    }
+
+    /// 计算所有 CPU 定义都能运行的最大公共模型（类似 virsh hypervisor-cpu-baseline）
+    pub fn baseline_with(map: &CpuMap, cpus: &[CpuConfig]) -> Result<CpuConfig, String> {
+        if cpus.is_empty() {
+            return Err("Cannot compute baseline of an empty CPU list".to_string());
+        }
+
+        let arches: BTreeSet<CpuArch> = cpus
+            .iter()
+            .filter_map(|cpu| cpu.resolve_arch(map, None))
+            .collect();
+        if arches.len() > 1 {
+            return Err("Cannot compute baseline across different architectures".to_string());
+        }
+        let arch = arches
+            .into_iter()
+            .next()
+            .ok_or_else(|| "Cannot determine CPU architecture for baseline".to_string())?;
+
+        let vendors: BTreeSet<String> = cpus
+            .iter()
+            .filter_map(|cpu| {
+                cpu.vendor.clone().or_else(|| {
+                    let model = cpu.model.as_ref()?;
+                    map.model_vendor(&model.name, Some(arch))
+                        .map(str::to_string)
+                })
+            })
+            .collect();
+        let vendor = if vendors.len() == 1 {
+            vendors.into_iter().next()
+        } else {
+            None
+        };
+
+        // ppc64 模型没有特性位，取最旧的一代
+        if arch == CpuArch::Ppc64 {
+            let generations: Vec<&CpuModelDef> = map.models(arch).collect();
+            let oldest = cpus
+                .iter()
+                .map(|cpu| {
+                    let name = cpu.model.as_ref().map(|m| m.name.as_str()).unwrap_or("");
+                    generations
+                        .iter()
+                        .position(|m| m.name == name)
+                        .ok_or_else(|| format!("Unknown CPU model '{}'", name))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .min()
+                .unwrap_or(0);
+            return Ok(Self::custom().with_match_mode(CpuMatch::Exact).with_model(
+                CpuModel::new(&generations[oldest].name).with_fallback(FallbackPolicy::Forbid),
+            ));
+        }
+
+        let mut common: Option<BTreeSet<String>> = None;
+        // optional 特性不保证存在，不计入基线
+        for cpu in cpus {
+            let (features, _) = cpu.split_features(map, Some(arch))?;
+            common = Some(match common {
+                Some(current) => current.intersection(&features).cloned().collect(),
+                None => features,
+            });
+        }
+
+        Ok(Self::from_feature_set(
+            map,
+            arch,
+            vendor.as_deref(),
+            &common.unwrap_or_default(),
+        ))
+    }
+
+    /// 选择特性集合所能容纳的最大模型，其余特性以 require 列出
+    pub(crate) fn from_feature_set(
+        map: &CpuMap,
+        arch: CpuArch,
+        vendor: Option<&str>,
+        features: &BTreeSet<String>,
+    ) -> CpuConfig {
+        let mut best: Option<(&CpuModelDef, BTreeSet<String>)> = None;
+        for model in map.models(arch) {
+            let model_vendor = map.model_vendor(&model.name, Some(arch));
+            if let (Some(vendor), Some(model_vendor)) = (vendor, model_vendor)
+                && vendor != model_vendor
+            {
+                continue;
+            }
+            let Some(model_features) = map.model_features(&model.name, Some(arch)) else {
+                continue;
+            };
+            if model_features.is_empty() || !model_features.is_subset(features) {
+                continue;
+            }
+            // 特性数相同时选择更新的模型
+            if best
+                .as_ref()
+                .is_none_or(|(_, current)| model_features.len() >= current.len())
+            {
+                best = Some((model, model_features));
+            }
+        }
+
+        let mut cpu = Self::custom().with_match_mode(CpuMatch::Exact);
+        if let Some(vendor) = vendor {
+            cpu = cpu.with_vendor(vendor);
+        }
+
+        let model_features = match best {
+            Some((model, model_features)) => {
+                cpu = cpu
+                    .with_model(CpuModel::new(&model.name).with_fallback(FallbackPolicy::Forbid));
+                model_features
+            }
+            None => BTreeSet::new(),
+        };
+
+        let extra: Vec<CpuFeature> = features
+            .difference(&model_features)
+            .filter(|name| map.is_known_feature(name, Some(arch)))
+            .map(|name| CpuFeature::new(name, FeaturePolicy::Require))
+            .collect();
+        if !extra.is_empty() {
+            cpu = cpu.with_features(extra);
+        }
+
+        cpu
+    }
+
+    /// 使用内置数据库与宿主机 CPU 比较
+    pub fn compare(&self, host: &HostCpuInfo) -> Result<CpuComparison, String> {
+        self.compare_with(CpuMap::builtin(), host)
+    }
+
+    /// 检查宿主机能否运行该 CPU 定义（类似 virsh cpu-compare）
+    pub fn compare_with(&self, map: &CpuMap, host: &HostCpuInfo) -> Result<CpuComparison, String> {
+        let mut comparison = CpuComparison {
+            result: CpuCompareResult::Identical,
+            missing: Vec::new(),
+            forbidden: Vec::new(),
+        };
+
+        // 依赖宿主机的模式总能在本机运行
+        if !self.is_custom() {
+            return Ok(comparison);
+        }
+
+        let arch = self.resolve_arch(map, host.arch);
+        if let (Some(arch), Some(host_arch)) = (arch, host.arch)
+            && arch != host_arch
+        {
+            return Err(format!(
+                "CPU architecture '{}' does not match host architecture '{}'",
+                arch, host_arch
+            ));
+        }
+
+        if arch == Some(CpuArch::Ppc64) {
+            let generation = |name: &str| map.models(CpuArch::Ppc64).position(|m| m.name == name);
+            let guest = self.model.as_ref().and_then(|m| generation(&m.name));
+            let host_generation = host.model_name.as_deref().and_then(generation);
+            if guest.is_none() || host_generation.is_none() || guest > host_generation {
+                comparison.result = CpuCompareResult::Incompatible;
+                comparison.missing = self.model.iter().map(|m| m.name.clone()).collect();
+            } else if guest < host_generation {
+                comparison.result = CpuCompareResult::Superset;
+            }
+            return Ok(comparison);
+        }
+
+        let (mut guest, optional) = self.split_features(map, arch)?;
+        let host_flags = host.normalized_flags(map);
+
+        // 宿主机缺少的 optional 特性直接不启用，不影响兼容性
+        comparison.missing = guest.difference(&host_flags).cloned().collect();
+        guest.extend(optional.intersection(&host_flags).cloned());
+        comparison.forbidden = self
+            .features
+            .iter()
+            .flatten()
+            .filter(|f| f.policy == FeaturePolicy::Forbid && host_flags.contains(&f.name))
+            .map(|f| f.name.clone())
+            .collect();
+
+        comparison.result = if !comparison.missing.is_empty() || !comparison.forbidden.is_empty() {
+            CpuCompareResult::Incompatible
+        } else if guest == host_flags {
+            CpuCompareResult::Identical
+        } else {
+            CpuCompareResult::Superset
+        };
+
+        Ok(comparison)
+    }
+}
@@ -0,0 +1,345 @@
+mod realtime;
+mod resctrl;
+
+use crate::vm_info::numatune::NodeSet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+
+pub use realtime::RealtimeProfile;
+
+pub use resctrl::{
+    CacheAllocation, CachePlan, ResctrlCacheInfo, ResctrlCacheType, ResctrlInfo, ResctrlMemBwInfo,
+    ResctrlMonitorInfo,
+};
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct Cputune {
+    #[serde(rename = "vcpupin", default)]
+    pub vcpu_pins: Vec<Vcpupin>,
+
+    #[serde(
+        rename = "emulatorpin",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub emulator_pin: Option<Emulatorpin>,
+
+    #[serde(rename = "iothreadpin", default)]
+    pub iothread_pins: Vec<Iothreadpin>,
+
+    #[serde(rename = "shares", skip_serializing_if = "Option::is_none")]
+    pub shares: Option<u32>,
+
+    #[serde(rename = "period", skip_serializing_if = "Option::is_none")]
+    pub period: Option<u64>,
+
+    #[serde(rename = "quota", skip_serializing_if = "Option::is_none")]
+    pub quota: Option<i64>,
+
+    #[serde(rename = "global_period", skip_serializing_if = "Option::is_none")]
+    pub global_period: Option<u64>,
+
+    #[serde(rename = "global_quota", skip_serializing_if = "Option::is_none")]
+    pub global_quota: Option<i64>,
+
+    #[serde(rename = "emulator_period", skip_serializing_if = "Option::is_none")]
+    pub emulator_period: Option<u64>,
+
+    #[serde(rename = "emulator_quota", skip_serializing_if = "Option::is_none")]
+    pub emulator_quota: Option<i64>,
+
+    #[serde(rename = "iothread_period", skip_serializing_if = "Option::is_none")]
+    pub iothread_period: Option<u64>,
+
+    #[serde(rename = "iothread_quota", skip_serializing_if = "Option::is_none")]
+    pub iothread_quota: Option<i64>,
+
+    #[serde(rename = "vcpusched", default)]
+    pub vcpu_scheds: Vec<Vcpusched>,
+
+    #[serde(rename = "iothreadsched", default)]
+    pub iothread_scheds: Vec<Iothreadsched>,
+
+    #[serde(rename = "cachetune", default)]
+    pub cache_tunes: Vec<Cachetune>,
+
+    #[serde(rename = "memorytune", default)]
+    pub memory_tunes: Vec<Memorytune>,
+}
+
+// vCPU将被固定到哪个主机的物理cpu 如果不指定该参数，且不指定元素vcpu的cpuset属性，则vcpu默认固定在所有物理cpu上。它包含两个必选属性，属性vcpu指定vcpu id，属性cpuset与元素vcpu的属性cpuset相同
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Vcpupin {
+    #[serde(rename = "@vcpu")]
+    pub vcpu: String,
+    #[serde(rename = "@cpuset")]
+    pub cpuset: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Emulatorpin {
+    #[serde(rename = "@cpuset")]
+    pub cpuset: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Iothreadpin {
+    #[serde(rename = "@iothread")]
+    pub iothread: String,
+    #[serde(rename = "@cpuset")]
+    pub cpuset: String,
+}
+
+/// 线程调度策略
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulerPolicy {
+    Batch,
+    Idle,
+    /// SCHED_FIFO，实时
+    Fifo,
+    /// SCHED_RR，实时
+    Rr,
+}
+
+impl SchedulerPolicy {
+    /// fifo/rr 为实时策略，必须指定优先级
+    pub fn is_realtime(&self) -> bool {
+        matches!(self, SchedulerPolicy::Fifo | SchedulerPolicy::Rr)
+    }
+}
+
+impl fmt::Display for SchedulerPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchedulerPolicy::Batch => write!(f, "batch"),
+            SchedulerPolicy::Idle => write!(f, "idle"),
+            SchedulerPolicy::Fifo => write!(f, "fifo"),
+            SchedulerPolicy::Rr => write!(f, "rr"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Vcpusched {
+    #[serde(rename = "@vcpus")]
+    pub vcpus: String,
+    #[serde(rename = "@scheduler")]
+    pub scheduler: SchedulerPolicy,
+    #[serde(rename = "@priority", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+}
+
+impl Vcpusched {
+    pub fn new(vcpus: &str, scheduler: SchedulerPolicy) -> Self {
+        Self {
+            vcpus: vcpus.to_string(),
+            scheduler,
+            priority: None,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Iothreadsched {
+    #[serde(rename = "@iothreads")]
+    pub iothreads: String,
+    #[serde(rename = "@scheduler")]
+    pub scheduler: SchedulerPolicy,
+    #[serde(rename = "@priority", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+}
+
+impl Iothreadsched {
+    pub fn new(iothreads: &str, scheduler: SchedulerPolicy) -> Self {
+        Self {
+            iothreads: iothreads.to_string(),
+            scheduler,
+            priority: None,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Cachetune {
+    #[serde(rename = "@vcpus")]
+    pub vcpus: String,
+    #[serde(rename = "cache", default)]
+    pub caches: Vec<Cache>,
+    #[serde(rename = "monitor", default)]
+    pub monitors: Vec<Monitor>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Cache {
+    #[serde(rename = "@id")]
+    pub id: String,
+    #[serde(rename = "@level")]
+    pub level: String,
+    #[serde(rename = "@type")]
+    pub cache_type: String,
+    #[serde(rename = "@size")]
+    pub size: String,
+    #[serde(rename = "@unit")]
+    pub unit: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Monitor {
+    #[serde(rename = "@level")]
+    pub level: String,
+    #[serde(rename = "@vcpus")]
+    pub vcpus: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Memorytune {
+    #[serde(rename = "@vcpus")]
+    pub vcpus: String,
+    #[serde(rename = "node", default)]
+    pub nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Node {
+    #[serde(rename = "@id")]
+    pub id: String,
+    #[serde(rename = "@bandwidth")]
+    pub bandwidth: String,
+}
+
+// 检查 fifo/rr 必须且只能在其上指定 1..99 的优先级
+fn validate_priority(
+    what: &str,
+    scheduler: SchedulerPolicy,
+    priority: Option<u32>,
+    errors: &mut Vec<String>,
+) {
+    match (scheduler.is_realtime(), priority) {
+        (true, None) => errors.push(format!(
+            "{} scheduler '{}' requires a priority",
+            what, scheduler
+        )),
+        (true, Some(p)) if !(1..=99).contains(&p) => {
+            errors.push(format!("{} priority {} is out of range 1..99", what, p))
+        }
+        (false, Some(_)) => errors.push(format!(
+            "{} scheduler '{}' does not accept a priority",
+            what, scheduler
+        )),
+        _ => {}
+    }
+}
+
+// 解析 id 集合并检查是否与之前的条目重叠
+fn parse_ids(
+    what: &str,
+    ids: &str,
+    seen: &mut BTreeSet<u32>,
+    errors: &mut Vec<String>,
+) -> BTreeSet<u32> {
+    let set: BTreeSet<u32> = match NodeSet::new(ids).parse() {
+        Ok(set) => set.into_iter().collect(),
+        Err(e) => {
+            errors.push(format!("Invalid {} '{}': {}", what, ids, e));
+            return BTreeSet::new();
+        }
+    };
+    if set.is_empty() {
+        errors.push(format!("{} '{}' is empty", what, ids));
+    }
+    let overlap: Vec<u32> = set.intersection(seen).copied().collect();
+    if !overlap.is_empty() {
+        errors.push(format!(
+            "{} '{}' overlap with another entry on {:?}",
+            what, ids, overlap
+        ));
+    }
+    seen.extend(set.iter().copied());
+    set
+}
+
+impl Cputune {
+    /// 验证 vcpupin/vcpusched/iothreadsched
+    ///
+    /// `iothread_ids` 为 None 时不检查 IOThread id 是否存在。
+    pub fn validate_scheduling(
+        &self,
+        vcpu_count: u32,
+        iothread_ids: Option<&BTreeSet<u32>>,
+    ) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        let mut pinned = BTreeSet::new();
+        for pin in &self.vcpu_pins {
+            match pin.vcpu.parse::<u32>() {
+                Ok(vcpu) if vcpu >= vcpu_count => errors.push(format!(
+                    "vcpupin references vcpu {} but the domain has {} vcpus",
+                    vcpu, vcpu_count
+                )),
+                Ok(vcpu) => {
+                    if !pinned.insert(vcpu) {
+                        errors.push(format!("Duplicate vcpupin for vcpu {}", vcpu));
+                    }
+                }
+                Err(_) => errors.push(format!("Invalid vcpupin vcpu '{}'", pin.vcpu)),
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        for sched in &self.vcpu_scheds {
+            validate_priority("vcpusched", sched.scheduler, sched.priority, &mut errors);
+            let vcpus = parse_ids("vcpusched vcpus", &sched.vcpus, &mut seen, &mut errors);
+            if let Some(max) = vcpus.iter().max()
+                && *max >= vcpu_count
+            {
+                errors.push(format!(
+                    "vcpusched vcpus '{}' reference vcpu {} but the domain has {} vcpus",
+                    sched.vcpus, max, vcpu_count
+                ));
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        for sched in &self.iothread_scheds {
+            validate_priority(
+                "iothreadsched",
+                sched.scheduler,
+                sched.priority,
+                &mut errors,
+            );
+            let ids = parse_ids(
+                "iothreadsched iothreads",
+                &sched.iothreads,
+                &mut seen,
+                &mut errors,
+            );
+            if let Some(known) = iothread_ids {
+                let missing: Vec<u32> = ids.difference(known).copied().collect();
+                if !missing.is_empty() {
+                    errors.push(format!(
+                        "iothreadsched references undefined iothread(s) {:?}",
+                        missing
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
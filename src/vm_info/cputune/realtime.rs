@@ -0,0 +1,169 @@
+use super::{Emulatorpin, SchedulerPolicy, Vcpupin, Vcpusched};
+use crate::vm_info::memory_backing::EmptyElement;
+use crate::vm_info::{Domain, FeatureState, Features, MemoryBacking, MemoryEstimate};
+use std::collections::BTreeSet;
+
+/// 按 libvirt 实时调优指南（kbase/kvm-realtime）生成配置
+///
+/// - 每个 vCPU 绑定到独占的宿主机 CPU，模拟器线程绑定到管理 CPU
+/// - 非管理 vCPU 使用 fifo 调度
+/// - 锁定客户机内存并关闭 KSM
+/// - 关闭虚拟 PMU
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealtimeProfile {
+    /// 第 i 个 vCPU 绑定的宿主机 CPU
+    pub vcpu_cpus: Vec<u32>,
+    /// 模拟器线程使用的宿主机 CPU
+    pub emulator_cpuset: Option<String>,
+    /// 保持普通调度、运行客户机管理任务的 vCPU
+    pub housekeeping_vcpus: BTreeSet<u32>,
+    pub scheduler: SchedulerPolicy,
+    pub priority: u32,
+}
+
+impl RealtimeProfile {
+    pub fn new(vcpu_cpus: Vec<u32>) -> Self {
+        Self {
+            vcpu_cpus,
+            emulator_cpuset: None,
+            housekeeping_vcpus: BTreeSet::new(),
+            scheduler: SchedulerPolicy::Fifo,
+            priority: 1,
+        }
+    }
+
+    pub fn with_emulator_cpuset(mut self, cpuset: &str) -> Self {
+        self.emulator_cpuset = Some(cpuset.to_string());
+        self
+    }
+
+    pub fn with_housekeeping_vcpus(mut self, vcpus: &[u32]) -> Self {
+        self.housekeeping_vcpus = vcpus.iter().copied().collect();
+        self
+    }
+
+    pub fn with_scheduler(mut self, scheduler: SchedulerPolicy, priority: u32) -> Self {
+        self.scheduler = scheduler;
+        self.priority = priority;
+        self
+    }
+
+    /// 将实时配置应用到域，原有的 vcpupin/emulatorpin/vcpusched 会被替换
+    ///
+    /// 未设置 `<memtune><hard_limit>` 时按内存估算补上，因为 `<locked/>` 需要它。
+    /// 返回错误时域保持不变。
+    pub fn apply(&self, domain: &mut Domain) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let vcpu_count = domain.vcpu.vcpu_count;
+
+        if self.vcpu_cpus.len() != vcpu_count as usize {
+            errors.push(format!(
+                "Realtime profile pins {} vcpus but the domain has {}",
+                self.vcpu_cpus.len(),
+                vcpu_count
+            ));
+        }
+        let unique: BTreeSet<u32> = self.vcpu_cpus.iter().copied().collect();
+        if unique.len() != self.vcpu_cpus.len() {
+            errors.push("Realtime vcpus must be pinned to distinct host CPUs".to_string());
+        }
+        if !self.scheduler.is_realtime() {
+            errors.push(format!(
+                "Realtime profile requires scheduler fifo or rr, got '{}'",
+                self.scheduler
+            ));
+        }
+        if !(1..=99).contains(&self.priority) {
+            errors.push(format!(
+                "Realtime priority must be between 1 and 99, got {}",
+                self.priority
+            ));
+        }
+        let rt_vcpus: Vec<u32> = (0..vcpu_count)
+            .filter(|v| !self.housekeeping_vcpus.contains(v))
+            .collect();
+        if rt_vcpus.is_empty() {
+            errors.push("Realtime profile leaves no realtime vcpus".to_string());
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        // 先在取出的 cputune 上替换并验证，失败时换回原值
+        let had_cputune = domain.cputune.is_some();
+        let mut cputune = domain.cputune.take().unwrap_or_default();
+        let old_pins = std::mem::replace(
+            &mut cputune.vcpu_pins,
+            self.vcpu_cpus
+                .iter()
+                .enumerate()
+                .map(|(vcpu, cpu)| Vcpupin {
+                    vcpu: vcpu.to_string(),
+                    cpuset: cpu.to_string(),
+                })
+                .collect(),
+        );
+        let old_emulator_pin = match &self.emulator_cpuset {
+            Some(cpuset) => cputune.emulator_pin.replace(Emulatorpin {
+                cpuset: cpuset.clone(),
+            }),
+            None => None,
+        };
+        let old_scheds = std::mem::replace(
+            &mut cputune.vcpu_scheds,
+            vec![
+                Vcpusched::new(&format_ids(&rt_vcpus), self.scheduler).with_priority(self.priority),
+            ],
+        );
+        if let Err(e) = cputune.validate_scheduling(vcpu_count, Some(&domain.iothread_ids())) {
+            cputune.vcpu_pins = old_pins;
+            if self.emulator_cpuset.is_some() {
+                cputune.emulator_pin = old_emulator_pin;
+            }
+            cputune.vcpu_scheds = old_scheds;
+            domain.cputune = had_cputune.then_some(cputune);
+            return Err(e);
+        }
+        domain.cputune = Some(cputune);
+
+        let memory_backing = domain.memory_backing.get_or_insert_with(MemoryBacking::new);
+        if memory_backing.locked.is_none() {
+            memory_backing.locked = Some(EmptyElement);
+        }
+        if memory_backing.nosharepages.is_none() {
+            memory_backing.nosharepages = Some(EmptyElement);
+        }
+
+        domain.features.get_or_insert_with(Features::new).pmu = Some(FeatureState::off());
+
+        if domain
+            .memtune
+            .as_ref()
+            .is_none_or(|mt| mt.hard_limit.is_none())
+        {
+            let memtune = MemoryEstimate::from_domain(domain).to_memtune();
+            let target = domain.memtune.get_or_insert_with(Default::default);
+            target.hard_limit = memtune.hard_limit;
+        }
+        Ok(())
+    }
+}
+
+// 将 id 列表压缩为 "0-3,5" 形式
+fn format_ids(ids: &[u32]) -> String {
+    let mut parts = Vec::new();
+    let mut iter = ids.iter().copied().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end += 1;
+            iter.next();
+        }
+        if start == end {
+            parts.push(start.to_string());
+        } else {
+            parts.push(format!("{}-{}", start, end));
+        }
+    }
+    parts.join(",")
+}
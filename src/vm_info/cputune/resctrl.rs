@@ -0,0 +1,648 @@
+use super::{Cache, Cachetune, Cputune};
+use crate::MemoryUnit;
+use crate::vm_info::Domain;
+use crate::vm_info::numatune::NodeSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// resctrl 缓存分配类型（开启 CDP 时区分 code/data）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ResctrlCacheType {
+    Both,
+    Code,
+    Data,
+}
+
+impl ResctrlCacheType {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "both" => Some(ResctrlCacheType::Both),
+            "code" => Some(ResctrlCacheType::Code),
+            "data" => Some(ResctrlCacheType::Data),
+            _ => None,
+        }
+    }
+
+    // info 目录名后缀
+    fn suffix(&self) -> &'static str {
+        match self {
+            ResctrlCacheType::Both => "",
+            ResctrlCacheType::Code => "CODE",
+            ResctrlCacheType::Data => "DATA",
+        }
+    }
+}
+
+impl fmt::Display for ResctrlCacheType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResctrlCacheType::Both => write!(f, "both"),
+            ResctrlCacheType::Code => write!(f, "code"),
+            ResctrlCacheType::Data => write!(f, "data"),
+        }
+    }
+}
+
+/// info/L3、info/L3CODE 等目录描述的一类缓存分配能力
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResctrlCacheInfo {
+    pub level: u32,
+    pub cache_type: ResctrlCacheType,
+    /// cbm_mask 中置位的位数
+    pub cbm_bits: u32,
+    pub min_cbm_bits: u32,
+    pub num_closids: u32,
+    /// 与其他硬件共享的位
+    pub shareable_bits: u64,
+    /// 单个缓存实例的大小（KiB）
+    pub cache_size_kib: u64,
+    /// 该级缓存的实例 id
+    pub ids: BTreeSet<u32>,
+}
+
+impl ResctrlCacheInfo {
+    /// 每个 CBM 位对应的缓存大小（KiB）
+    pub fn granularity_kib(&self) -> u64 {
+        if self.cbm_bits == 0 {
+            0
+        } else {
+            self.cache_size_kib / u64::from(self.cbm_bits)
+        }
+    }
+
+    pub fn full_mask(&self) -> u64 {
+        if self.cbm_bits >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << self.cbm_bits) - 1
+        }
+    }
+}
+
+/// info/MB 描述的内存带宽分配能力
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResctrlMemBwInfo {
+    pub bandwidth_gran: u32,
+    pub min_bandwidth: u32,
+    pub num_closids: u32,
+}
+
+/// info/L3_MON 描述的监控能力
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResctrlMonitorInfo {
+    pub num_rmids: u32,
+    pub features: Vec<String>,
+}
+
+/// 宿主机 resctrl 能力
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResctrlInfo {
+    pub caches: Vec<ResctrlCacheInfo>,
+    pub memory_bandwidth: Option<ResctrlMemBwInfo>,
+    pub monitor: Option<ResctrlMonitorInfo>,
+}
+
+impl ResctrlInfo {
+    pub fn from_host() -> Result<Self, String> {
+        Self::from_root("/")
+    }
+
+    /// 从指定根目录读取 sys/fs/resctrl/info，缓存大小与 id 取自
+    /// sys/devices/system/cpu/cpu*/cache/index*
+    pub fn from_root<P: AsRef<Path>>(root: P) -> Result<Self, String> {
+        let root = root.as_ref();
+        let info_dir = root.join("sys/fs/resctrl/info");
+        if !info_dir.is_dir() {
+            return Err(format!(
+                "{} not found, is resctrl mounted?",
+                info_dir.display()
+            ));
+        }
+
+        let host_caches = read_host_caches(root);
+        let mut info = ResctrlInfo::default();
+
+        for level in 2..=3 {
+            for cache_type in [
+                ResctrlCacheType::Both,
+                ResctrlCacheType::Code,
+                ResctrlCacheType::Data,
+            ] {
+                let dir = info_dir.join(format!("L{}{}", level, cache_type.suffix()));
+                if !dir.is_dir() {
+                    continue;
+                }
+                let cbm_mask = read_hex(&dir.join("cbm_mask"))?;
+                let (cache_size_kib, ids) = host_caches.get(&level).cloned().unwrap_or_default();
+                info.caches.push(ResctrlCacheInfo {
+                    level,
+                    cache_type,
+                    cbm_bits: cbm_mask.count_ones(),
+                    min_cbm_bits: read_u32(&dir.join("min_cbm_bits"))?,
+                    num_closids: read_u32(&dir.join("num_closids"))?,
+                    shareable_bits: read_hex(&dir.join("shareable_bits")).unwrap_or(0),
+                    cache_size_kib,
+                    ids,
+                });
+            }
+        }
+
+        let mb = info_dir.join("MB");
+        if mb.is_dir() {
+            info.memory_bandwidth = Some(ResctrlMemBwInfo {
+                bandwidth_gran: read_u32(&mb.join("bandwidth_gran"))?,
+                min_bandwidth: read_u32(&mb.join("min_bandwidth"))?,
+                num_closids: read_u32(&mb.join("num_closids"))?,
+            });
+        }
+
+        let mon = info_dir.join("L3_MON");
+        if mon.is_dir() {
+            info.monitor = Some(ResctrlMonitorInfo {
+                num_rmids: read_u32(&mon.join("num_rmids"))?,
+                features: fs::read_to_string(mon.join("mon_features"))
+                    .unwrap_or_default()
+                    .lines()
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect(),
+            });
+        }
+
+        Ok(info)
+    }
+
+    pub fn cache(&self, level: u32, cache_type: ResctrlCacheType) -> Option<&ResctrlCacheInfo> {
+        self.caches
+            .iter()
+            .find(|c| c.level == level && c.cache_type == cache_type)
+    }
+
+    /// 可同时存在的分配组数量（含默认组）
+    pub fn num_closids(&self) -> Option<u32> {
+        self.caches
+            .iter()
+            .map(|c| c.num_closids)
+            .chain(self.memory_bandwidth.iter().map(|mb| mb.num_closids))
+            .min()
+    }
+}
+
+fn read_u32(path: &Path) -> Result<u32, String> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid value in {}", path.display()))
+}
+
+fn read_hex(path: &Path) -> Result<u64, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    u64::from_str_radix(content.trim(), 16)
+        .map_err(|_| format!("Invalid bitmask in {}", path.display()))
+}
+
+// 缓存级别 -> (单实例大小 KiB, 实例 id 集合)，只统计 Unified/Data 缓存
+fn read_host_caches(root: &Path) -> BTreeMap<u32, (u64, BTreeSet<u32>)> {
+    let mut caches: BTreeMap<u32, (u64, BTreeSet<u32>)> = BTreeMap::new();
+    let Ok(cpus) = fs::read_dir(root.join("sys/devices/system/cpu")) else {
+        return caches;
+    };
+
+    for cpu in cpus.flatten() {
+        let is_cpu = cpu
+            .file_name()
+            .to_str()
+            .and_then(|n| n.strip_prefix("cpu"))
+            .is_some_and(|n| n.parse::<u32>().is_ok());
+        if !is_cpu {
+            continue;
+        }
+        let Ok(indexes) = fs::read_dir(cpu.path().join("cache")) else {
+            continue;
+        };
+        for index in indexes.flatten() {
+            let dir = index.path();
+            let read = |name: &str| {
+                fs::read_to_string(dir.join(name))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default()
+            };
+            if read("type") == "Instruction" {
+                continue;
+            }
+            let Ok(level) = read("level").parse::<u32>() else {
+                continue;
+            };
+            let size = read("size");
+            let size_kib = size
+                .strip_suffix('K')
+                .and_then(|s| s.parse::<u64>().ok())
+                .or_else(|| {
+                    size.strip_suffix('M')
+                        .and_then(|s| s.parse::<u64>().ok())
+                        .map(|m| m * 1024)
+                })
+                .unwrap_or(0);
+            let entry = caches.entry(level).or_default();
+            entry.0 = entry.0.max(size_kib);
+            if let Ok(id) = read("id").parse::<u32>() {
+                entry.1.insert(id);
+            }
+        }
+    }
+
+    caches
+}
+
+fn parse_vcpus(what: &str, vcpus: &str, errors: &mut Vec<String>) -> Option<BTreeSet<u32>> {
+    match NodeSet::new(vcpus).parse() {
+        Ok(set) if !set.is_empty() => Some(set.into_iter().collect()),
+        Ok(_) => {
+            errors.push(format!("{} vcpus '{}' is empty", what, vcpus));
+            None
+        }
+        Err(e) => {
+            errors.push(format!("Invalid {} vcpus '{}': {}", what, vcpus, e));
+            None
+        }
+    }
+}
+
+impl Cache {
+    /// 请求的缓存大小（KiB）
+    pub fn size_kib(&self) -> Result<u64, String> {
+        let size: u64 = self
+            .size
+            .parse()
+            .map_err(|_| format!("Invalid cache size '{}'", self.size))?;
+        let unit: MemoryUnit = self.unit.parse()?;
+        Ok(unit.to_bytes(size) / 1024)
+    }
+}
+
+impl Cputune {
+    /// 验证 cachetune/memorytune
+    ///
+    /// 不依赖宿主机的检查总会进行；提供 `info` 时还会检查缓存级别、类型、
+    /// 分配粒度、CLOSID 数量与内存带宽粒度。
+    pub fn validate_resctrl(&self, info: Option<&ResctrlInfo>) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        let mut allocations: Vec<BTreeSet<u32>> = Vec::new();
+        let mut cachetune_sets: Vec<BTreeSet<u32>> = Vec::new();
+        let mut monitors = 0u32;
+
+        for cachetune in &self.cache_tunes {
+            let Some(vcpus) = parse_vcpus("cachetune", &cachetune.vcpus, &mut errors) else {
+                continue;
+            };
+            for other in &cachetune_sets {
+                if !vcpus.is_disjoint(other) {
+                    errors.push(format!(
+                        "cachetune vcpus '{}' overlap with another cachetune",
+                        cachetune.vcpus
+                    ));
+                    break;
+                }
+            }
+
+            let mut seen = HashSet::new();
+            for cache in &cachetune.caches {
+                if !seen.insert((&cache.id, &cache.level, &cache.cache_type)) {
+                    errors.push(format!(
+                        "cachetune vcpus '{}' has duplicate cache id {} level {} type {}",
+                        cachetune.vcpus, cache.id, cache.level, cache.cache_type
+                    ));
+                }
+                if let Some(info) = info {
+                    validate_cache(cache, info, &mut errors);
+                } else if let Err(e) = cache.size_kib() {
+                    errors.push(e);
+                }
+            }
+
+            let mut monitor_sets = Vec::new();
+            for monitor in &cachetune.monitors {
+                monitors += 1;
+                if monitor.level != "3" {
+                    errors.push(format!(
+                        "Cache monitor level {} is not supported, only level 3",
+                        monitor.level
+                    ));
+                }
+                let Some(set) = parse_vcpus("monitor", &monitor.vcpus, &mut errors) else {
+                    continue;
+                };
+                if !set.is_subset(&vcpus) {
+                    errors.push(format!(
+                        "Monitor vcpus '{}' are not a subset of cachetune vcpus '{}'",
+                        monitor.vcpus, cachetune.vcpus
+                    ));
+                }
+                if monitor_sets.contains(&set) {
+                    errors.push(format!(
+                        "Duplicate monitor for vcpus '{}' in cachetune '{}'",
+                        monitor.vcpus, cachetune.vcpus
+                    ));
+                }
+                monitor_sets.push(set);
+            }
+
+            if !cachetune.caches.is_empty() {
+                allocations.push(vcpus.clone());
+            }
+            cachetune_sets.push(vcpus);
+        }
+
+        let mut memorytune_sets: Vec<BTreeSet<u32>> = Vec::new();
+        for memorytune in &self.memory_tunes {
+            let Some(vcpus) = parse_vcpus("memorytune", &memorytune.vcpus, &mut errors) else {
+                continue;
+            };
+            // 与 cachetune 共用分配组时 vcpus 必须完全一致
+            if cachetune_sets
+                .iter()
+                .any(|set| *set != vcpus && !set.is_disjoint(&vcpus))
+            {
+                errors.push(format!(
+                    "memorytune vcpus '{}' must either match a cachetune exactly or not overlap it",
+                    memorytune.vcpus
+                ));
+            }
+            if memorytune_sets.iter().any(|set| !set.is_disjoint(&vcpus)) {
+                errors.push(format!(
+                    "memorytune vcpus '{}' overlap with another memorytune",
+                    memorytune.vcpus
+                ));
+            }
+
+            let mut seen = HashSet::new();
+            for node in &memorytune.nodes {
+                if !seen.insert(&node.id) {
+                    errors.push(format!(
+                        "memorytune vcpus '{}' has duplicate node id {}",
+                        memorytune.vcpus, node.id
+                    ));
+                }
+                let Ok(bandwidth) = node.bandwidth.parse::<u32>() else {
+                    errors.push(format!("Invalid memory bandwidth '{}'", node.bandwidth));
+                    continue;
+                };
+                if bandwidth == 0 || bandwidth > 100 {
+                    errors.push(format!(
+                        "Memory bandwidth {} for node {} must be between 1 and 100",
+                        bandwidth, node.id
+                    ));
+                }
+                if let Some(info) = info {
+                    match &info.memory_bandwidth {
+                        Some(mb) => {
+                            if bandwidth < mb.min_bandwidth {
+                                errors.push(format!(
+                                    "Memory bandwidth {} for node {} is below the host minimum {}",
+                                    bandwidth, node.id, mb.min_bandwidth
+                                ));
+                            }
+                            if mb.bandwidth_gran > 0 && bandwidth % mb.bandwidth_gran != 0 {
+                                errors.push(format!(
+                                    "Memory bandwidth {} for node {} is not a multiple of the host granularity {}",
+                                    bandwidth, node.id, mb.bandwidth_gran
+                                ));
+                            }
+                        }
+                        None => errors.push(
+                            "Host does not support memory bandwidth allocation (no info/MB)"
+                                .to_string(),
+                        ),
+                    }
+                }
+            }
+
+            if !allocations.contains(&vcpus) {
+                allocations.push(vcpus.clone());
+            }
+            memorytune_sets.push(vcpus);
+        }
+
+        if let Some(info) = info {
+            // 默认资源组占用一个 CLOSID
+            if let Some(closids) = info.num_closids()
+                && allocations.len() as u32 + 1 > closids
+            {
+                errors.push(format!(
+                    "{} allocation groups plus the default group exceed the host's {} CLOSIDs",
+                    allocations.len(),
+                    closids
+                ));
+            }
+            if monitors > 0 {
+                match &info.monitor {
+                    Some(mon) if monitors > mon.num_rmids => errors.push(format!(
+                        "{} cache monitors exceed the host's {} RMIDs",
+                        monitors, mon.num_rmids
+                    )),
+                    Some(_) => {}
+                    None => errors.push(
+                        "Host does not support cache monitoring (no info/L3_MON)".to_string(),
+                    ),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn validate_cache(cache: &Cache, info: &ResctrlInfo, errors: &mut Vec<String>) {
+    let (Ok(level), Some(cache_type)) = (
+        cache.level.parse::<u32>(),
+        ResctrlCacheType::parse(&cache.cache_type),
+    ) else {
+        errors.push(format!(
+            "Invalid cache level '{}' or type '{}'",
+            cache.level, cache.cache_type
+        ));
+        return;
+    };
+    let Some(host) = info.cache(level, cache_type) else {
+        errors.push(format!(
+            "Host does not support allocation of L{} cache type '{}'",
+            level, cache_type
+        ));
+        return;
+    };
+    if let Ok(id) = cache.id.parse::<u32>()
+        && !host.ids.is_empty()
+        && !host.ids.contains(&id)
+    {
+        errors.push(format!("Host has no L{} cache with id {}", level, id));
+    }
+
+    let size_kib = match cache.size_kib() {
+        Ok(size) => size,
+        Err(e) => {
+            errors.push(e);
+            return;
+        }
+    };
+    let granularity = host.granularity_kib();
+    if granularity == 0 {
+        return;
+    }
+    if size_kib % granularity != 0 {
+        errors.push(format!(
+            "L{} cache size {} KiB is not a multiple of the allocation granularity {} KiB",
+            level, size_kib, granularity
+        ));
+    }
+    let min = granularity * u64::from(host.min_cbm_bits);
+    if size_kib < min {
+        errors.push(format!(
+            "L{} cache size {} KiB is below the minimum allocation {} KiB",
+            level, size_kib, min
+        ));
+    }
+    // 默认组至少保留 min_cbm_bits
+    let Some(max) = host.cache_size_kib.checked_sub(min) else {
+        errors.push(format!(
+            "Host L{} min_cbm_bits {} exceeds the {} bits of cbm_mask",
+            level, host.min_cbm_bits, host.cbm_bits
+        ));
+        return;
+    };
+    if size_kib > max {
+        errors.push(format!(
+            "L{} cache size {} KiB exceeds the allocatable {} KiB",
+            level, size_kib, max
+        ));
+    }
+}
+
+/// 一次缓存分配的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheAllocation {
+    pub domain: String,
+    pub vcpus: String,
+    pub level: u32,
+    pub cache_type: ResctrlCacheType,
+    pub id: u32,
+    pub mask: u64,
+}
+
+impl CacheAllocation {
+    /// resctrl schemata 行，如 `L3:0=f0000`（CDP 时为 L3CODE/L3DATA）
+    pub fn schemata(&self) -> String {
+        format!(
+            "L{}{}:{}={:x}",
+            self.level,
+            self.cache_type.suffix(),
+            self.id,
+            self.mask
+        )
+    }
+}
+
+/// 将多个域的 cachetune 请求打包为互不重叠的缓存位图
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CachePlan {
+    pub allocations: Vec<CacheAllocation>,
+    pub errors: Vec<String>,
+}
+
+impl CachePlan {
+    /// 从高位向低位连续分配，低位的 min_cbm_bits 保留给默认组
+    pub fn compute(domains: &[&Domain], info: &ResctrlInfo) -> Self {
+        let mut plan = Self::default();
+        // (level, type, id) -> 下一个可用的最高位（不含）
+        let mut next_bit: BTreeMap<(u32, ResctrlCacheType, u32), u32> = BTreeMap::new();
+
+        for domain in domains {
+            let cache_tunes: &[Cachetune] = domain
+                .cputune
+                .as_ref()
+                .map_or(&[], |ct| ct.cache_tunes.as_slice());
+            for cachetune in cache_tunes {
+                for cache in &cachetune.caches {
+                    if let Err(e) = plan.allocate(domain, cachetune, cache, info, &mut next_bit) {
+                        plan.errors.push(format!("{}: {}", domain.name, e));
+                    }
+                }
+            }
+        }
+
+        plan
+    }
+
+    fn allocate(
+        &mut self,
+        domain: &Domain,
+        cachetune: &Cachetune,
+        cache: &Cache,
+        info: &ResctrlInfo,
+        next_bit: &mut BTreeMap<(u32, ResctrlCacheType, u32), u32>,
+    ) -> Result<(), String> {
+        let level: u32 = cache
+            .level
+            .parse()
+            .map_err(|_| format!("Invalid cache level '{}'", cache.level))?;
+        let cache_type = ResctrlCacheType::parse(&cache.cache_type)
+            .ok_or_else(|| format!("Invalid cache type '{}'", cache.cache_type))?;
+        let id: u32 = cache
+            .id
+            .parse()
+            .map_err(|_| format!("Invalid cache id '{}'", cache.id))?;
+        let host = info.cache(level, cache_type).ok_or_else(|| {
+            format!(
+                "Host does not support allocation of L{} cache type '{}'",
+                level, cache_type
+            )
+        })?;
+        let granularity = host.granularity_kib();
+        if granularity == 0 {
+            return Err(format!("L{} cache size on the host is unknown", level));
+        }
+
+        let bits = u32::try_from(cache.size_kib()?.div_ceil(granularity))
+            .unwrap_or(u32::MAX)
+            .max(host.min_cbm_bits)
+            .max(1);
+        let top = next_bit
+            .entry((level, cache_type, id))
+            .or_insert(host.cbm_bits);
+        if *top < bits.saturating_add(host.min_cbm_bits) {
+            return Err(format!(
+                "Not enough L{} {} cache on id {} for vcpus '{}': need {} bits, {} left",
+                level,
+                cache_type,
+                id,
+                cachetune.vcpus,
+                bits,
+                top.saturating_sub(host.min_cbm_bits)
+            ));
+        }
+
+        *top -= bits;
+        let mask = ((u64::MAX >> (64 - bits)) << *top) & host.full_mask();
+        self.allocations.push(CacheAllocation {
+            domain: domain.name.clone(),
+            vcpus: cachetune.vcpus.clone(),
+            level,
+            cache_type,
+            id,
+            mask,
+        });
+        Ok(())
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
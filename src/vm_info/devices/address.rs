@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+/// 用户定义别名的前缀，这类别名会保存在持久配置中
+const USER_ALIAS_PREFIX: &str = "ua-";
+
+// 设备别名 <alias name='virtio-disk0'/>，运行中的域由 libvirt 自动分配
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceAlias {
+    #[serde(rename = "@name")]
+    pub name: String,
+}
+
+impl DeviceAlias {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    /// 以 `ua-` 开头的别名由用户指定，不属于运行时信息
+    pub fn is_user_defined(&self) -> bool {
+        self.name.starts_with(USER_ALIAS_PREFIX)
+    }
+}
+
+// 客户机侧设备地址 <address type='pci|drive|usb|virtio-serial|ccw|...'/>
+// 未指定时由 libvirt 在定义或启动时自动分配
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceAddress {
+    #[serde(rename = "@type")]
+    pub address_type: String,
+    #[serde(rename = "@domain", skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(rename = "@bus", skip_serializing_if = "Option::is_none")]
+    pub bus: Option<String>,
+    #[serde(rename = "@slot", skip_serializing_if = "Option::is_none")]
+    pub slot: Option<String>,
+    #[serde(rename = "@function", skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(rename = "@multifunction", skip_serializing_if = "Option::is_none")]
+    pub multifunction: Option<String>,
+    /// drive 地址
+    #[serde(rename = "@controller", skip_serializing_if = "Option::is_none")]
+    pub controller: Option<String>,
+    #[serde(rename = "@target", skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(rename = "@unit", skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// usb/virtio-serial 端口
+    #[serde(rename = "@port", skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    /// s390 ccw 地址
+    #[serde(rename = "@cssid", skip_serializing_if = "Option::is_none")]
+    pub cssid: Option<String>,
+    #[serde(rename = "@ssid", skip_serializing_if = "Option::is_none")]
+    pub ssid: Option<String>,
+    #[serde(rename = "@devno", skip_serializing_if = "Option::is_none")]
+    pub devno: Option<String>,
+    /// dimm 基地址
+    #[serde(rename = "@base", skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// isa 地址
+    #[serde(rename = "@iobase", skip_serializing_if = "Option::is_none")]
+    pub iobase: Option<String>,
+    #[serde(rename = "@irq", skip_serializing_if = "Option::is_none")]
+    pub irq: Option<String>,
+}
+
+impl DeviceAddress {
+    fn new(address_type: &str) -> Self {
+        Self {
+            address_type: address_type.to_string(),
+            domain: None,
+            bus: None,
+            slot: None,
+            function: None,
+            multifunction: None,
+            controller: None,
+            target: None,
+            unit: None,
+            port: None,
+            cssid: None,
+            ssid: None,
+            devno: None,
+            base: None,
+            iobase: None,
+            irq: None,
+        }
+    }
+
+    pub fn pci(domain: u16, bus: u8, slot: u8, function: u8) -> Self {
+        Self {
+            domain: Some(format!("{:#06x}", domain)),
+            bus: Some(format!("{:#04x}", bus)),
+            slot: Some(format!("{:#04x}", slot)),
+            function: Some(format!("{:#x}", function)),
+            ..Self::new("pci")
+        }
+    }
+
+    pub fn drive(controller: u32, bus: u32, target: u32, unit: u32) -> Self {
+        Self {
+            controller: Some(controller.to_string()),
+            bus: Some(bus.to_string()),
+            target: Some(target.to_string()),
+            unit: Some(unit.to_string()),
+            ..Self::new("drive")
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::vm_info::{Domain, OsKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// 设备上的 `<boot order='N'/>`，与 `<os><boot dev>` 互斥
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BootOrder {
+    /// 从 1 开始，数值越小越先尝试
+    #[serde(rename = "@order")]
+    pub order: u32,
+    /// s390 引导菜单参数，最多 8 个字符
+    #[serde(rename = "@loadparm", skip_serializing_if = "Option::is_none")]
+    pub loadparm: Option<String>,
+}
+
+impl BootOrder {
+    pub fn new(order: u32) -> Self {
+        Self {
+            order,
+            loadparm: None,
+        }
+    }
+}
+
+/// 固件将尝试引导的设备
+#[derive(Debug, Clone, PartialEq)]
+pub enum BootDevice {
+    /// 直接内核引导
+    Kernel(String),
+    /// 磁盘，`device` 为 disk、cdrom 或 floppy
+    Disk {
+        dev: String,
+        device: String,
+    },
+    /// 网卡，按 MAC 地址标识（未设置时为 None）
+    Interface {
+        mac: Option<String>,
+    },
+    Hostdev(String),
+    Redirdev(String),
+}
+
+impl fmt::Display for BootDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BootDevice::Kernel(path) => write!(f, "kernel {}", path),
+            BootDevice::Disk { dev, device } => write!(f, "{} {}", device, dev),
+            BootDevice::Interface { mac: Some(mac) } => write!(f, "interface {}", mac),
+            BootDevice::Interface { mac: None } => write!(f, "interface"),
+            BootDevice::Hostdev(desc) => write!(f, "hostdev {}", desc),
+            BootDevice::Redirdev(bus) => write!(f, "redirdev {}", bus),
+        }
+    }
+}
+
+impl Domain {
+    // 所有带 <boot order> 的设备
+    fn boot_order_devices(&self) -> Vec<(&BootOrder, BootDevice)> {
+        let devices = &self.devices;
+        let mut entries = Vec::new();
+        for disk in devices.disk.iter().flatten() {
+            if let Some(boot) = &disk.boot {
+                entries.push((
+                    boot,
+                    BootDevice::Disk {
+                        dev: disk.target.dev.clone(),
+                        device: disk.device.clone(),
+                    },
+                ));
+            }
+        }
+        for interface in devices.interfaces.iter().flatten() {
+            if let Some(boot) = &interface.boot {
+                entries.push((
+                    boot,
+                    BootDevice::Interface {
+                        mac: interface.mac.as_ref().map(|m| m.address.clone()),
+                    },
+                ));
+            }
+        }
+        for hostdev in devices.hostdevs.iter().flatten() {
+            if let Some(boot) = &hostdev.boot {
+                entries.push((boot, BootDevice::Hostdev(hostdev.describe())));
+            }
+        }
+        for redirdev in devices.redirdevs.iter().flatten() {
+            if let Some(boot) = &redirdev.boot {
+                entries.push((boot, BootDevice::Redirdev(redirdev.bus.clone())));
+            }
+        }
+        entries
+    }
+
+    /// 解析后的引导顺序
+    ///
+    /// - 直接内核引导时只有内核
+    /// - 设备带 `<boot order>` 时按 order 排序
+    /// - 否则按 `<os><boot dev>` 展开：hd/cdrom/fd 依次对应 disk/cdrom/floppy 磁盘，
+    ///   network 对应全部网卡；未指定时与 libvirt 一致，默认为 hd
+    ///
+    /// 容器没有引导设备，返回空列表。
+    pub fn effective_boot_sequence(&self) -> Vec<BootDevice> {
+        if self.os.os_type.value == OsKind::Exe {
+            return Vec::new();
+        }
+        if let Some(kernel) = &self.os.kernel {
+            return vec![BootDevice::Kernel(kernel.clone())];
+        }
+
+        let mut ordered = self.boot_order_devices();
+        if !ordered.is_empty() {
+            ordered.sort_by_key(|(boot, _)| boot.order);
+            return ordered.into_iter().map(|(_, device)| device).collect();
+        }
+
+        let boot_devs: Vec<&str> = if self.os.boots.is_empty() {
+            vec!["hd"]
+        } else {
+            self.os.boots.iter().map(|b| b.dev.as_str()).collect()
+        };
+
+        let mut sequence = Vec::new();
+        for dev in boot_devs {
+            let disk_device = match dev {
+                "hd" => "disk",
+                "cdrom" => "cdrom",
+                "fd" => "floppy",
+                "network" => {
+                    for interface in self.devices.interfaces.iter().flatten() {
+                        sequence.push(BootDevice::Interface {
+                            mac: interface.mac.as_ref().map(|m| m.address.clone()),
+                        });
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            for disk in self.devices.disk.iter().flatten() {
+                if disk.device == disk_device {
+                    sequence.push(BootDevice::Disk {
+                        dev: disk.target.dev.clone(),
+                        device: disk.device.clone(),
+                    });
+                }
+            }
+        }
+        sequence
+    }
+
+    /// 验证 `<os><boot dev>` 与设备 `<boot order>` 的组合
+    pub fn validate_boot_order(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let entries = self.boot_order_devices();
+
+        if !entries.is_empty() && !self.os.boots.is_empty() {
+            errors
+                .push("Per-device <boot order> cannot be combined with <os><boot dev>".to_string());
+        }
+
+        let mut seen: BTreeMap<u32, &BootDevice> = BTreeMap::new();
+        for (boot, device) in &entries {
+            if boot.order == 0 {
+                errors.push(format!("Boot order of {} must be at least 1", device));
+            } else if let Some(other) = seen.insert(boot.order, device) {
+                errors.push(format!(
+                    "Boot order {} is used by both {} and {}",
+                    boot.order, other, device
+                ));
+            }
+            if let Some(loadparm) = &boot.loadparm
+                && (loadparm.is_empty()
+                    || loadparm.len() > 8
+                    || !loadparm.chars().all(|c| {
+                        c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.' || c == ' '
+                    }))
+            {
+                errors.push(format!(
+                    "loadparm '{}' of {} must be 1-8 characters of A-Z, 0-9, '.' or ' '",
+                    loadparm, device
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use super::{DeviceAddress, DeviceAlias, DriverIothreads};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Controller {
+    #[serde(rename = "@type")]
+    pub controller_type: String,
+    #[serde(rename = "@index", skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+    #[serde(rename = "@model", skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<ControllerDriver>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+impl Controller {
+    pub fn new(controller_type: &str, index: u32) -> Self {
+        Self {
+            controller_type: controller_type.to_string(),
+            index: Some(index),
+            model: None,
+            driver: None,
+            alias: None,
+            address: None,
+        }
+    }
+
+    /// virtio-scsi 控制器
+    pub fn virtio_scsi(index: u32) -> Self {
+        let mut controller = Self::new("scsi", index);
+        controller.model = Some("virtio-scsi".to_string());
+        controller
+    }
+
+    pub fn with_iothread(mut self, iothread: u32) -> Self {
+        self.driver.get_or_insert_with(Default::default).iothread = Some(iothread);
+        self
+    }
+}
+
+/// `<controller><driver>`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ControllerDriver {
+    #[serde(rename = "@queues", skip_serializing_if = "Option::is_none")]
+    pub queues: Option<u32>,
+    #[serde(rename = "@iothread", skip_serializing_if = "Option::is_none")]
+    pub iothread: Option<u32>,
+    #[serde(rename = "@iommu", skip_serializing_if = "Option::is_none")]
+    pub iommu: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iothreads: Option<DriverIothreads>,
+}
@@ -1,3 +1,4 @@
+use super::{BootOrder, DeviceAddress, DeviceAlias};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -7,8 +8,23 @@ pub struct Disk {
     #[serde(rename = "@device")]
     pub device: String,
     pub driver: Driver,
+    /// 空光驱没有 `<source>`
+    #[serde(default, skip_serializing_if = "Source::is_empty")]
     pub source: Source,
     pub target: Target,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot: Option<BootOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+impl Disk {
+    pub fn with_boot_order(mut self, order: u32) -> Self {
+        self.boot = Some(BootOrder::new(order));
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,12 +33,119 @@ pub struct Driver {
     pub name: String,
     #[serde(rename = "@type")]
     pub driver_type: String,
+    /// 缓存模式：default、none、writethrough、writeback、directsync、unsafe
+    #[serde(rename = "@cache", skip_serializing_if = "Option::is_none")]
+    pub cache: Option<String>,
+    /// virtio-blk 队列数
+    #[serde(rename = "@queues", skip_serializing_if = "Option::is_none")]
+    pub queues: Option<u32>,
+    /// 处理该磁盘 I/O 的 IOThread
+    #[serde(rename = "@iothread", skip_serializing_if = "Option::is_none")]
+    pub iothread: Option<u32>,
+    /// 通过虚拟 IOMMU 访问内存，机密计算客户机的 virtio 设备需要 'on'
+    #[serde(rename = "@iommu", skip_serializing_if = "Option::is_none")]
+    pub iommu: Option<String>,
+    /// 队列到多个 IOThread 的映射，与 `iothread` 属性互斥
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iothreads: Option<DriverIothreads>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Driver {
+    pub fn new(name: &str, driver_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            driver_type: driver_type.to_string(),
+            cache: None,
+            queues: None,
+            iothread: None,
+            iommu: None,
+            iothreads: None,
+        }
+    }
+
+    pub fn with_iothread(mut self, iothread: u32) -> Self {
+        self.iothread = Some(iothread);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: &str) -> Self {
+        self.cache = Some(cache.to_string());
+        self
+    }
+
+    pub fn with_iommu(mut self, on: bool) -> Self {
+        self.iommu = Some(if on { "on" } else { "off" }.to_string());
+        self
+    }
+}
+
+/// `<driver><iothreads>`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DriverIothreads {
+    #[serde(rename = "iothread", default)]
+    pub iothreads: Vec<DriverIothread>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriverIothread {
+    #[serde(rename = "@id")]
+    pub id: u32,
+    #[serde(rename = "queue", default)]
+    pub queues: Vec<IothreadQueue>,
+}
+
+impl DriverIothread {
+    pub fn new(id: u32, queues: &[u32]) -> Self {
+        Self {
+            id,
+            queues: queues.iter().map(|&id| IothreadQueue { id }).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IothreadQueue {
+    #[serde(rename = "@id")]
+    pub id: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Source {
-    #[serde(rename = "@file")]
+    /// type='file' 的镜像路径
+    #[serde(rename = "@file", default, skip_serializing_if = "String::is_empty")]
     pub file: String,
+    /// type='block' 的宿主机块设备
+    #[serde(rename = "@dev", skip_serializing_if = "Option::is_none")]
+    pub dev: Option<String>,
+}
+
+impl Source {
+    /// 按路径选择 `file=` 或 `dev=`：`/dev/` 下的路径视为块设备
+    pub fn from_path(path: &str) -> Self {
+        if path.starts_with("/dev/") {
+            Self {
+                file: String::new(),
+                dev: Some(path.to_string()),
+            }
+        } else {
+            Self {
+                file: path.to_string(),
+                dev: None,
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path().is_none()
+    }
+
+    /// 宿主机上的路径，未设置时为 None
+    pub fn path(&self) -> Option<&str> {
+        self.dev
+            .as_deref()
+            .filter(|dev| !dev.is_empty())
+            .or((!self.file.is_empty()).then_some(self.file.as_str()))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
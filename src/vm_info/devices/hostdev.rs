@@ -0,0 +1,91 @@
+use super::{BootOrder, DeviceAddress, DeviceAlias};
+use serde::{Deserialize, Serialize};
+
+/// 宿主机设备直通（PCI、USB、SCSI、mdev 等）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Hostdev {
+    /// subsystem 或 capabilities
+    #[serde(rename = "@mode")]
+    pub mode: String,
+    /// pci、usb、scsi、scsi_host、mdev
+    #[serde(rename = "@type")]
+    pub hostdev_type: String,
+    #[serde(rename = "@managed", skip_serializing_if = "Option::is_none")]
+    pub managed: Option<String>,
+    #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
+    pub source: Option<HostdevSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot: Option<BootOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HostdevSource {
+    #[serde(rename = "address", skip_serializing_if = "Option::is_none")]
+    pub address: Option<HostdevAddress>,
+}
+
+/// 宿主机设备地址，PCI 使用 domain/bus/slot/function，USB 使用 bus/device
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HostdevAddress {
+    #[serde(rename = "@domain", skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(rename = "@bus", skip_serializing_if = "Option::is_none")]
+    pub bus: Option<String>,
+    #[serde(rename = "@slot", skip_serializing_if = "Option::is_none")]
+    pub slot: Option<String>,
+    #[serde(rename = "@function", skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(rename = "@device", skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+}
+
+impl Hostdev {
+    /// managed 的 PCI 直通设备
+    pub fn pci(domain: u16, bus: u8, slot: u8, function: u8) -> Self {
+        Self {
+            mode: "subsystem".to_string(),
+            hostdev_type: "pci".to_string(),
+            managed: Some("yes".to_string()),
+            source: Some(HostdevSource {
+                address: Some(HostdevAddress {
+                    domain: Some(format!("{:#06x}", domain)),
+                    bus: Some(format!("{:#04x}", bus)),
+                    slot: Some(format!("{:#04x}", slot)),
+                    function: Some(format!("{:#x}", function)),
+                    device: None,
+                }),
+            }),
+            boot: None,
+            alias: None,
+            address: None,
+        }
+    }
+
+    pub fn with_boot_order(mut self, order: u32) -> Self {
+        self.boot = Some(BootOrder::new(order));
+        self
+    }
+
+    /// 可读的设备描述，如 pci 0x0000:0x01:0x00.0x0
+    pub fn describe(&self) -> String {
+        match self.source.as_ref().and_then(|s| s.address.as_ref()) {
+            Some(addr) if self.hostdev_type == "pci" => format!(
+                "pci {}:{}:{}.{}",
+                addr.domain.as_deref().unwrap_or("0x0000"),
+                addr.bus.as_deref().unwrap_or("?"),
+                addr.slot.as_deref().unwrap_or("?"),
+                addr.function.as_deref().unwrap_or("?")
+            ),
+            Some(addr) if self.hostdev_type == "usb" => format!(
+                "usb {}:{}",
+                addr.bus.as_deref().unwrap_or("?"),
+                addr.device.as_deref().unwrap_or("?")
+            ),
+            _ => self.hostdev_type.clone(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use super::{BootOrder, DeviceAddress, DeviceAlias, VirtioDriver};
+use serde::{Deserialize, Serialize};
+
+// 网络接口 <interface type='network|bridge|user|ethernet|direct'>
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Interface {
+    #[serde(rename = "@type")]
+    pub interface_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac: Option<MacAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<InterfaceSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<InterfaceTarget>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<InterfaceModel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<VirtioDriver>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot: Option<BootOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MacAddress {
+    #[serde(rename = "@address")]
+    pub address: String,
+}
+
+// 接口来源：network 使用 network 属性，bridge 使用 bridge 属性，direct 使用 dev/mode
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct InterfaceSource {
+    #[serde(rename = "@network", skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    /// 运行中的域在网络上分配的端口 uuid，仅出现在活动 XML 中
+    #[serde(rename = "@portid", skip_serializing_if = "Option::is_none")]
+    pub portid: Option<String>,
+    #[serde(rename = "@bridge", skip_serializing_if = "Option::is_none")]
+    pub bridge: Option<String>,
+    #[serde(rename = "@dev", skip_serializing_if = "Option::is_none")]
+    pub dev: Option<String>,
+    #[serde(rename = "@mode", skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+// 主机侧 tap 设备名
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InterfaceTarget {
+    #[serde(rename = "@dev")]
+    pub dev: String,
+}
+
+// 客户机可见的网卡型号，例如 virtio、e1000、rtl8139
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InterfaceModel {
+    #[serde(rename = "@type")]
+    pub model_type: String,
+}
+
+impl Interface {
+    pub fn new(interface_type: &str) -> Self {
+        Self {
+            interface_type: interface_type.to_string(),
+            mac: None,
+            source: None,
+            target: None,
+            model: None,
+            driver: None,
+            boot: None,
+            alias: None,
+            address: None,
+        }
+    }
+
+    pub fn with_mac(mut self, address: &str) -> Self {
+        self.mac = Some(MacAddress {
+            address: address.to_string(),
+        });
+        self
+    }
+
+    pub fn with_source(mut self, source: InterfaceSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn with_target(mut self, dev: &str) -> Self {
+        self.target = Some(InterfaceTarget {
+            dev: dev.to_string(),
+        });
+        self
+    }
+
+    pub fn with_model(mut self, model_type: &str) -> Self {
+        self.model = Some(InterfaceModel {
+            model_type: model_type.to_string(),
+        });
+        self
+    }
+
+    pub fn with_boot_order(mut self, order: u32) -> Self {
+        self.boot = Some(BootOrder::new(order));
+        self
+    }
+}
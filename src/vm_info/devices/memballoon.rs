@@ -0,0 +1,111 @@
+use super::{DeviceAddress, DeviceAlias, VirtioDriver};
+use serde::{Deserialize, Serialize};
+
+/// 内存气球设备 `<memballoon model='virtio'>`，每个域最多一个
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemBalloon {
+    /// virtio、virtio-transitional、virtio-non-transitional、xen、none
+    #[serde(rename = "@model")]
+    pub model: String,
+    /// 宿主机内存不足时允许气球自动放气
+    #[serde(rename = "@autodeflate", skip_serializing_if = "Option::is_none")]
+    pub autodeflate: Option<String>,
+    /// 客户机向宿主机报告空闲页
+    #[serde(rename = "@freePageReporting", skip_serializing_if = "Option::is_none")]
+    pub free_page_reporting: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<MemBalloonStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<VirtioDriver>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+/// 内存统计的采集周期（秒），0 表示关闭
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemBalloonStats {
+    #[serde(rename = "@period")]
+    pub period: u32,
+}
+
+impl MemBalloon {
+    pub fn virtio() -> Self {
+        Self::new("virtio")
+    }
+
+    /// 不提供气球设备
+    pub fn none() -> Self {
+        Self::new("none")
+    }
+
+    fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            autodeflate: None,
+            free_page_reporting: None,
+            stats: None,
+            driver: None,
+            alias: None,
+            address: None,
+        }
+    }
+
+    pub fn with_stats_period(mut self, seconds: u32) -> Self {
+        self.stats = Some(MemBalloonStats { period: seconds });
+        self
+    }
+
+    pub fn is_virtio(&self) -> bool {
+        self.model.starts_with("virtio")
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if !matches!(
+            self.model.as_str(),
+            "virtio" | "virtio-transitional" | "virtio-non-transitional" | "xen" | "none"
+        ) {
+            errors.push(format!("Unknown memballoon model '{}'", self.model));
+        }
+
+        for (name, value) in [
+            ("autodeflate", &self.autodeflate),
+            ("freePageReporting", &self.free_page_reporting),
+        ] {
+            let Some(value) = value else {
+                continue;
+            };
+            if value != "on" && value != "off" {
+                errors.push(format!(
+                    "memballoon {} must be 'on' or 'off', got '{}'",
+                    name, value
+                ));
+            }
+            if !self.is_virtio() {
+                errors.push(format!(
+                    "memballoon {} requires a virtio model, got '{}'",
+                    name, self.model
+                ));
+            }
+        }
+
+        if self.stats.is_some() && !self.is_virtio() {
+            errors.push(format!(
+                "memballoon <stats> requires a virtio model, got '{}'",
+                self.model
+            ));
+        }
+        if self.model == "none" && (self.driver.is_some() || self.address.is_some()) {
+            errors.push("memballoon model 'none' cannot have a <driver> or <address>".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use super::{DeviceAddress, DeviceAlias};
+use crate::MemoryValue;
+use serde::{Deserialize, Serialize};
+
+/// 内存设备（dimm、nvdimm、virtio-mem、virtio-pmem 等）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemoryDevice {
+    #[serde(rename = "@model")]
+    pub model: String,
+    #[serde(rename = "@access", skip_serializing_if = "Option::is_none")]
+    pub access: Option<String>,
+    pub target: MemoryDeviceTarget,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemoryDeviceTarget {
+    pub size: MemoryValue,
+    /// 客户机 NUMA 节点
+    #[serde(rename = "node", skip_serializing_if = "Option::is_none")]
+    pub node: Option<u32>,
+}
+
+impl MemoryDevice {
+    pub fn dimm(size: MemoryValue, node: Option<u32>) -> Self {
+        Self {
+            model: "dimm".to_string(),
+            access: None,
+            target: MemoryDeviceTarget { size, node },
+            alias: None,
+            address: None,
+        }
+    }
+
+    pub fn size_kib(&self) -> u64 {
+        self.target.size.to_kib()
+    }
+}
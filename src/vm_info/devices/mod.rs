@@ -1,10 +1,316 @@
+mod address;
+mod boot;
+mod controller;
 mod disk;
-use disk::Disk;
+mod hostdev;
+mod interface;
+mod memballoon;
+mod memory;
+mod panic;
+mod redirdev;
+mod rng;
+mod tpm;
+mod video;
+mod virtio;
+mod watchdog;
+use super::{Domain, MachineType, OsArch};
+pub use address::{DeviceAddress, DeviceAlias};
+pub use boot::{BootDevice, BootOrder};
+pub use controller::{Controller, ControllerDriver};
+pub use disk::{Disk, Driver, DriverIothread, DriverIothreads, IothreadQueue, Source, Target};
+pub use hostdev::{Hostdev, HostdevAddress, HostdevSource};
+pub use interface::{Interface, InterfaceSource};
+pub use memballoon::{MemBalloon, MemBalloonStats};
+pub use memory::{MemoryDevice, MemoryDeviceTarget};
+pub use panic::Panic;
+pub use redirdev::Redirdev;
+pub use rng::{Rng, RngBackend, RngProtocol, RngRate, RngSource};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+pub use tpm::{PcrBanks, Tpm, TpmBackend, TpmDevice, TpmEncryption};
+pub use video::{Video, VideoModel};
+pub use virtio::VirtioDriver;
+pub use watchdog::Watchdog;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Devices {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub disk: Option<Vec<Disk>>,
-    // 可扩展其他设备（网卡、控制器等）
+    #[serde(rename = "controller", skip_serializing_if = "Option::is_none")]
+    pub controllers: Option<Vec<Controller>>,
+    #[serde(rename = "interface", skip_serializing_if = "Option::is_none")]
+    pub interfaces: Option<Vec<Interface>>,
+    #[serde(rename = "hostdev", skip_serializing_if = "Option::is_none")]
+    pub hostdevs: Option<Vec<Hostdev>>,
+    #[serde(rename = "redirdev", skip_serializing_if = "Option::is_none")]
+    pub redirdevs: Option<Vec<Redirdev>>,
+    #[serde(rename = "video", skip_serializing_if = "Option::is_none")]
+    pub videos: Option<Vec<Video>>,
+    #[serde(rename = "memory", skip_serializing_if = "Option::is_none")]
+    pub memory_devices: Option<Vec<MemoryDevice>>,
+    #[serde(rename = "watchdog", skip_serializing_if = "Option::is_none")]
+    pub watchdogs: Option<Vec<Watchdog>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memballoon: Option<MemBalloon>,
+    #[serde(rename = "rng", skip_serializing_if = "Option::is_none")]
+    pub rngs: Option<Vec<Rng>>,
+    #[serde(rename = "tpm", skip_serializing_if = "Option::is_none")]
+    pub tpms: Option<Vec<Tpm>>,
+    #[serde(rename = "panic", skip_serializing_if = "Option::is_none")]
+    pub panics: Option<Vec<Panic>>,
+    // 可扩展其他设备
+}
+
+type RuntimeSlot<'a> = (
+    String,
+    &'a mut Option<DeviceAlias>,
+    &'a mut Option<DeviceAddress>,
+);
+
+impl Devices {
+    /// 每个设备的描述（如 `disk[0]`）及其别名、地址
+    pub fn aliases_and_addresses(
+        &self,
+    ) -> Vec<(String, Option<&DeviceAlias>, Option<&DeviceAddress>)> {
+        let mut slots = Vec::new();
+        for (i, d) in self.disk.iter().flatten().enumerate() {
+            slots.push((format!("disk[{}]", i), d.alias.as_ref(), d.address.as_ref()));
+        }
+        for (i, d) in self.controllers.iter().flatten().enumerate() {
+            slots.push((
+                format!("controller[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        for (i, d) in self.interfaces.iter().flatten().enumerate() {
+            slots.push((
+                format!("interface[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        for (i, d) in self.hostdevs.iter().flatten().enumerate() {
+            slots.push((
+                format!("hostdev[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        for (i, d) in self.redirdevs.iter().flatten().enumerate() {
+            slots.push((
+                format!("redirdev[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        for (i, d) in self.videos.iter().flatten().enumerate() {
+            slots.push((
+                format!("video[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        for (i, d) in self.memory_devices.iter().flatten().enumerate() {
+            slots.push((
+                format!("memory[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        for (i, d) in self.watchdogs.iter().flatten().enumerate() {
+            slots.push((
+                format!("watchdog[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        if let Some(d) = &self.memballoon {
+            slots.push((
+                "memballoon".to_string(),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        for (i, d) in self.rngs.iter().flatten().enumerate() {
+            slots.push((format!("rng[{}]", i), d.alias.as_ref(), d.address.as_ref()));
+        }
+        for (i, d) in self.tpms.iter().flatten().enumerate() {
+            slots.push((format!("tpm[{}]", i), d.alias.as_ref(), d.address.as_ref()));
+        }
+        for (i, d) in self.panics.iter().flatten().enumerate() {
+            slots.push((
+                format!("panic[{}]", i),
+                d.alias.as_ref(),
+                d.address.as_ref(),
+            ));
+        }
+        slots
+    }
+
+    /// 没有设置 `iommu='on'` 的 virtio 设备
+    pub fn virtio_without_iommu(&self) -> Vec<String> {
+        let mut devices = Vec::new();
+        for disk in self.disk.iter().flatten() {
+            if disk.target.bus == "virtio" && disk.driver.iommu.as_deref() != Some("on") {
+                devices.push(format!("disk '{}'", disk.target.dev));
+            }
+        }
+        for (i, controller) in self.controllers.iter().flatten().enumerate() {
+            let virtio = controller
+                .model
+                .as_deref()
+                .is_some_and(|m| m.starts_with("virtio"))
+                || controller.controller_type == "virtio-serial";
+            let iommu = controller
+                .driver
+                .as_ref()
+                .is_some_and(|d| d.iommu.as_deref() == Some("on"));
+            if virtio && !iommu {
+                devices.push(format!(
+                    "controller[{}] '{}'",
+                    i, controller.controller_type
+                ));
+            }
+        }
+        for (i, iface) in self.interfaces.iter().flatten().enumerate() {
+            let virtio = iface
+                .model
+                .as_ref()
+                .is_some_and(|m| m.model_type == "virtio");
+            if virtio && !iface.driver.as_ref().is_some_and(VirtioDriver::has_iommu) {
+                devices.push(format!("interface[{}]", i));
+            }
+        }
+        for (i, video) in self.videos.iter().flatten().enumerate() {
+            if video.model.model_type == "virtio"
+                && !video.driver.as_ref().is_some_and(VirtioDriver::has_iommu)
+            {
+                devices.push(format!("video[{}]", i));
+            }
+        }
+        for (i, rng) in self.rngs.iter().flatten().enumerate() {
+            if rng.is_virtio() && !rng.driver.as_ref().is_some_and(VirtioDriver::has_iommu) {
+                devices.push(format!("rng[{}]", i));
+            }
+        }
+        if let Some(balloon) = &self.memballoon
+            && balloon.is_virtio()
+            && !balloon.driver.as_ref().is_some_and(VirtioDriver::has_iommu)
+        {
+            devices.push("memballoon".to_string());
+        }
+        devices
+    }
+
+    /// 同 [`Devices::aliases_and_addresses`]，返回可修改的字段
+    pub(crate) fn aliases_and_addresses_mut(&mut self) -> Vec<RuntimeSlot<'_>> {
+        let mut slots = Vec::new();
+        for (i, d) in self.disk.iter_mut().flatten().enumerate() {
+            slots.push((format!("disk[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.controllers.iter_mut().flatten().enumerate() {
+            slots.push((format!("controller[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.interfaces.iter_mut().flatten().enumerate() {
+            slots.push((format!("interface[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.hostdevs.iter_mut().flatten().enumerate() {
+            slots.push((format!("hostdev[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.redirdevs.iter_mut().flatten().enumerate() {
+            slots.push((format!("redirdev[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.videos.iter_mut().flatten().enumerate() {
+            slots.push((format!("video[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.memory_devices.iter_mut().flatten().enumerate() {
+            slots.push((format!("memory[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.watchdogs.iter_mut().flatten().enumerate() {
+            slots.push((format!("watchdog[{}]", i), &mut d.alias, &mut d.address));
+        }
+        if let Some(d) = &mut self.memballoon {
+            slots.push(("memballoon".to_string(), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.rngs.iter_mut().flatten().enumerate() {
+            slots.push((format!("rng[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.tpms.iter_mut().flatten().enumerate() {
+            slots.push((format!("tpm[{}]", i), &mut d.alias, &mut d.address));
+        }
+        for (i, d) in self.panics.iter_mut().flatten().enumerate() {
+            slots.push((format!("panic[{}]", i), &mut d.alias, &mut d.address));
+        }
+        slots
+    }
+}
+
+impl Domain {
+    /// 验证 TPM、RNG、看门狗、panic 与内存气球设备
+    ///
+    /// 机器类型或体系结构未指定时跳过相关检查。
+    pub fn validate_devices(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let devices = &self.devices;
+        let arch = self.os.os_type.arch.as_ref().map(OsArch::as_str);
+        let machine = self.os.os_type.machine.as_ref();
+        let q35 = machine.map(MachineType::is_q35);
+        let hyperv = self.features.as_ref().is_some_and(|f| f.hyperv.is_some());
+
+        let tpms = devices.tpms.as_deref().unwrap_or_default();
+        for tpm in tpms {
+            if let Err(mut e) = tpm.validate(q35) {
+                errors.append(&mut e);
+            }
+        }
+        // spapr-tpm-proxy 可以与一个普通 TPM 共存
+        let regular = tpms
+            .iter()
+            .filter(|t| t.model.as_deref() != Some("spapr-tpm-proxy"))
+            .count();
+        if regular > 1 {
+            errors.push(format!("At most one <tpm> is allowed, found {}", regular));
+        }
+
+        for rng in devices.rngs.iter().flatten() {
+            if let Err(mut e) = rng.validate() {
+                errors.append(&mut e);
+            }
+        }
+
+        let mut models = BTreeSet::new();
+        for watchdog in devices.watchdogs.iter().flatten() {
+            if let Err(mut e) = watchdog.validate(arch, machine) {
+                errors.append(&mut e);
+            }
+            if !models.insert(watchdog.model.as_str()) {
+                errors.push(format!("Duplicate watchdog model '{}'", watchdog.model));
+            }
+        }
+
+        let mut models = BTreeSet::new();
+        for panic in devices.panics.iter().flatten() {
+            if let Err(mut e) = panic.validate(arch, hyperv) {
+                errors.append(&mut e);
+            }
+            if let Some(model) = &panic.model
+                && !models.insert(model.as_str())
+            {
+                errors.push(format!("Duplicate panic model '{}'", model));
+            }
+        }
+
+        if let Some(balloon) = &devices.memballoon
+            && let Err(mut e) = balloon.validate()
+        {
+            errors.append(&mut e);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
@@ -0,0 +1,74 @@
+use super::{DeviceAddress, DeviceAlias};
+use serde::{Deserialize, Serialize};
+
+/// 客户机崩溃通知设备 `<panic model='isa'/>`，触发 on_crash 动作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Panic {
+    /// isa、pvpanic（PCI）、hyperv、pseries、s390
+    #[serde(rename = "@model", skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+impl Panic {
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: Some(model.to_string()),
+            alias: None,
+            address: None,
+        }
+    }
+
+    /// `arch` 未知时不检查体系结构，`hyperv` 为 `<features><hyperv>` 是否启用
+    pub fn validate(&self, arch: Option<&str>, hyperv: bool) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let Some(model) = self.model.as_deref() else {
+            return Ok(());
+        };
+
+        let required_arch: &[&str] = match model {
+            "isa" | "hyperv" => &["i686", "x86_64"],
+            "pvpanic" => &["i686", "x86_64", "aarch64"],
+            "pseries" => &["ppc64", "ppc64le"],
+            "s390" => &["s390x"],
+            other => {
+                errors.push(format!(
+                    "Unknown panic model '{}', expected 'isa', 'pvpanic', 'hyperv', 'pseries' or 's390'",
+                    other
+                ));
+                &[]
+            }
+        };
+        if let Some(arch) = arch
+            && !required_arch.is_empty()
+            && !required_arch.contains(&arch)
+        {
+            errors.push(format!(
+                "Panic model '{}' is not supported on arch '{}'",
+                model, arch
+            ));
+        }
+
+        if model == "hyperv" && !hyperv {
+            errors.push("Panic model 'hyperv' requires <features><hyperv>".to_string());
+        }
+        if model != "isa"
+            && model != "pvpanic"
+            && let Some(address) = &self.address
+        {
+            errors.push(format!(
+                "Panic model '{}' does not accept an <address type='{}'>",
+                model, address.address_type
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use super::{BootOrder, DeviceAddress, DeviceAlias};
+use serde::{Deserialize, Serialize};
+
+/// USB 重定向设备 `<redirdev bus='usb' type='spicevmc|tcp'>`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Redirdev {
+    #[serde(rename = "@bus")]
+    pub bus: String,
+    #[serde(rename = "@type")]
+    pub redirdev_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot: Option<BootOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+impl Redirdev {
+    /// 通过 SPICE 通道重定向的 USB 设备
+    pub fn spicevmc() -> Self {
+        Self {
+            bus: "usb".to_string(),
+            redirdev_type: "spicevmc".to_string(),
+            boot: None,
+            alias: None,
+            address: None,
+        }
+    }
+
+    pub fn with_boot_order(mut self, order: u32) -> Self {
+        self.boot = Some(BootOrder::new(order));
+        self
+    }
+}
@@ -0,0 +1,216 @@
+use super::{DeviceAddress, DeviceAlias, VirtioDriver};
+use serde::{Deserialize, Serialize};
+
+/// random 后端允许的宿主机熵源
+const RNG_SOURCES: [&str; 3] = ["/dev/random", "/dev/urandom", "/dev/hwrng"];
+
+/// 随机数生成器 `<rng model='virtio'>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rng {
+    /// virtio、virtio-transitional、virtio-non-transitional
+    #[serde(rename = "@model")]
+    pub model: String,
+    /// 限制客户机每个周期可读取的字节数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<RngRate>,
+    pub backend: RngBackend,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<VirtioDriver>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RngRate {
+    #[serde(rename = "@bytes")]
+    pub bytes: u64,
+    /// 毫秒，默认 1000
+    #[serde(rename = "@period", skip_serializing_if = "Option::is_none")]
+    pub period: Option<u64>,
+}
+
+// 后端 <backend model='random'>/dev/urandom</backend>、model='builtin' 或 model='egd'
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RngBackend {
+    #[serde(rename = "@model")]
+    pub model: String,
+    /// egd 使用的字符设备类型，如 tcp、udp、unix
+    #[serde(rename = "@type", skip_serializing_if = "Option::is_none")]
+    pub backend_type: Option<String>,
+    /// egd 连接的字符设备 `<source>`
+    #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
+    pub egd_source: Option<RngSource>,
+    /// egd 的传输协议，只支持 raw
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<RngProtocol>,
+    /// random 后端的宿主机设备
+    #[serde(rename = "$text", skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// egd 字符设备的 `<source mode='connect' host='..' service='..'/>`，unix 类型使用 `path`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RngSource {
+    /// connect 或 bind
+    #[serde(rename = "@mode", skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(rename = "@host", skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(rename = "@service", skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    #[serde(rename = "@path", skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RngProtocol {
+    #[serde(rename = "@type")]
+    pub protocol_type: String,
+}
+
+impl Rng {
+    /// 从宿主机 /dev/urandom 取熵的 virtio RNG
+    pub fn virtio() -> Self {
+        Self {
+            model: "virtio".to_string(),
+            rate: None,
+            backend: RngBackend {
+                model: "random".to_string(),
+                backend_type: None,
+                egd_source: None,
+                protocol: None,
+                source: Some("/dev/urandom".to_string()),
+            },
+            driver: None,
+            alias: None,
+            address: None,
+        }
+    }
+
+    /// 通过 TCP 连接 EGD 守护进程的 virtio RNG
+    pub fn egd_tcp(host: &str, service: &str) -> Self {
+        let mut rng = Self::virtio();
+        rng.backend = RngBackend {
+            model: "egd".to_string(),
+            backend_type: Some("tcp".to_string()),
+            egd_source: Some(RngSource {
+                mode: Some("connect".to_string()),
+                host: Some(host.to_string()),
+                service: Some(service.to_string()),
+                path: None,
+            }),
+            protocol: Some(RngProtocol {
+                protocol_type: "raw".to_string(),
+            }),
+            source: None,
+        };
+        rng
+    }
+
+    pub fn with_rate(mut self, bytes: u64, period_ms: u64) -> Self {
+        self.rate = Some(RngRate {
+            bytes,
+            period: Some(period_ms),
+        });
+        self
+    }
+
+    pub fn is_virtio(&self) -> bool {
+        self.model.starts_with("virtio")
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if !matches!(
+            self.model.as_str(),
+            "virtio" | "virtio-transitional" | "virtio-non-transitional"
+        ) {
+            errors.push(format!("Unknown RNG model '{}'", self.model));
+        }
+
+        if let Some(rate) = &self.rate {
+            if rate.bytes == 0 {
+                errors.push("RNG rate bytes must be greater than 0".to_string());
+            }
+            if rate.period == Some(0) {
+                errors.push("RNG rate period must be greater than 0".to_string());
+            }
+        }
+
+        let backend = &self.backend;
+        match backend.model.as_str() {
+            "random" => {
+                if let Some(source) = &backend.source
+                    && !RNG_SOURCES.contains(&source.as_str())
+                {
+                    errors.push(format!(
+                        "RNG random backend source must be one of {}, got '{}'",
+                        RNG_SOURCES.join(", "),
+                        source
+                    ));
+                }
+            }
+            "egd" => {
+                if backend.backend_type.is_none() {
+                    errors.push("RNG egd backend requires a type".to_string());
+                }
+                match &backend.egd_source {
+                    None => errors.push("RNG egd backend requires a <source>".to_string()),
+                    Some(source) => {
+                        if let Some(mode) = &source.mode
+                            && mode != "connect"
+                            && mode != "bind"
+                        {
+                            errors.push(format!(
+                                "RNG egd source mode must be 'connect' or 'bind', got '{}'",
+                                mode
+                            ));
+                        }
+                        match backend.backend_type.as_deref() {
+                            Some("tcp" | "udp") if source.service.is_none() => {
+                                errors.push("RNG egd tcp/udp source requires a service".to_string())
+                            }
+                            Some("unix") if source.path.is_none() => {
+                                errors.push("RNG egd unix source requires a path".to_string())
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                if let Some(protocol) = &backend.protocol
+                    && protocol.protocol_type != "raw"
+                {
+                    errors.push(format!(
+                        "RNG egd protocol must be 'raw', got '{}'",
+                        protocol.protocol_type
+                    ));
+                }
+            }
+            "builtin" => {
+                if backend.source.is_some() {
+                    errors.push("RNG builtin backend does not accept a source".to_string());
+                }
+            }
+            other => errors.push(format!(
+                "Unknown RNG backend '{}', expected 'random', 'egd' or 'builtin'",
+                other
+            )),
+        }
+
+        if backend.model != "egd" && (backend.egd_source.is_some() || backend.protocol.is_some()) {
+            errors.push(format!(
+                "RNG {} backend does not accept <source> or <protocol>",
+                backend.model
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use super::{DeviceAddress, DeviceAlias};
+use crate::vm_info::EmptyElement;
+use serde::{Deserialize, Serialize};
+
+const TPM_MODELS: [&str; 4] = ["tpm-tis", "tpm-crb", "tpm-spapr", "spapr-tpm-proxy"];
+
+/// TPM 设备 `<tpm model='tpm-crb'>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tpm {
+    /// tpm-tis、tpm-crb、tpm-spapr、spapr-tpm-proxy
+    #[serde(rename = "@model", skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub backend: TpmBackend,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+/// TPM 后端：emulator（swtpm）、passthrough（宿主机 TPM）或 external
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TpmBackend {
+    #[serde(rename = "@type")]
+    pub backend_type: String,
+    /// 1.2 或 2.0，仅 emulator
+    #[serde(rename = "@version", skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 删除域时保留 TPM 状态，仅 emulator
+    #[serde(rename = "@persistent_state", skip_serializing_if = "Option::is_none")]
+    pub persistent_state: Option<String>,
+    /// passthrough 使用的宿主机设备
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<TpmDevice>,
+    /// 加密 TPM 状态的 secret
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<TpmEncryption>,
+    /// 启用的 PCR bank，仅 TPM 2.0
+    #[serde(rename = "active_pcr_banks", skip_serializing_if = "Option::is_none")]
+    pub active_pcr_banks: Option<PcrBanks>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TpmDevice {
+    #[serde(rename = "@path")]
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TpmEncryption {
+    #[serde(rename = "@secret")]
+    pub secret: String,
+}
+
+/// `<active_pcr_banks>`，每个 bank 为一个空元素
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PcrBanks {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<EmptyElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<EmptyElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha384: Option<EmptyElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha512: Option<EmptyElement>,
+}
+
+impl PcrBanks {
+    /// 按名称创建，无法识别的名称返回错误
+    pub fn from_names(names: &[&str]) -> Result<Self, String> {
+        let mut banks = Self::default();
+        for name in names {
+            let bank = match *name {
+                "sha1" => &mut banks.sha1,
+                "sha256" => &mut banks.sha256,
+                "sha384" => &mut banks.sha384,
+                "sha512" => &mut banks.sha512,
+                other => return Err(format!("Unknown PCR bank '{}'", other)),
+            };
+            *bank = Some(EmptyElement);
+        }
+        Ok(banks)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        [
+            ("sha1", &self.sha1),
+            ("sha256", &self.sha256),
+            ("sha384", &self.sha384),
+            ("sha512", &self.sha512),
+        ]
+        .into_iter()
+        .filter(|(_, bank)| bank.is_some())
+        .map(|(name, _)| name)
+        .collect()
+    }
+}
+
+impl Tpm {
+    /// swtpm 模拟的 TPM
+    pub fn emulator(model: &str, version: &str) -> Self {
+        Self {
+            model: Some(model.to_string()),
+            backend: TpmBackend {
+                backend_type: "emulator".to_string(),
+                version: Some(version.to_string()),
+                persistent_state: None,
+                device: None,
+                encryption: None,
+                active_pcr_banks: None,
+            },
+            alias: None,
+            address: None,
+        }
+    }
+
+    /// 直通宿主机 TPM，如 /dev/tpm0
+    pub fn passthrough(model: &str, path: &str) -> Self {
+        Self {
+            model: Some(model.to_string()),
+            backend: TpmBackend {
+                backend_type: "passthrough".to_string(),
+                version: None,
+                persistent_state: None,
+                device: Some(TpmDevice {
+                    path: path.to_string(),
+                }),
+                encryption: None,
+                active_pcr_banks: None,
+            },
+            alias: None,
+            address: None,
+        }
+    }
+
+    pub fn with_persistent_state(mut self) -> Self {
+        self.backend.persistent_state = Some("yes".to_string());
+        self
+    }
+
+    pub fn with_pcr_banks(mut self, banks: PcrBanks) -> Self {
+        self.backend.active_pcr_banks = Some(banks);
+        self
+    }
+
+    /// `q35` 为机器类型是否为 q35，未知时不检查
+    pub fn validate(&self, q35: Option<bool>) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let backend = &self.backend;
+        let model = self.model.as_deref().unwrap_or("tpm-tis");
+
+        if !TPM_MODELS.contains(&model) {
+            errors.push(format!(
+                "Unknown TPM model '{}', expected one of {}",
+                model,
+                TPM_MODELS.join(", ")
+            ));
+        }
+
+        let emulator = backend.backend_type == "emulator";
+        match backend.backend_type.as_str() {
+            "emulator" | "external" => {
+                if backend.device.is_some() {
+                    errors.push(format!(
+                        "TPM backend '{}' does not accept a <device>",
+                        backend.backend_type
+                    ));
+                }
+            }
+            "passthrough" => {
+                if backend.device.is_none() {
+                    errors.push("TPM passthrough backend requires <device path=...>".to_string());
+                }
+            }
+            other => errors.push(format!(
+                "Unknown TPM backend '{}', expected 'emulator', 'passthrough' or 'external'",
+                other
+            )),
+        }
+
+        if let Some(version) = &backend.version {
+            if version != "1.2" && version != "2.0" {
+                errors.push(format!(
+                    "TPM version must be '1.2' or '2.0', got '{}'",
+                    version
+                ));
+            }
+            if !emulator {
+                errors.push("TPM version is only valid for the emulator backend".to_string());
+            }
+        }
+        if let Some(state) = &backend.persistent_state {
+            if state != "yes" && state != "no" {
+                errors.push(format!(
+                    "TPM persistent_state must be 'yes' or 'no', got '{}'",
+                    state
+                ));
+            }
+            if !emulator {
+                errors.push(
+                    "TPM persistent_state is only valid for the emulator backend".to_string(),
+                );
+            }
+        }
+        if let Some(banks) = &backend.active_pcr_banks {
+            // 未指定版本时 libvirt 默认使用 2.0
+            if !emulator || backend.version.as_deref() == Some("1.2") {
+                errors.push(
+                    "TPM <active_pcr_banks> requires an emulator backend with TPM 2.0".to_string(),
+                );
+            }
+            if banks.names().is_empty() {
+                errors.push("TPM <active_pcr_banks> must enable at least one bank".to_string());
+            }
+        }
+
+        // CRB 接口只定义于 TPM 2.0
+        if model == "tpm-crb" {
+            match backend.version.as_deref() {
+                Some("1.2") => {
+                    errors.push("TPM model 'tpm-crb' requires version '2.0'".to_string())
+                }
+                Some("2.0") => {}
+                _ if q35 == Some(false) => errors.push(
+                    "TPM model 'tpm-crb' requires a q35 machine type or version='2.0'".to_string(),
+                ),
+                _ => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
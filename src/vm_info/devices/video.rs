@@ -0,0 +1,76 @@
+use super::{DeviceAddress, DeviceAlias, VirtioDriver};
+use serde::{Deserialize, Serialize};
+
+/// 显卡设备
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Video {
+    pub model: VideoModel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<VirtioDriver>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+/// 显卡型号及显存配置，显存单位均为 KiB
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VideoModel {
+    /// vga、cirrus、qxl、virtio、bochs、ramfb、none 等
+    #[serde(rename = "@type")]
+    pub model_type: String,
+    #[serde(rename = "@vram", skip_serializing_if = "Option::is_none")]
+    pub vram: Option<u64>,
+    /// 仅 qxl
+    #[serde(rename = "@ram", skip_serializing_if = "Option::is_none")]
+    pub ram: Option<u64>,
+    /// 仅 qxl
+    #[serde(rename = "@vgamem", skip_serializing_if = "Option::is_none")]
+    pub vgamem: Option<u64>,
+    /// 仅 qxl
+    #[serde(rename = "@vram64", skip_serializing_if = "Option::is_none")]
+    pub vram64: Option<u64>,
+    #[serde(rename = "@heads", skip_serializing_if = "Option::is_none")]
+    pub heads: Option<u32>,
+    #[serde(rename = "@primary", skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>,
+}
+
+impl Video {
+    pub fn new(model_type: &str) -> Self {
+        Self {
+            model: VideoModel {
+                model_type: model_type.to_string(),
+                vram: None,
+                ram: None,
+                vgamem: None,
+                vram64: None,
+                heads: None,
+                primary: None,
+            },
+            driver: None,
+            alias: None,
+            address: None,
+        }
+    }
+
+    pub fn with_vram(mut self, vram_kib: u64) -> Self {
+        self.model.vram = Some(vram_kib);
+        self
+    }
+
+    /// 显卡占用的 QEMU 进程内存（KiB），未配置时使用 libvirt 的默认值
+    pub fn memory_kib(&self) -> u64 {
+        let m = &self.model;
+        match m.model_type.as_str() {
+            "qxl" => {
+                m.vram.unwrap_or(65536)
+                    + m.ram.unwrap_or(65536)
+                    + m.vgamem.unwrap_or(16384)
+                    + m.vram64.unwrap_or(0)
+            }
+            "vga" | "cirrus" | "vmvga" | "bochs" => m.vram.unwrap_or(16384),
+            _ => m.vram.unwrap_or(0),
+        }
+    }
+}
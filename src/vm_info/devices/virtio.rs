@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+// virtio 设备通用的 <driver iommu='on' ats='on' packed='on'/>
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VirtioDriver {
+    /// 通过虚拟 IOMMU 访问内存，机密计算客户机需要 'on'
+    #[serde(rename = "@iommu", skip_serializing_if = "Option::is_none")]
+    pub iommu: Option<String>,
+    /// PCIe Address Translation Services
+    #[serde(rename = "@ats", skip_serializing_if = "Option::is_none")]
+    pub ats: Option<String>,
+    /// 使用 packed virtqueue
+    #[serde(rename = "@packed", skip_serializing_if = "Option::is_none")]
+    pub packed: Option<String>,
+}
+
+impl VirtioDriver {
+    pub fn iommu() -> Self {
+        Self {
+            iommu: Some("on".to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn has_iommu(&self) -> bool {
+        self.iommu.as_deref() == Some("on")
+    }
+}
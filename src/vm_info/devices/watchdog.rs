@@ -0,0 +1,101 @@
+use super::{DeviceAddress, DeviceAlias};
+use crate::vm_info::MachineType;
+use serde::{Deserialize, Serialize};
+
+const WATCHDOG_ACTIONS: [&str; 7] = [
+    "reset",
+    "shutdown",
+    "poweroff",
+    "pause",
+    "none",
+    "dump",
+    "inject-nmi",
+];
+
+/// 看门狗设备 `<watchdog model='i6300esb' action='reset'/>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Watchdog {
+    /// i6300esb（PCI）、ib700（ISA）、diag288（s390）、itco（q35 芯片组内置）
+    #[serde(rename = "@model")]
+    pub model: String,
+    /// 超时后的动作，默认 reset
+    #[serde(rename = "@action", skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<DeviceAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<DeviceAddress>,
+}
+
+impl Watchdog {
+    pub fn new(model: &str, action: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            action: Some(action.to_string()),
+            alias: None,
+            address: None,
+        }
+    }
+
+    /// `arch` 与 `machine` 未知时不检查
+    pub fn validate(
+        &self,
+        arch: Option<&str>,
+        machine: Option<&MachineType>,
+    ) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        match self.model.as_str() {
+            "i6300esb" => {
+                if let Some(machine) = machine
+                    && !machine.has_pci()
+                {
+                    errors.push(format!(
+                        "Watchdog model 'i6300esb' requires a machine type with PCI, got '{}'",
+                        machine
+                    ));
+                }
+            }
+            "ib700" => {
+                if !arch.is_none_or(|a| matches!(a, "i686" | "x86_64")) {
+                    errors.push("Watchdog model 'ib700' requires an x86 guest".to_string());
+                }
+            }
+            "itco" => {
+                if machine.is_some_and(|m| !m.is_q35()) {
+                    errors.push("Watchdog model 'itco' requires a q35 machine type".to_string());
+                }
+            }
+            "diag288" => {
+                if let Some(arch) = arch
+                    && arch != "s390x"
+                {
+                    errors.push(format!(
+                        "Watchdog model 'diag288' requires arch 's390x', got '{}'",
+                        arch
+                    ));
+                }
+            }
+            other => errors.push(format!("Unknown watchdog model '{}'", other)),
+        }
+        if self.model == "itco" && self.address.is_some() {
+            errors.push("Watchdog model 'itco' does not accept an <address>".to_string());
+        }
+
+        if let Some(action) = &self.action
+            && !WATCHDOG_ACTIONS.contains(&action.as_str())
+        {
+            errors.push(format!(
+                "Unknown watchdog action '{}', expected one of {}",
+                action,
+                WATCHDOG_ACTIONS.join(", ")
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use super::memory::{CurrentMemory, MaxMemory};
+use super::sysinfo::normalize_uuid;
 use super::{
-    BlkioTune, CpuConfig, Cputune, Devices, Features, LifecycleAction, MemTune, Memory,
-    MemoryBacking, MetaData, NumaTune, Os, PowerManagement, ResourceConfig, Sysinfo,
-    ThrottleGroups, Vcpu, Vcpus,
+    BlkioTune, CapabilityRegistry, Clock, CpuArch, CpuConfig, CpuMap, Cputune, DefaultIothread,
+    Devices, Features, Hypervisor, IothreadIds, KeyWrap, LaunchSecurity, LifecycleAction,
+    LifecycleConfig, MemTune, Memory, MemoryBacking, MemoryEstimate, MetaData, NumaTune, Os,
+    OsArch, OsKind, OsType, PowerManagement, ResctrlInfo, ResourceConfig, Seclabel, Sysinfo,
+    ThrottleGroups, TimerName, Vcpu, Vcpus,
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,10 +13,11 @@ use serde::{Deserialize, Serialize};
 #[serde(rename = "domain")]
 pub struct Domain {
     #[serde(rename = "@type")]
-    pub domain_type: String,
-    // 基本信息
-    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_type: Hypervisor,
+    /// 运行中的域的 id，仅出现在活动 XML 中
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    // 基本信息
     #[serde(skip_serializing_if = "Option::is_none")]
     pub uuid: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -36,8 +40,21 @@ pub struct Domain {
     pub vcpu: Vcpu, //虚拟机最大cpu
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vcpus: Option<Vcpus>, //控制每个vcpu的状态
+    /// IOThread 数量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iothreads: Option<u32>,
+    #[serde(rename = "iothreadids", skip_serializing_if = "Option::is_none")]
+    pub iothread_ids: Option<IothreadIds>,
+    #[serde(rename = "defaultiothread", skip_serializing_if = "Option::is_none")]
+    pub default_iothread: Option<DefaultIothread>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cputune: Option<Cputune>, // cpu可调参数
     // 引导
+    /// 宿主机上的引导程序（如 Xen 的 pygrub），与 `<os><kernel>` 互斥
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootloader: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootloader_args: Option<String>,
     pub os: Os, // 虚拟机的引导
     #[serde(rename = "sysinfo", skip_serializing_if = "Option::is_none")]
     pub sysinfo: Option<Vec<Sysinfo>>,
@@ -54,6 +71,9 @@ pub struct Domain {
     /// CPU 配置
     #[serde(rename = "cpu", skip_serializing_if = "Option::is_none")]
     pub cpu: Option<CpuConfig>,
+    /// 时钟配置
+    #[serde(rename = "clock", skip_serializing_if = "Option::is_none")]
+    pub clock: Option<Clock>,
 
     /// 生命周期配置
     #[serde(rename = "on_poweroff", skip_serializing_if = "Option::is_none")]
@@ -76,4 +96,381 @@ pub struct Domain {
 
     // 设备
     pub devices: Devices,
+
+    /// 安全标签
+    #[serde(rename = "seclabel", skip_serializing_if = "Option::is_none")]
+    pub seclabels: Option<Vec<Seclabel>>,
+    /// s390 密钥包装
+    #[serde(rename = "keywrap", skip_serializing_if = "Option::is_none")]
+    pub keywrap: Option<KeyWrap>,
+    /// 机密计算启动参数
+    #[serde(rename = "launchSecurity", skip_serializing_if = "Option::is_none")]
+    pub launch_security: Option<LaunchSecurity>,
+}
+
+impl Domain {
+    /// 创建最小可用的域配置（内存为 0，1 个 vCPU，hvm 引导，无设备）
+    pub fn new(domain_type: &str, name: &str) -> Self {
+        Self {
+            domain_type: Hypervisor::from(domain_type),
+            id: None,
+            uuid: None,
+            genid: None,
+            title: None,
+            description: None,
+            metadata: None,
+            name: name.to_string(),
+            memory: Memory {
+                unit: "KiB".to_string(),
+                value: 0,
+            },
+            max_memory: None,
+            current_memory: None,
+            vcpu: Vcpu {
+                placement: None,
+                cpuset: None,
+                current: None,
+                vcpu_count: 1,
+            },
+            vcpus: None,
+            iothreads: None,
+            iothread_ids: None,
+            default_iothread: None,
+            cputune: None,
+            bootloader: None,
+            bootloader_args: None,
+            os: Os::new(OsType::new(OsKind::Hvm)),
+            sysinfo: None,
+            memory_backing: None,
+            memtune: None,
+            numatune: None,
+            blkio_tune: None,
+            resource: None,
+            cpu: None,
+            clock: None,
+            on_poweroff: None,
+            on_reboot: None,
+            on_crash: None,
+            on_lockfailure: None,
+            power_management: None,
+            throttle_groups: None,
+            features: None,
+            devices: Devices::default(),
+            seclabels: None,
+            keywrap: None,
+            launch_security: None,
+        }
+    }
+}
+
+impl Domain {
+    /// 汇总 on_poweroff/on_reboot/on_crash/on_lockfailure 配置
+    pub fn lifecycle_config(&self) -> LifecycleConfig {
+        LifecycleConfig {
+            on_poweroff: self.on_poweroff,
+            on_reboot: self.on_reboot,
+            on_crash: self.on_crash,
+            on_lockfailure: self.on_lockfailure,
+        }
+    }
+}
+
+impl Domain {
+    /// 解析域 XML，`<metadata>` 中的元素保留原文
+    pub fn from_xml(xml: &str) -> Result<Self, String> {
+        let mut domain: Domain =
+            quick_xml::de::from_str(xml).map_err(|e| format!("Failed to parse domain: {}", e))?;
+        if let Some(metadata) = &mut domain.metadata {
+            metadata.capture_raw(xml)?;
+        }
+        Ok(domain)
+    }
+
+    /// 序列化为 XML，未修改的 `<metadata>` 元素按原文写回
+    pub fn to_xml(&self) -> Result<String, String> {
+        let xml = quick_xml::se::to_string(self)
+            .map_err(|e| format!("Failed to serialize domain: {}", e))?;
+        match &self.metadata {
+            Some(metadata) => metadata.restore_raw(xml),
+            None => Ok(xml),
+        }
+    }
+
+    /// `<metadata>`，不存在时创建
+    pub fn metadata_mut(&mut self) -> &mut MetaData {
+        self.metadata.get_or_insert_with(MetaData::default)
+    }
+}
+
+impl Domain {
+    /// 按 `os.type@arch` 与 `domain@type` 验证 `<features>`
+    pub fn validate_features(&self) -> Result<(), Vec<String>> {
+        let Some(features) = &self.features else {
+            return Ok(());
+        };
+        let mut errors = Vec::new();
+
+        if let Err(mut e) = features.validate(
+            self.os.os_type.arch.as_ref().map(OsArch::as_str),
+            self.domain_type.as_str(),
+        ) {
+            errors.append(&mut e);
+        }
+
+        if let Some(hyperv) = &features.hyperv
+            && let Err(mut e) = hyperv.validate(self.cpu.as_ref())
+        {
+            errors.append(&mut e);
+        }
+
+        // QEMU 的 hv-stimer 依赖 hv-time（hypervclock）
+        if let Some(hyperv) = &features.hyperv
+            && !hyperv.is_passthrough()
+            && hyperv.stimer_enabled()
+            && !self
+                .clock
+                .as_ref()
+                .is_some_and(|c| c.timer_present(TimerName::Hypervclock))
+        {
+            errors.push(
+                "Hyper-V 'stimer' requires <clock><timer name='hypervclock' present='yes'/>"
+                    .to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 检查 Windows 客户机 GPU 直通所需的 `<features>` 组合
+    pub fn validate_gpu_passthrough(&self) -> Result<(), Vec<String>> {
+        match &self.features {
+            Some(features) => features.validate_gpu_passthrough(),
+            None => Err(vec![
+                "GPU passthrough requires <features> with <kvm><hidden state='on'/></kvm>"
+                    .to_string(),
+            ]),
+        }
+    }
+}
+
+impl Domain {
+    /// 验证 `<clock>`，hypervclock 需要结合 `<features>` 检查
+    pub fn validate_clock(&self) -> Result<(), Vec<String>> {
+        match &self.clock {
+            Some(clock) => clock.validate(self.features.as_ref()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Domain {
+    /// 估算 QEMU 进程的内存占用，用于设置 `<memtune>`
+    pub fn estimate_memory(&self) -> MemoryEstimate {
+        MemoryEstimate::from_domain(self)
+    }
+
+    /// 验证 `<memtune>`，包括 hard_limit 是否足以容纳估算的内存占用
+    pub fn validate_memtune(&self) -> Result<(), Vec<String>> {
+        match &self.memtune {
+            Some(memtune) => memtune.validate_for(self),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Domain {
+    /// 验证 `<cputune>` 中的 cachetune/memorytune，`info` 为宿主机 resctrl 能力
+    pub fn validate_resctrl(&self, info: Option<&ResctrlInfo>) -> Result<(), Vec<String>> {
+        match &self.cputune {
+            Some(cputune) => cputune.validate_resctrl(info),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Domain {
+    /// 验证 `<cpu>`，模型与特性名称按内置 cpu_map 检查
+    pub fn validate_cpu(&self) -> Result<(), Vec<String>> {
+        let Some(cpu) = &self.cpu else {
+            return Ok(());
+        };
+        let mut errors = Vec::new();
+        if let Err(mut e) = cpu.validate(Some(self.vcpu.vcpu_count)) {
+            errors.append(&mut e);
+        }
+        let arch = self
+            .os
+            .os_type
+            .arch
+            .as_ref()
+            .and_then(|a| CpuArch::from_arch(a.as_str()));
+        if let Err(mut e) = cpu.validate_names(CpuMap::builtin(), arch) {
+            errors.append(&mut e);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Domain {
+    /// 验证 vcpupin 与 vcpusched/iothreadsched 的调度策略和 id
+    pub fn validate_scheduling(&self) -> Result<(), Vec<String>> {
+        match &self.cputune {
+            Some(cputune) => {
+                cputune.validate_scheduling(self.vcpu.vcpu_count, Some(&self.iothread_ids()))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Domain {
+    /// 验证 `<os>` 以及 `<bootloader>`
+    ///
+    /// `<type>` 与 `domain@type` 是否匹配由 [`CapabilityRegistry`] 检查。
+    pub fn validate_os(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        if let Err(mut e) = self.os.validate() {
+            errors.append(&mut e);
+        }
+
+        let kind = self.os.os_type.value;
+
+        if self.bootloader.is_some() {
+            if self.os.kernel.is_some() {
+                errors.push("<bootloader> cannot be combined with <os><kernel>".to_string());
+            }
+            if !kind.is_paravirt() {
+                errors.push(format!(
+                    "<bootloader> requires a paravirtualized Xen guest, not '{}'",
+                    kind
+                ));
+            }
+        } else if self.bootloader_args.is_some() {
+            errors.push("<bootloader_args> requires <bootloader>".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Domain {
+    /// 验证 `<sysinfo>` 及其与 `<uuid>`、`<os><smbios>` 的一致性
+    pub fn validate_sysinfo(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let sysinfo = self.sysinfo.as_deref().unwrap_or_default();
+
+        for kind in ["smbios", "fwcfg"] {
+            let count = sysinfo.iter().filter(|s| s.sysinfo_type() == kind).count();
+            if count > 1 {
+                errors.push(format!("Only one <sysinfo type='{}'> is allowed", kind));
+            }
+        }
+        for info in sysinfo {
+            if let Err(mut e) = info.validate() {
+                errors.append(&mut e);
+            }
+        }
+
+        let smbios = sysinfo.iter().find_map(Sysinfo::as_smbios);
+        if let (Some(system_uuid), Some(uuid)) = (smbios.and_then(|s| s.system_uuid()), &self.uuid)
+            && let (Some(a), Some(b)) = (normalize_uuid(system_uuid), normalize_uuid(uuid))
+            && a != b
+        {
+            errors.push(format!(
+                "SMBIOS system uuid '{}' does not match domain uuid '{}'",
+                system_uuid, uuid
+            ));
+        }
+
+        if let Some(mode) = self.os.smbios.as_ref().map(|s| s.mode.as_str()) {
+            match mode {
+                "sysinfo" if smbios.is_none() => errors.push(
+                    "<os><smbios mode='sysinfo'/> requires a <sysinfo type='smbios'> block"
+                        .to_string(),
+                ),
+                "sysinfo" | "emulate" | "host" => {}
+                other => errors.push(format!(
+                    "<os><smbios> mode must be emulate, host or sysinfo, got '{}'",
+                    other
+                )),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Domain {
+    /// 使用内置能力表执行全部验证，见 [`Domain::validate_with`]
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        self.validate_with(&CapabilityRegistry::default())
+    }
+
+    /// 执行全部验证，与域类型相关的规则由 `registry` 决定
+    ///
+    /// 需要宿主机信息的检查（如 resctrl 容量）不在此列。
+    pub fn validate_with(&self, registry: &CapabilityRegistry) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if let Err(mut e) = registry.validate(self) {
+            errors.append(&mut e);
+        }
+        if let Err(mut e) = self.lifecycle_config().validate() {
+            errors.append(&mut e);
+        }
+        if let Some(resource) = &self.resource
+            && let Err(mut e) = resource.validate()
+        {
+            errors.append(&mut e);
+        }
+        if let Some(metadata) = &self.metadata
+            && let Err(mut e) = metadata.validate()
+        {
+            errors.append(&mut e);
+        }
+
+        let checks = [
+            self.validate_os(),
+            self.validate_firmware(),
+            self.validate_boot_order(),
+            self.validate_sysinfo(),
+            self.validate_features(),
+            self.validate_cpu(),
+            self.validate_clock(),
+            self.validate_memtune(),
+            self.validate_resctrl(None),
+            self.validate_scheduling(),
+            self.validate_iothreads(),
+            self.validate_security(),
+            self.validate_devices(),
+        ];
+        for check in checks {
+            if let Err(mut e) = check {
+                errors.append(&mut e);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
@@ -1,3 +1,6 @@
+use super::{LifecycleAction, LifecycleEvent};
+use serde::{Deserialize, Serialize};
+
 /// 生命周期事件配置
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct LifecycleConfig {
@@ -74,21 +77,15 @@ impl LifecycleConfig {
         }
     }
 
-    /// 验证配置
-    pub fn validate(&self, driver: Option<&str>) -> Result<(), Vec<String>> {
+    /// 验证事件与动作的对应关系，域类型是否支持由 [`CapabilityRegistry`] 检查
+    ///
+    /// [`CapabilityRegistry`]: crate::vm_info::CapabilityRegistry
+    pub fn validate(&self) -> Result<(), Vec<String>> {
         let mut errors = Vec::new();
 
         // 检查动作的有效性
         self.validate_actions(&mut errors);
 
-        // 检查驱动兼容性
-        if let Some(driver_name) = driver {
-            self.validate_driver_compatibility(driver_name, &mut errors);
-        }
-
-        // 检查特定组合限制
-        self.validate_action_combinations(&mut errors);
-
         if errors.is_empty() {
             Ok(())
         } else {
@@ -96,6 +93,22 @@ impl LifecycleConfig {
         }
     }
 
+    /// 合法但少见的配置，不影响验证结果
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        // 检查安装场景的常见配置
+        if self.on_reboot == Some(LifecycleAction::Destroy)
+            && self
+                .on_poweroff
+                .is_some_and(|a| a != LifecycleAction::Destroy)
+        {
+            warnings.push(
+                "Unusual configuration. When on_reboot=destroy, typically on_poweroff should also be destroy".to_string(),
+            );
+        }
+        warnings
+    }
+
     /// 验证动作的有效性
     fn validate_actions(&self, errors: &mut Vec<String>) {
         let checks = vec![
@@ -112,11 +125,12 @@ impl LifecycleConfig {
                         "Invalid action '{}' for event '{}'. Allowed actions: {}",
                         action,
                         event,
-                        event.allowed_actions()
-                             .iter()
-                             .map(|a| a.to_string())
-                             .collect::<Vec<_>>()
-                             .join(", ")
+                        event
+                            .allowed_actions()
+                            .iter()
+                            .map(|a| a.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
                     ));
                 }
 
@@ -138,62 +152,6 @@ impl LifecycleConfig {
         }
     }
 
-    /// 验证驱动兼容性
-    fn validate_driver_compatibility(&self, driver: &str, errors: &mut Vec<String>) {
-        let driver_lower = driver.to_lowercase();
-
-        let checks = vec![
-            (LifecycleEvent::OnPoweroff, self.on_poweroff),
-            (LifecycleEvent::OnReboot, self.on_reboot),
-            (LifecycleEvent::OnCrash, self.on_crash),
-            (LifecycleEvent::OnLockfailure, self.on_lockfailure),
-        ];
-
-        for (event, action_opt) in checks {
-            if let Some(action) = action_opt {
-                if !action.is_supported_by_driver(&driver_lower) {
-                    errors.push(format!(
-                        "Action '{}' for event '{}' is not supported by driver '{}'",
-                        action, event, driver
-                    ));
-                }
-
-                // QEMU/KVM/HVF 特定限制
-                if matches!(driver_lower.as_str(), "qemu" | "kvm" | "hvf") {
-                    if matches!(event, LifecycleEvent::OnPoweroff | LifecycleEvent::OnReboot) {
-                        if matches!(action, LifecycleAction::Preserve | LifecycleAction::RenameRestart) {
-                            errors.push(format!(
-                                "QEMU/KVM/HVF driver does not support action '{}' for event '{}'",
-                                action, event
-                            ));
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    /// 验证动作组合限制
-    fn validate_action_combinations(&self, errors: &mut Vec<String>) {
-        // QEMU/KVM/HVF 限制：on_poweroff=restart 和 on_reboot=destroy 的组合是禁止的
-        if let (Some(poweroff_action), Some(reboot_action)) = (self.on_poweroff, self.on_reboot) {
-            if poweroff_action == LifecycleAction::Restart
-                && reboot_action == LifecycleAction::Destroy {
-                errors.push(
-                    "QEMU/KVM/HVF: The combination of on_poweroff=restart and on_reboot=destroy is forbidden".to_string()
-                );
-            }
-        }
-
-        // 检查安装场景的常见配置
-        if self.on_reboot == Some(LifecycleAction::Destroy)
-            && self.on_poweroff != Some(LifecycleAction::Destroy) {
-            errors.push(
-                "Warning: Unusual configuration. When on_reboot=destroy, typically on_poweroff should also be destroy".to_string()
-            );
-        }
-    }
-
     /// 获取配置摘要
     pub fn get_summary(&self) -> String {
         let mut summary = String::from("Lifecycle Event Configuration:\n");
@@ -223,7 +181,7 @@ impl LifecycleConfig {
         // 操作系统安装期间的默认配置
         Self::new()
             .on_poweroff(LifecycleAction::Destroy)
-            .on_reboot(LifecycleAction::Destroy)  // 在安装过程中，重启被视为关机
+            .on_reboot(LifecycleAction::Destroy) // 在安装过程中，重启被视为关机
     }
 
     /// 获取生产环境默认配置
@@ -240,17 +198,17 @@ impl LifecycleConfig {
         Self::new()
             .on_poweroff(LifecycleAction::Destroy)
             .on_reboot(LifecycleAction::Restart)
-            .on_crash(LifecycleAction::Preserve)  // 保留崩溃环境用于调试
-            .on_lockfailure(LifecycleAction::Pause)  // 暂停以便调试锁定问题
+            .on_crash(LifecycleAction::Preserve) // 保留崩溃环境用于调试
+            .on_lockfailure(LifecycleAction::Pause) // 暂停以便调试锁定问题
     }
 
     /// 获取高可用性配置
     pub fn high_availability_config() -> Self {
         Self::new()
-            .on_poweroff(LifecycleAction::Restart)  // 自动重启
+            .on_poweroff(LifecycleAction::Restart) // 自动重启
             .on_reboot(LifecycleAction::Restart)
-            .on_crash(LifecycleAction::CoredumpRestart)  // 转储核心后重启
-            .on_lockfailure(LifecycleAction::Restart)  // 重启以重新获取锁
+            .on_crash(LifecycleAction::CoredumpRestart) // 转储核心后重启
+            .on_lockfailure(LifecycleAction::Restart) // 重启以重新获取锁
     }
 
     /// 获取调试配置
@@ -1,3 +1,7 @@
+use super::LifecycleAction;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
 /// 生命周期事件类型
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
 #[serde(rename_all = "lowercase")]
@@ -78,10 +82,7 @@ impl LifecycleEvent {
                 "virsh reboot command",
                 "Guest OS reboot request",
             ],
-            LifecycleEvent::OnCrash => vec![
-                "Guest OS crash",
-                "Hypervisor crash detection",
-            ],
+            LifecycleEvent::OnCrash => vec!["Guest OS crash", "Hypervisor crash detection"],
             LifecycleEvent::OnLockfailure => vec![
                 "Lock manager loses resource locks",
                 "Distributed lock management failure",
@@ -99,4 +100,4 @@ impl fmt::Display for LifecycleEvent {
             LifecycleEvent::OnLockfailure => write!(f, "on_lockfailure"),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,18 @@
+mod lifecycle_config;
+mod lifecycle_event;
+mod lifecycle_manager;
+mod state_machine;
+
+pub use lifecycle_config::LifecycleConfig;
+pub use lifecycle_event::LifecycleEvent;
+pub use lifecycle_manager::LifecycleManager;
 use serde::{Deserialize, Serialize};
+pub use state_machine::{DomainEvent, DomainState, DomainStateMachine, SideEffect, Transition};
 use std::fmt;
 
 /// 生命周期动作枚举
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "kebab-case")]
 pub enum LifecycleAction {
     /// 销毁域并释放所有资源
     Destroy,
@@ -56,14 +65,6 @@ impl LifecycleAction {
             LifecycleAction::Poweroff | LifecycleAction::Pause | LifecycleAction::Ignore
         )
     }
-
-    /// 检查动作是否受特定驱动支持
-    pub fn is_supported_by_driver(&self, driver: &str) -> bool {
-        match self {
-            LifecycleAction::RenameRestart => driver == "libxl",
-            _ => true,
-        }
-    }
 }
 
 impl fmt::Display for LifecycleAction {
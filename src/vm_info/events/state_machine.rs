@@ -0,0 +1,360 @@
+use super::{LifecycleAction, LifecycleConfig, LifecycleEvent};
+use crate::vm_info::Domain;
+use crate::vm_info::pm::{PowerManagement, SleepState};
+use std::fmt;
+
+/// 域运行状态（对应 virDomainState 的主要取值）
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum DomainState {
+    /// 已关闭
+    #[default]
+    Shutoff,
+    /// 运行中
+    Running,
+    /// 已暂停
+    Paused,
+    /// 客户机通过电源管理挂起（S3）
+    PmSuspended,
+    /// 已崩溃，资源被保留
+    Crashed,
+    /// 已关机，资源被保留
+    Shutdown,
+}
+
+impl fmt::Display for DomainState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainState::Shutoff => write!(f, "shutoff"),
+            DomainState::Running => write!(f, "running"),
+            DomainState::Paused => write!(f, "paused"),
+            DomainState::PmSuspended => write!(f, "pmsuspended"),
+            DomainState::Crashed => write!(f, "crashed"),
+            DomainState::Shutdown => write!(f, "shutdown"),
+        }
+    }
+}
+
+/// 输入到状态机的事件
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DomainEvent {
+    /// 管理端启动域（virDomainCreate）
+    Start,
+    /// 管理端强制销毁域（virDomainDestroy）
+    Destroy,
+    /// 管理端恢复暂停的域（virDomainResume）
+    Resume,
+    /// 客户机请求关机
+    GuestPoweroff,
+    /// 客户机请求重启
+    GuestReboot,
+    /// 客户机崩溃
+    GuestCrash,
+    /// 锁管理器丢失资源锁
+    LockFailure,
+    /// 客户机挂起到内存（S3）
+    SuspendToMem,
+    /// 客户机挂起到磁盘（S4）
+    SuspendToDisk,
+    /// 唤醒挂起的客户机
+    Wakeup,
+}
+
+impl DomainEvent {
+    /// 对应的 on_* 生命周期事件
+    pub fn lifecycle_event(&self) -> Option<LifecycleEvent> {
+        match self {
+            DomainEvent::GuestPoweroff => Some(LifecycleEvent::OnPoweroff),
+            DomainEvent::GuestReboot => Some(LifecycleEvent::OnReboot),
+            DomainEvent::GuestCrash => Some(LifecycleEvent::OnCrash),
+            DomainEvent::LockFailure => Some(LifecycleEvent::OnLockfailure),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DomainEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainEvent::Start => write!(f, "start"),
+            DomainEvent::Destroy => write!(f, "destroy"),
+            DomainEvent::Resume => write!(f, "resume"),
+            DomainEvent::GuestPoweroff => write!(f, "guest-poweroff"),
+            DomainEvent::GuestReboot => write!(f, "guest-reboot"),
+            DomainEvent::GuestCrash => write!(f, "guest-crash"),
+            DomainEvent::LockFailure => write!(f, "lock-failure"),
+            DomainEvent::SuspendToMem => write!(f, "suspend-to-mem"),
+            DomainEvent::SuspendToDisk => write!(f, "suspend-to-disk"),
+            DomainEvent::Wakeup => write!(f, "wakeup"),
+        }
+    }
+}
+
+/// 状态转换附带的副作用
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum SideEffect {
+    /// 生成核心转储
+    Coredump,
+    /// 原域以新名称保留，并以原名称重新启动
+    RenameRestart { preserved_as: String },
+    /// 释放域的全部资源
+    ResourcesReleased,
+    /// 保留域的资源用于分析
+    ResourcesPreserved,
+    /// 重新启动客户机
+    Restarted,
+    /// 客户机内存已写入磁盘（休眠）
+    HibernationImageWritten,
+    /// 事件被忽略
+    Ignored,
+}
+
+impl fmt::Display for SideEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SideEffect::Coredump => write!(f, "coredump"),
+            SideEffect::RenameRestart { preserved_as } => {
+                write!(f, "rename-restart (preserved as '{}')", preserved_as)
+            }
+            SideEffect::ResourcesReleased => write!(f, "resources released"),
+            SideEffect::ResourcesPreserved => write!(f, "resources preserved"),
+            SideEffect::Restarted => write!(f, "restarted"),
+            SideEffect::HibernationImageWritten => write!(f, "hibernation image written"),
+            SideEffect::Ignored => write!(f, "ignored"),
+        }
+    }
+}
+
+/// 一次状态转换记录
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Transition {
+    pub event: DomainEvent,
+    pub from: DomainState,
+    pub to: DomainState,
+    /// 实际应用的生命周期动作（仅 on_* 事件）
+    pub action: Option<LifecycleAction>,
+    pub effects: Vec<SideEffect>,
+}
+
+impl fmt::Display for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.event, self.from, self.to)?;
+        if let Some(action) = self.action {
+            write!(f, " [{}]", action)?;
+        }
+        if !self.effects.is_empty() {
+            let effects: Vec<String> = self.effects.iter().map(|e| e.to_string()).collect();
+            write!(f, " ({})", effects.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// 确定性的域生命周期模拟器
+///
+/// 根据域的 on_* 动作与 `<pm>` 配置处理事件，输出状态转换及副作用，
+/// 用于在不启动虚拟机的情况下测试高可用策略。
+#[derive(Debug, Clone)]
+pub struct DomainStateMachine {
+    name: String,
+    state: DomainState,
+    config: LifecycleConfig,
+    power_management: PowerManagement,
+    rename_counter: u32,
+    history: Vec<Transition>,
+}
+
+impl DomainStateMachine {
+    /// 创建新的状态机，初始状态为 shutoff
+    pub fn new(name: &str, config: LifecycleConfig, power_management: PowerManagement) -> Self {
+        Self {
+            name: name.to_string(),
+            state: DomainState::Shutoff,
+            config,
+            power_management,
+            rename_counter: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// 从域配置创建状态机
+    pub fn from_domain(domain: &Domain) -> Self {
+        Self::new(
+            &domain.name,
+            domain.lifecycle_config(),
+            domain.power_management.clone().unwrap_or_default(),
+        )
+    }
+
+    /// 设置初始状态
+    pub fn with_state(mut self, state: DomainState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// 当前状态
+    pub fn state(&self) -> DomainState {
+        self.state
+    }
+
+    /// 已发生的状态转换
+    pub fn history(&self) -> &[Transition] {
+        &self.history
+    }
+
+    /// 未配置时 hypervisor 的默认动作
+    pub fn default_action(event: LifecycleEvent) -> LifecycleAction {
+        match event {
+            LifecycleEvent::OnPoweroff => LifecycleAction::Destroy,
+            LifecycleEvent::OnReboot => LifecycleAction::Restart,
+            LifecycleEvent::OnCrash => LifecycleAction::Destroy,
+            LifecycleEvent::OnLockfailure => LifecycleAction::Poweroff,
+        }
+    }
+
+    /// 处理单个事件
+    pub fn apply(&mut self, event: DomainEvent) -> Result<Transition, String> {
+        let transition = match event.lifecycle_event() {
+            Some(lifecycle_event) => self.apply_lifecycle(event, lifecycle_event)?,
+            None => self.apply_control(event)?,
+        };
+
+        self.state = transition.to;
+        self.history.push(transition.clone());
+        Ok(transition)
+    }
+
+    /// 依次处理一组事件，遇到非法事件时停止
+    pub fn run(&mut self, events: &[DomainEvent]) -> Result<Vec<Transition>, String> {
+        events.iter().map(|event| self.apply(*event)).collect()
+    }
+
+    fn apply_control(&mut self, event: DomainEvent) -> Result<Transition, String> {
+        let from = self.state;
+        let (to, effects) = match (event, from) {
+            (DomainEvent::Start, DomainState::Shutoff) => (DomainState::Running, Vec::new()),
+            (DomainEvent::Destroy, DomainState::Shutoff) => {
+                return Err(format!("Domain '{}' is not running", self.name));
+            }
+            (DomainEvent::Destroy, _) => {
+                (DomainState::Shutoff, vec![SideEffect::ResourcesReleased])
+            }
+            (DomainEvent::Resume, DomainState::Paused) => (DomainState::Running, Vec::new()),
+            (DomainEvent::SuspendToMem, DomainState::Running) => {
+                self.check_sleep_state(SleepState::SuspendToMem)?;
+                (DomainState::PmSuspended, Vec::new())
+            }
+            (DomainEvent::SuspendToDisk, DomainState::Running) => {
+                self.check_sleep_state(SleepState::SuspendToDisk)?;
+                (
+                    DomainState::Shutoff,
+                    vec![SideEffect::HibernationImageWritten],
+                )
+            }
+            (DomainEvent::Wakeup, DomainState::PmSuspended) => (DomainState::Running, Vec::new()),
+            _ => {
+                return Err(format!(
+                    "Event '{}' is not valid in state '{}'",
+                    event, from
+                ));
+            }
+        };
+
+        Ok(Transition {
+            event,
+            from,
+            to,
+            action: None,
+            effects,
+        })
+    }
+
+    fn apply_lifecycle(
+        &mut self,
+        event: DomainEvent,
+        lifecycle_event: LifecycleEvent,
+    ) -> Result<Transition, String> {
+        let from = self.state;
+        // 锁失效也可能发生在暂停状态；其余事件只能由运行中的客户机触发
+        let valid_state = match lifecycle_event {
+            LifecycleEvent::OnLockfailure => {
+                matches!(from, DomainState::Running | DomainState::Paused)
+            }
+            _ => from == DomainState::Running,
+        };
+        if !valid_state {
+            return Err(format!(
+                "Event '{}' is not valid in state '{}'",
+                event, from
+            ));
+        }
+
+        let action = self
+            .config
+            .get_action(lifecycle_event)
+            .unwrap_or_else(|| Self::default_action(lifecycle_event));
+
+        if !lifecycle_event.is_action_valid(action) {
+            return Err(format!(
+                "Action '{}' is not allowed for {}",
+                action, lifecycle_event
+            ));
+        }
+
+        // 保留资源时，关机/重启进入 shutdown，崩溃进入 crashed
+        let preserved_state = if lifecycle_event == LifecycleEvent::OnCrash {
+            DomainState::Crashed
+        } else {
+            DomainState::Shutdown
+        };
+
+        let (to, effects) = match action {
+            LifecycleAction::Destroy | LifecycleAction::Poweroff => {
+                (DomainState::Shutoff, vec![SideEffect::ResourcesReleased])
+            }
+            LifecycleAction::Restart => (DomainState::Running, vec![SideEffect::Restarted]),
+            LifecycleAction::Preserve => (preserved_state, vec![SideEffect::ResourcesPreserved]),
+            LifecycleAction::RenameRestart => {
+                self.rename_counter += 1;
+                let preserved_as = format!("{}-{}", self.name, self.rename_counter);
+                (
+                    DomainState::Running,
+                    vec![
+                        SideEffect::RenameRestart { preserved_as },
+                        SideEffect::Restarted,
+                    ],
+                )
+            }
+            LifecycleAction::CoredumpDestroy => (
+                DomainState::Shutoff,
+                vec![SideEffect::Coredump, SideEffect::ResourcesReleased],
+            ),
+            LifecycleAction::CoredumpRestart => (
+                DomainState::Running,
+                vec![SideEffect::Coredump, SideEffect::Restarted],
+            ),
+            LifecycleAction::Pause => (DomainState::Paused, Vec::new()),
+            LifecycleAction::Ignore => (from, vec![SideEffect::Ignored]),
+        };
+
+        Ok(Transition {
+            event,
+            from,
+            to,
+            action: Some(action),
+            effects,
+        })
+    }
+
+    fn check_sleep_state(&self, state: SleepState) -> Result<(), String> {
+        if self.power_management.is_state_enabled(state) {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} ({}) is not enabled in <pm> for domain '{}'",
+                state,
+                state.acpi_state(),
+                self.name
+            ))
+        }
+    }
+}
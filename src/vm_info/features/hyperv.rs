@@ -1,16 +1,19 @@
-use crate::vm_info::features::FeatureState;
+use super::FeatureState;
+use crate::vm_info::cpu::CpuConfig;
 use serde::{Deserialize, Serialize};
 
 /// Hyper-V 模式
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum HypervMode {
     Custom,
     Default,
+    /// 启用 hypervisor 支持的全部 enlightenment
+    Passthrough,
 }
 
 /// Hyper-V 特性配置
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct HypervFeatures {
     #[serde(rename = "@mode", skip_serializing_if = "Option::is_none")]
     pub mode: Option<HypervMode>,
@@ -81,40 +84,40 @@ pub struct HypervFeatures {
 }
 
 /// Spinlocks 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct SpinlocksConfig {
     #[serde(rename = "@state")]
-    pub state: FeatureState,
+    pub state: String,
 
     #[serde(rename = "@retries", skip_serializing_if = "Option::is_none")]
     pub retries: Option<u32>,
 }
 
 /// Synthetic Timer 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct StimerConfig {
     #[serde(rename = "@state")]
-    pub state: FeatureState,
+    pub state: String,
 
     #[serde(rename = "direct", skip_serializing_if = "Option::is_none")]
     pub direct: Option<FeatureState>,
 }
 
 /// Vendor ID 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct VendorIdConfig {
     #[serde(rename = "@state")]
-    pub state: FeatureState,
+    pub state: String,
 
     #[serde(rename = "@value", skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
 }
 
 /// TLB Flush 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct TlbFlushConfig {
     #[serde(rename = "@state")]
-    pub state: FeatureState,
+    pub state: String,
 
     #[serde(rename = "direct", skip_serializing_if = "Option::is_none")]
     pub direct: Option<FeatureState>,
@@ -122,3 +125,175 @@ pub struct TlbFlushConfig {
     #[serde(rename = "extended", skip_serializing_if = "Option::is_none")]
     pub extended: Option<FeatureState>,
 }
+
+fn is_on(state: Option<&FeatureState>) -> bool {
+    state.is_some_and(FeatureState::is_on)
+}
+
+impl HypervFeatures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Windows 客户机推荐的 enlightenment 组合
+    pub fn windows_recommended() -> Self {
+        Self {
+            mode: Some(HypervMode::Custom),
+            relaxed: Some(FeatureState::on()),
+            vapic: Some(FeatureState::on()),
+            spinlocks: Some(SpinlocksConfig {
+                state: "on".to_string(),
+                retries: Some(8191),
+            }),
+            vpindex: Some(FeatureState::on()),
+            runtime: Some(FeatureState::on()),
+            synic: Some(FeatureState::on()),
+            stimer: Some(StimerConfig {
+                state: "on".to_string(),
+                direct: Some(FeatureState::on()),
+            }),
+            reset: Some(FeatureState::on()),
+            frequencies: Some(FeatureState::on()),
+            tlbflush: Some(TlbFlushConfig {
+                state: "on".to_string(),
+                direct: Some(FeatureState::on()),
+                extended: Some(FeatureState::on()),
+            }),
+            ipi: Some(FeatureState::on()),
+            ..Self::default()
+        }
+    }
+
+    /// passthrough 模式：由 hypervisor 决定全部 enlightenment
+    pub fn passthrough() -> Self {
+        Self {
+            mode: Some(HypervMode::Passthrough),
+            ..Self::default()
+        }
+    }
+
+    /// 设置 vendor_id（最多 12 个字符）
+    pub fn with_vendor_id(mut self, value: &str) -> Self {
+        self.vendor_id = Some(VendorIdConfig {
+            state: "on".to_string(),
+            value: Some(value.to_string()),
+        });
+        self
+    }
+
+    /// 启用 enlightened VMCS（需要嵌套 vmx）
+    pub fn with_evmcs(mut self) -> Self {
+        self.evmcs = Some(FeatureState::on());
+        self
+    }
+
+    pub fn is_passthrough(&self) -> bool {
+        self.mode == Some(HypervMode::Passthrough)
+    }
+
+    pub fn synic_enabled(&self) -> bool {
+        is_on(self.synic.as_ref())
+    }
+
+    pub fn stimer_enabled(&self) -> bool {
+        self.stimer.as_ref().is_some_and(|s| s.state == "on")
+    }
+
+    /// 验证 enlightenment 之间的依赖关系
+    ///
+    /// `cpu` 用于检查 evmcs 所需的嵌套 vmx。
+    pub fn validate(&self, cpu: Option<&CpuConfig>) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        // passthrough 模式下由 hypervisor 决定，不检查依赖
+        if self.is_passthrough() {
+            return Ok(());
+        }
+
+        let vpindex = is_on(self.vpindex.as_ref());
+        let synic = self.synic_enabled();
+        let stimer = self.stimer_enabled();
+
+        if synic && !vpindex {
+            errors.push("Hyper-V 'synic' requires 'vpindex'".to_string());
+        }
+
+        if stimer {
+            if !vpindex {
+                errors.push("Hyper-V 'stimer' requires 'vpindex'".to_string());
+            }
+            if !synic {
+                errors.push("Hyper-V 'stimer' requires 'synic'".to_string());
+            }
+        }
+
+        if let Some(stimer_config) = &self.stimer
+            && is_on(stimer_config.direct.as_ref())
+            && !stimer
+        {
+            errors.push("Hyper-V 'stimer direct' requires 'stimer'".to_string());
+        }
+
+        if is_on(self.evmcs.as_ref()) {
+            if !is_on(self.vapic.as_ref()) {
+                errors.push("Hyper-V 'evmcs' requires 'vapic'".to_string());
+            }
+            if !cpu.is_some_and(|c| c.provides_feature("vmx")) {
+                errors.push(
+                    "Hyper-V 'evmcs' requires nested virtualization (CPU feature 'vmx')"
+                        .to_string(),
+                );
+            }
+        }
+
+        if let Some(tlbflush) = &self.tlbflush {
+            let enabled = tlbflush.state == "on";
+            let direct = is_on(tlbflush.direct.as_ref());
+            if enabled && !vpindex {
+                errors.push("Hyper-V 'tlbflush' requires 'vpindex'".to_string());
+            }
+            if direct && !enabled {
+                errors.push("Hyper-V 'tlbflush direct' requires 'tlbflush'".to_string());
+            }
+            if is_on(tlbflush.extended.as_ref()) && !direct {
+                errors.push("Hyper-V 'tlbflush extended' requires 'tlbflush direct'".to_string());
+            }
+        }
+
+        if is_on(self.ipi.as_ref()) && !vpindex {
+            errors.push("Hyper-V 'ipi' requires 'vpindex'".to_string());
+        }
+
+        if let Some(spinlocks) = &self.spinlocks
+            && spinlocks.state == "on"
+        {
+            match spinlocks.retries {
+                Some(retries) if retries < 4095 => errors.push(format!(
+                    "Hyper-V spinlocks retries must be at least 4095, got {}",
+                    retries
+                )),
+                None => errors.push("Hyper-V spinlocks requires 'retries'".to_string()),
+                _ => {}
+            }
+        }
+
+        if let Some(vendor_id) = &self.vendor_id
+            && vendor_id.state == "on"
+        {
+            match &vendor_id.value {
+                Some(value) if value.is_empty() || value.len() > 12 => errors.push(format!(
+                    "Hyper-V vendor_id must be 1-12 characters, got '{}'",
+                    value
+                )),
+                None => errors.push("Hyper-V vendor_id requires 'value'".to_string()),
+                _ => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -2,7 +2,7 @@ use super::FeatureState;
 use serde::{Deserialize, Serialize};
 
 // KVM 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct KvmFeatures {
     #[serde(rename = "hidden", skip_serializing_if = "Option::is_none")]
     pub hidden: Option<FeatureState>,
@@ -19,11 +19,12 @@ pub struct KvmFeatures {
     #[serde(rename = "dirty-ring", skip_serializing_if = "Option::is_none")]
     pub dirty_ring: Option<DirtyRingConfig>,
 }
+
 /// Dirty Ring 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct DirtyRingConfig {
     #[serde(rename = "@state")]
-    pub state: FeatureState,
+    pub state: String,
 
     #[serde(rename = "@size", skip_serializing_if = "Option::is_none")]
     pub size: Option<u32>,
@@ -1,304 +1,401 @@
+mod basic;
+mod hyperv;
+mod kvm;
+mod xen;
+
 use serde::{Deserialize, Serialize};
 
+pub use basic::{
+    AiaConfig, AiaValue, GicConfig, HptConfig, HptResizing, IbsConfig, IbsValue, IoApicConfig,
+    IoApicDriver, MsrsConfig, MsrsUnknown, SmmConfig, SpeculationConfig, SpeculationValue,
+    TcgConfig,
+};
+pub use hyperv::{
+    HypervFeatures, HypervMode, SpinlocksConfig, StimerConfig, TlbFlushConfig, VendorIdConfig,
+};
+pub use kvm::{DirtyRingConfig, KvmFeatures};
+pub use xen::{PassthroughConfig, XenFeatures, XenPassthroughMode};
+
 // 空元素标记
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct EmptyElement;
 
 // 基础功能状态
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct FeatureState {
     #[serde(rename = "@state")]
     pub state: String,
 }
 
+impl FeatureState {
+    pub fn on() -> Self {
+        Self {
+            state: "on".to_string(),
+        }
+    }
+
+    pub fn off() -> Self {
+        Self {
+            state: "off".to_string(),
+        }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.state == "on"
+    }
+}
+
 // 启用属性
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct EnabledAttribute {
     #[serde(rename = "@enabled")]
     pub enabled: String,
 }
 
-// 值属性
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct ValueAttribute {
-    #[serde(rename = "@value")]
-    pub value: String,
-}
-
 // 页面大小配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct PageSizeConfig {
-    #[serde(rename = "@unit")]
-    pub unit: String,
+    #[serde(rename = "@unit", skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
 
     #[serde(rename = "$value")]
     pub value: u32,
 }
 
-// Hyper-V 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct HypervConfig {
-    #[serde(rename = "@mode")]
-    pub mode: String,
-
-    #[serde(rename = "relaxed")]
-    pub relaxed: FeatureState,
-
-    #[serde(rename = "vapic")]
-    pub vapic: FeatureState,
-
-    #[serde(rename = "spinlocks")]
-    pub spinlocks: Spinlocks,
-
-    #[serde(rename = "vpindex")]
-    pub vpindex: FeatureState,
-
-    #[serde(rename = "runtime")]
-    pub runtime: FeatureState,
-
-    #[serde(rename = "synic")]
-    pub synic: FeatureState,
-
-    #[serde(rename = "stimer")]
-    pub stimer: Stimer,
-
-    #[serde(rename = "reset")]
-    pub reset: FeatureState,
-
-    #[serde(rename = "vendor_id")]
-    pub vendor_id: VendorId,
-
-    #[serde(rename = "frequencies")]
-    pub frequencies: FeatureState,
-
-    #[serde(rename = "reenlightenment")]
-    pub reenlightenment: FeatureState,
-
-    #[serde(rename = "tlbflush")]
-    pub tlbflush: TlbFlush,
-
-    #[serde(rename = "ipi")]
-    pub ipi: FeatureState,
-
-    #[serde(rename = "evmcs")]
-    pub evmcs: FeatureState,
-
-    #[serde(rename = "emsr_bitmap")]
-    pub emsr_bitmap: FeatureState,
-
-    #[serde(rename = "xmm_input")]
-    pub xmm_input: FeatureState,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Spinlocks {
-    #[serde(rename = "@state")]
-    pub state: String,
-
-    #[serde(rename = "@retries")]
-    pub retries: u32,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Stimer {
-    #[serde(rename = "@state")]
-    pub state: String,
-
-    #[serde(rename = "direct")]
-    pub direct: FeatureState,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct VendorId {
-    #[serde(rename = "@state")]
-    pub state: String,
-
-    #[serde(rename = "@value")]
-    pub value: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TlbFlush {
-    #[serde(rename = "@state")]
-    pub state: String,
-
-    #[serde(rename = "direct")]
-    pub direct: FeatureState,
-
-    #[serde(rename = "extended")]
-    pub extended: FeatureState,
-}
-
-// KVM 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct KvmConfig {
-    #[serde(rename = "hidden")]
-    pub hidden: FeatureState,
-
-    #[serde(rename = "hint-dedicated")]
-    pub hint_dedicated: FeatureState,
-
-    #[serde(rename = "poll-control")]
-    pub poll_control: FeatureState,
-
-    #[serde(rename = "pv-ipi")]
-    pub pv_ipi: FeatureState,
-
-    #[serde(rename = "dirty-ring")]
-    pub dirty_ring: DirtyRing,
+/// 特性适用的体系结构族
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ArchFamily {
+    X86,
+    Arm,
+    Ppc,
+    RiscV,
+    S390,
+    LoongArch,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DirtyRing {
-    #[serde(rename = "@state")]
-    pub state: String,
-
-    #[serde(rename = "@size")]
-    pub size: u32,
-}
-
-// Xen 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct XenConfig {
-    #[serde(rename = "e820_host")]
-    pub e820_host: FeatureState,
-
-    #[serde(rename = "passthrough")]
-    pub passthrough: Passthrough,
+impl ArchFamily {
+    fn from_arch(arch: &str) -> Option<Self> {
+        match arch {
+            "i386" | "i686" | "x86_64" => Some(ArchFamily::X86),
+            "aarch64" | "armv6l" | "armv7l" => Some(ArchFamily::Arm),
+            "ppc" | "ppc64" | "ppc64le" => Some(ArchFamily::Ppc),
+            "riscv32" | "riscv64" => Some(ArchFamily::RiscV),
+            "s390" | "s390x" => Some(ArchFamily::S390),
+            "loongarch64" => Some(ArchFamily::LoongArch),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Passthrough {
-    #[serde(rename = "@state")]
-    pub state: String,
-
-    #[serde(rename = "@mode")]
-    pub mode: String,
-}
-
-// GIC 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct GicConfig {
-    #[serde(rename = "@version")]
-    pub version: String,
-}
-
-// IOAPIC 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IoApicConfig {
-    #[serde(rename = "@driver")]
-    pub driver: String,
-}
-
-// HPT 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct HptConfig {
-    #[serde(rename = "@resizing")]
-    pub resizing: String,
-
-    #[serde(rename = "maxpagesize")]
-    pub maxpagesize: PageSizeConfig,
-}
-
-// SMM 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct SmmConfig {
-    #[serde(rename = "@state")]
-    pub state: String,
-
-    #[serde(rename = "tseg")]
-    pub tseg: PageSizeConfig,
-}
-
-// MSRS 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct MsrsConfig {
-    #[serde(rename = "@unknown")]
-    pub unknown: String,
-}
-
-// TCG 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TcgConfig {
-    #[serde(rename = "tb-cache")]
-    pub tb_cache: PageSizeConfig,
-}
+use ArchFamily::{Arm, LoongArch, Ppc, RiscV, X86};
+
+/// 特性约束：(元素名, 允许的体系结构族, 允许的域类型)，空列表表示不限制
+const FEATURE_RULES: &[(&str, &[ArchFamily], &[&str])] = &[
+    ("pae", &[X86], &[]),
+    ("acpi", &[X86, Arm, RiscV, LoongArch], &[]),
+    ("apic", &[X86], &[]),
+    ("hap", &[X86], &[]),
+    ("privnet", &[], &["lxc"]),
+    ("hyperv", &[X86], &["kvm", "qemu"]),
+    ("kvm", &[], &["kvm"]),
+    ("xen", &[X86], &["xen"]),
+    ("pvspinlock", &[X86], &["kvm", "qemu"]),
+    ("pmu", &[], &["kvm", "qemu"]),
+    ("gic", &[Arm], &[]),
+    ("ioapic", &[X86], &["kvm", "qemu"]),
+    ("hpt", &[Ppc], &["kvm", "qemu"]),
+    ("vmcoreinfo", &[], &["kvm", "qemu"]),
+    ("smm", &[X86], &["kvm", "qemu"]),
+    ("htm", &[Ppc], &["kvm", "qemu"]),
+    ("ccf-assist", &[Ppc], &["kvm", "qemu"]),
+    ("msrs", &[X86], &["kvm", "qemu"]),
+    ("cfpc", &[Ppc], &["kvm", "qemu"]),
+    ("sbbc", &[Ppc], &["kvm", "qemu"]),
+    ("ibs", &[Ppc], &["kvm", "qemu"]),
+    ("tcg", &[], &["qemu"]),
+    ("async-teardown", &[], &["kvm", "qemu"]),
+    ("ras", &[Arm], &["kvm", "qemu"]),
+    ("ps2", &[X86], &["kvm", "qemu"]),
+    ("aia", &[RiscV], &["kvm", "qemu"]),
+];
 
 // 顶层 Features 结构
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct Features {
-    #[serde(rename = "pae")]
-    pub pae: EmptyElement,
+    #[serde(rename = "pae", skip_serializing_if = "Option::is_none")]
+    pub pae: Option<EmptyElement>,
+
+    #[serde(rename = "acpi", skip_serializing_if = "Option::is_none")]
+    pub acpi: Option<EmptyElement>,
 
-    #[serde(rename = "acpi")]
-    pub acpi: EmptyElement,
+    #[serde(rename = "apic", skip_serializing_if = "Option::is_none")]
+    pub apic: Option<EmptyElement>,
 
-    #[serde(rename = "apic")]
-    pub apic: EmptyElement,
+    #[serde(rename = "hap", skip_serializing_if = "Option::is_none")]
+    pub hap: Option<EmptyElement>,
 
-    #[serde(rename = "hap")]
-    pub hap: EmptyElement,
+    #[serde(rename = "privnet", skip_serializing_if = "Option::is_none")]
+    pub privnet: Option<EmptyElement>,
 
-    #[serde(rename = "privnet")]
-    pub privnet: EmptyElement,
+    #[serde(rename = "hyperv", skip_serializing_if = "Option::is_none")]
+    pub hyperv: Option<HypervFeatures>,
 
-    #[serde(rename = "hyperv")]
-    pub hyperv: HypervConfig,
+    #[serde(rename = "kvm", skip_serializing_if = "Option::is_none")]
+    pub kvm: Option<KvmFeatures>,
 
-    #[serde(rename = "kvm")]
-    pub kvm: KvmConfig,
+    #[serde(rename = "xen", skip_serializing_if = "Option::is_none")]
+    pub xen: Option<XenFeatures>,
 
-    #[serde(rename = "xen")]
-    pub xen: XenConfig,
+    #[serde(rename = "pvspinlock", skip_serializing_if = "Option::is_none")]
+    pub pvspinlock: Option<FeatureState>,
 
-    #[serde(rename = "pvspinlock")]
-    pub pvspinlock: FeatureState,
+    /// 虚拟 PMU，实时客户机通常关闭
+    #[serde(rename = "pmu", skip_serializing_if = "Option::is_none")]
+    pub pmu: Option<FeatureState>,
 
-    #[serde(rename = "gic")]
-    pub gic: GicConfig,
+    #[serde(rename = "gic", skip_serializing_if = "Option::is_none")]
+    pub gic: Option<GicConfig>,
 
-    #[serde(rename = "ioapic")]
-    pub ioapic: IoApicConfig,
+    #[serde(rename = "ioapic", skip_serializing_if = "Option::is_none")]
+    pub ioapic: Option<IoApicConfig>,
 
-    #[serde(rename = "hpt")]
-    pub hpt: HptConfig,
+    #[serde(rename = "hpt", skip_serializing_if = "Option::is_none")]
+    pub hpt: Option<HptConfig>,
 
-    #[serde(rename = "vmcoreinfo")]
-    pub vmcoreinfo: FeatureState,
+    #[serde(rename = "vmcoreinfo", skip_serializing_if = "Option::is_none")]
+    pub vmcoreinfo: Option<FeatureState>,
 
-    #[serde(rename = "smm")]
-    pub smm: SmmConfig,
+    #[serde(rename = "smm", skip_serializing_if = "Option::is_none")]
+    pub smm: Option<SmmConfig>,
 
-    #[serde(rename = "htm")]
-    pub htm: FeatureState,
+    #[serde(rename = "htm", skip_serializing_if = "Option::is_none")]
+    pub htm: Option<FeatureState>,
 
-    #[serde(rename = "ccf-assist")]
-    pub ccf_assist: FeatureState,
+    #[serde(rename = "ccf-assist", skip_serializing_if = "Option::is_none")]
+    pub ccf_assist: Option<FeatureState>,
 
-    #[serde(rename = "msrs")]
-    pub msrs: MsrsConfig,
+    #[serde(rename = "msrs", skip_serializing_if = "Option::is_none")]
+    pub msrs: Option<MsrsConfig>,
 
-    #[serde(rename = "cfpc")]
-    pub cfpc: ValueAttribute,
+    #[serde(rename = "cfpc", skip_serializing_if = "Option::is_none")]
+    pub cfpc: Option<SpeculationConfig>,
 
-    #[serde(rename = "sbbc")]
-    pub sbbc: ValueAttribute,
+    #[serde(rename = "sbbc", skip_serializing_if = "Option::is_none")]
+    pub sbbc: Option<SpeculationConfig>,
 
-    #[serde(rename = "ibs")]
-    pub ibs: ValueAttribute,
+    #[serde(rename = "ibs", skip_serializing_if = "Option::is_none")]
+    pub ibs: Option<IbsConfig>,
 
-    #[serde(rename = "tcg")]
-    pub tcg: TcgConfig,
+    #[serde(rename = "tcg", skip_serializing_if = "Option::is_none")]
+    pub tcg: Option<TcgConfig>,
 
-    #[serde(rename = "async-teardown")]
-    pub async_teardown: EnabledAttribute,
+    #[serde(rename = "async-teardown", skip_serializing_if = "Option::is_none")]
+    pub async_teardown: Option<EnabledAttribute>,
 
-    #[serde(rename = "ras")]
-    pub ras: FeatureState,
+    #[serde(rename = "ras", skip_serializing_if = "Option::is_none")]
+    pub ras: Option<FeatureState>,
 
-    #[serde(rename = "ps2")]
-    pub ps2: FeatureState,
+    #[serde(rename = "ps2", skip_serializing_if = "Option::is_none")]
+    pub ps2: Option<FeatureState>,
+
+    #[serde(rename = "aia", skip_serializing_if = "Option::is_none")]
+    pub aia: Option<AiaConfig>,
+}
 
-    #[serde(rename = "aia")]
-    pub aia: ValueAttribute,
+impl Features {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// x86 客户机的常用组合：acpi + apic
+    pub fn x86_default() -> Self {
+        Self {
+            acpi: Some(EmptyElement),
+            apic: Some(EmptyElement),
+            ..Self::default()
+        }
+    }
+
+    /// 已配置的特性元素名
+    pub fn present(&self) -> Vec<&'static str> {
+        let flags = [
+            ("pae", self.pae.is_some()),
+            ("acpi", self.acpi.is_some()),
+            ("apic", self.apic.is_some()),
+            ("hap", self.hap.is_some()),
+            ("privnet", self.privnet.is_some()),
+            ("hyperv", self.hyperv.is_some()),
+            ("kvm", self.kvm.is_some()),
+            ("xen", self.xen.is_some()),
+            ("pvspinlock", self.pvspinlock.is_some()),
+            ("pmu", self.pmu.is_some()),
+            ("gic", self.gic.is_some()),
+            ("ioapic", self.ioapic.is_some()),
+            ("hpt", self.hpt.is_some()),
+            ("vmcoreinfo", self.vmcoreinfo.is_some()),
+            ("smm", self.smm.is_some()),
+            ("htm", self.htm.is_some()),
+            ("ccf-assist", self.ccf_assist.is_some()),
+            ("msrs", self.msrs.is_some()),
+            ("cfpc", self.cfpc.is_some()),
+            ("sbbc", self.sbbc.is_some()),
+            ("ibs", self.ibs.is_some()),
+            ("tcg", self.tcg.is_some()),
+            ("async-teardown", self.async_teardown.is_some()),
+            ("ras", self.ras.is_some()),
+            ("ps2", self.ps2.is_some()),
+            ("aia", self.aia.is_some()),
+        ];
+
+        flags
+            .iter()
+            .filter(|(_, present)| *present)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// 验证特性是否适用于 `os.type@arch` 与 `domain@type`
+    ///
+    /// arch 未知或未指定时跳过体系结构检查。
+    pub fn validate(&self, arch: Option<&str>, domain_type: &str) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let family = arch.and_then(ArchFamily::from_arch);
+        let domain_type = domain_type.to_lowercase();
+
+        for name in self.present() {
+            let Some((_, archs, types)) = FEATURE_RULES.iter().find(|(n, _, _)| *n == name) else {
+                continue;
+            };
+
+            if let (Some(arch), Some(family)) = (arch, family)
+                && !archs.is_empty()
+                && !archs.contains(&family)
+            {
+                errors.push(format!(
+                    "Feature <{}> is not supported on architecture '{}'",
+                    name, arch
+                ));
+            }
+
+            if !types.is_empty() && !types.contains(&domain_type.as_str()) {
+                errors.push(format!(
+                    "Feature <{}> is not supported by domain type '{}' (supported: {})",
+                    name,
+                    domain_type,
+                    types.join(", ")
+                ));
+            }
+        }
+
+        self.validate_values(&mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 检查 Windows 客户机 GPU 直通所需的特性组合
+    ///
+    /// NVIDIA 驱动检测到 hypervisor 时会拒绝工作，需要隐藏 KVM 签名，
+    /// 并在启用 Hyper-V enlightenment 时替换默认的 vendor_id。
+    pub fn validate_gpu_passthrough(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        let hidden = self
+            .kvm
+            .as_ref()
+            .and_then(|kvm| kvm.hidden.as_ref())
+            .is_some_and(FeatureState::is_on);
+        if !hidden {
+            errors.push("GPU passthrough requires <kvm><hidden state='on'/></kvm>".to_string());
+        }
+
+        if let Some(hyperv) = &self.hyperv
+            && !hyperv.is_passthrough()
+        {
+            let vendor_set = hyperv
+                .vendor_id
+                .as_ref()
+                .is_some_and(|v| v.state == "on" && v.value.is_some());
+            if !vendor_set {
+                errors.push(
+                    "GPU passthrough with Hyper-V enlightenments requires <vendor_id state='on' value='...'/>"
+                        .to_string(),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 验证各特性的取值
+    fn validate_values(&self, errors: &mut Vec<String>) {
+        let states = [
+            ("pvspinlock", self.pvspinlock.as_ref()),
+            ("pmu", self.pmu.as_ref()),
+            ("vmcoreinfo", self.vmcoreinfo.as_ref()),
+            ("htm", self.htm.as_ref()),
+            ("ccf-assist", self.ccf_assist.as_ref()),
+            ("ras", self.ras.as_ref()),
+            ("ps2", self.ps2.as_ref()),
+        ];
+        for (name, state) in states {
+            if let Some(state) = state
+                && state.state != "on"
+                && state.state != "off"
+            {
+                errors.push(format!(
+                    "Feature <{}> state must be 'on' or 'off', got '{}'",
+                    name, state.state
+                ));
+            }
+        }
+
+        if let Some(smm) = &self.smm
+            && smm.state != "on"
+            && smm.state != "off"
+        {
+            errors.push(format!(
+                "Feature <smm> state must be 'on' or 'off', got '{}'",
+                smm.state
+            ));
+        }
+
+        if let Some(gic) = &self.gic
+            && let Some(version) = &gic.version
+            && !matches!(version.as_str(), "2" | "3" | "host")
+        {
+            errors.push(format!(
+                "GIC version must be '2', '3' or 'host', got '{}'",
+                version
+            ));
+        }
+
+        if let Some(teardown) = &self.async_teardown
+            && teardown.enabled != "yes"
+            && teardown.enabled != "no"
+        {
+            errors.push(format!(
+                "Feature <async-teardown> enabled must be 'yes' or 'no', got '{}'",
+                teardown.enabled
+            ));
+        }
+
+        if let Some(kvm) = &self.kvm
+            && let Some(dirty_ring) = &kvm.dirty_ring
+            && let Some(size) = dirty_ring.size
+            && (!size.is_power_of_two() || !(1024..=65536).contains(&size))
+        {
+            errors.push(format!(
+                "KVM dirty-ring size must be a power of 2 between 1024 and 65536, got {}",
+                size
+            ));
+        }
+    }
 }
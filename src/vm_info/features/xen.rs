@@ -3,7 +3,6 @@ use serde::{Deserialize, Serialize};
 
 /// Xen passthrough 模式
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Hash)]
-#[serde(rename_all = "lowercase")]
 pub enum XenPassthroughMode {
     #[serde(rename = "share_pt")]
     SharePt,
@@ -12,7 +11,7 @@ pub enum XenPassthroughMode {
 }
 
 // Xen 配置
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct XenFeatures {
     #[serde(rename = "e820_host", skip_serializing_if = "Option::is_none")]
     pub e820_host: Option<FeatureState>,
@@ -21,7 +20,7 @@ pub struct XenFeatures {
     pub passthrough: Option<PassthroughConfig>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct PassthroughConfig {
     #[serde(rename = "@state")]
     pub state: String,
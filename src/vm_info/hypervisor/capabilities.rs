@@ -0,0 +1,415 @@
+use super::Hypervisor;
+use crate::vm_info::{
+    Domain, LifecycleAction, LifecycleConfig, LifecycleEvent, OsKind, PowerManagement,
+    ResourceConfig,
+};
+
+const ALL_EVENTS: [LifecycleEvent; 4] = [
+    LifecycleEvent::OnPoweroff,
+    LifecycleEvent::OnReboot,
+    LifecycleEvent::OnCrash,
+    LifecycleEvent::OnLockfailure,
+];
+
+/// 某种域类型支持的配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct HypervisorCapabilities {
+    pub hypervisor: Hypervisor,
+    /// 每个可配置的生命周期事件及其可用的动作，未列出的事件不受支持
+    pub lifecycle_actions: Vec<(LifecycleEvent, Vec<LifecycleAction>)>,
+    /// 不能同时配置的两个事件动作
+    pub lifecycle_conflicts: Vec<[(LifecycleEvent, LifecycleAction); 2]>,
+    /// 支持的 `<os><type>`
+    pub os_kinds: Vec<OsKind>,
+    /// 支持 `<pm>`
+    pub power_management: bool,
+    /// 支持 `<resource><partition>`
+    pub resource_partition: bool,
+    /// 支持 `<resource><fibrechannel>`
+    pub fibre_channel: bool,
+}
+
+impl HypervisorCapabilities {
+    /// 只支持关机、重启事件的 destroy/restart，以及 hvm 客户机
+    pub fn new(hypervisor: Hypervisor) -> Self {
+        let basic = vec![LifecycleAction::Destroy, LifecycleAction::Restart];
+        Self {
+            hypervisor,
+            lifecycle_actions: vec![
+                (LifecycleEvent::OnPoweroff, basic.clone()),
+                (LifecycleEvent::OnReboot, basic),
+            ],
+            lifecycle_conflicts: Vec::new(),
+            os_kinds: vec![OsKind::Hvm],
+            power_management: false,
+            resource_partition: false,
+            fibre_channel: false,
+        }
+    }
+
+    /// 设置支持的事件，每个事件只保留 `actions` 中该事件本身允许的动作
+    pub fn with_lifecycle_events(
+        mut self,
+        events: &[LifecycleEvent],
+        actions: &[LifecycleAction],
+    ) -> Self {
+        self.lifecycle_actions = events
+            .iter()
+            .map(|event| {
+                let allowed = actions
+                    .iter()
+                    .copied()
+                    .filter(|action| event.is_action_valid(*action))
+                    .collect();
+                (*event, allowed)
+            })
+            .collect();
+        self
+    }
+
+    /// 设置或替换单个事件可用的动作
+    pub fn with_lifecycle_actions(
+        mut self,
+        event: LifecycleEvent,
+        actions: &[LifecycleAction],
+    ) -> Self {
+        match self.lifecycle_actions.iter_mut().find(|(e, _)| *e == event) {
+            Some((_, allowed)) => *allowed = actions.to_vec(),
+            None => self.lifecycle_actions.push((event, actions.to_vec())),
+        }
+        self
+    }
+
+    pub fn with_lifecycle_conflict(
+        mut self,
+        first: (LifecycleEvent, LifecycleAction),
+        second: (LifecycleEvent, LifecycleAction),
+    ) -> Self {
+        self.lifecycle_conflicts.push([first, second]);
+        self
+    }
+
+    pub fn with_os_kinds(mut self, kinds: &[OsKind]) -> Self {
+        self.os_kinds = kinds.to_vec();
+        self
+    }
+
+    pub fn with_power_management(mut self, supported: bool) -> Self {
+        self.power_management = supported;
+        self
+    }
+
+    pub fn with_resource_partition(mut self, supported: bool) -> Self {
+        self.resource_partition = supported;
+        self
+    }
+
+    pub fn with_fibre_channel(mut self, supported: bool) -> Self {
+        self.fibre_channel = supported;
+        self
+    }
+
+    /// 事件可用的动作，不支持的事件返回空列表
+    pub fn actions_for(&self, event: LifecycleEvent) -> &[LifecycleAction] {
+        self.lifecycle_actions
+            .iter()
+            .find(|(e, _)| *e == event)
+            .map_or(&[], |(_, actions)| actions.as_slice())
+    }
+
+    pub fn supports_event(&self, event: LifecycleEvent) -> bool {
+        self.lifecycle_actions.iter().any(|(e, _)| *e == event)
+    }
+
+    pub fn supports_action(&self, event: LifecycleEvent, action: LifecycleAction) -> bool {
+        self.actions_for(event).contains(&action)
+    }
+}
+
+/// 各域类型的能力表，所有与驱动相关的验证都从这里查询
+#[derive(Debug, Clone)]
+pub struct CapabilityRegistry {
+    capabilities: Vec<HypervisorCapabilities>,
+}
+
+impl Default for CapabilityRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CapabilityRegistry {
+    /// 内置的能力表
+    pub fn new() -> Self {
+        use LifecycleAction::*;
+
+        let all_actions = [
+            Destroy,
+            Restart,
+            Preserve,
+            RenameRestart,
+            CoredumpDestroy,
+            CoredumpRestart,
+            Poweroff,
+            Pause,
+            Ignore,
+        ];
+        let qemu_actions = [
+            Destroy,
+            Restart,
+            Preserve,
+            CoredumpDestroy,
+            CoredumpRestart,
+            Poweroff,
+            Pause,
+            Ignore,
+        ];
+        // QEMU 无法在关机、重启时保留客户机，这两个事件只能 destroy 或 restart
+        let qemu = |hypervisor| {
+            HypervisorCapabilities::new(hypervisor)
+                .with_lifecycle_events(&ALL_EVENTS, &qemu_actions)
+                .with_lifecycle_actions(LifecycleEvent::OnPoweroff, &[Destroy, Restart])
+                .with_lifecycle_actions(LifecycleEvent::OnReboot, &[Destroy, Restart])
+                .with_lifecycle_conflict(
+                    (LifecycleEvent::OnPoweroff, Restart),
+                    (LifecycleEvent::OnReboot, Destroy),
+                )
+                .with_power_management(true)
+                .with_resource_partition(true)
+                .with_fibre_channel(true)
+        };
+        let crash = [
+            LifecycleEvent::OnPoweroff,
+            LifecycleEvent::OnReboot,
+            LifecycleEvent::OnCrash,
+        ];
+
+        Self {
+            capabilities: vec![
+                qemu(Hypervisor::Kvm),
+                qemu(Hypervisor::Qemu),
+                qemu(Hypervisor::Hvf),
+                HypervisorCapabilities::new(Hypervisor::Xen)
+                    .with_lifecycle_events(&ALL_EVENTS, &all_actions)
+                    .with_os_kinds(&[OsKind::Hvm, OsKind::Linux, OsKind::Xen, OsKind::Xenpvh]),
+                HypervisorCapabilities::new(Hypervisor::Lxc)
+                    .with_lifecycle_events(&crash, &[Destroy, Restart])
+                    .with_os_kinds(&[OsKind::Exe])
+                    .with_resource_partition(true),
+                HypervisorCapabilities::new(Hypervisor::Openvz).with_os_kinds(&[OsKind::Exe]),
+                HypervisorCapabilities::new(Hypervisor::Vz)
+                    .with_os_kinds(&[OsKind::Hvm, OsKind::Exe]),
+                HypervisorCapabilities::new(Hypervisor::Uml).with_os_kinds(&[OsKind::Uml]),
+                HypervisorCapabilities::new(Hypervisor::Vmware),
+                HypervisorCapabilities::new(Hypervisor::Hyperv),
+                HypervisorCapabilities::new(Hypervisor::Vbox),
+                HypervisorCapabilities::new(Hypervisor::Bhyve)
+                    .with_lifecycle_events(&crash, &[Destroy, Restart]),
+                HypervisorCapabilities::new(Hypervisor::Ch)
+                    .with_lifecycle_events(&crash, &[Destroy, Restart]),
+                // test 驱动接受所有配置
+                HypervisorCapabilities::new(Hypervisor::Test)
+                    .with_lifecycle_events(&ALL_EVENTS, &all_actions)
+                    .with_os_kinds(&[
+                        OsKind::Hvm,
+                        OsKind::Linux,
+                        OsKind::Xen,
+                        OsKind::Xenpvh,
+                        OsKind::Exe,
+                        OsKind::Uml,
+                    ])
+                    .with_power_management(true)
+                    .with_resource_partition(true)
+                    .with_fibre_channel(true),
+            ],
+        }
+    }
+
+    /// 空的能力表
+    pub fn empty() -> Self {
+        Self {
+            capabilities: Vec::new(),
+        }
+    }
+
+    /// 注册或替换某种域类型的能力
+    pub fn with_capabilities(mut self, capabilities: HypervisorCapabilities) -> Self {
+        self.capabilities
+            .retain(|c| c.hypervisor != capabilities.hypervisor);
+        self.capabilities.push(capabilities);
+        self
+    }
+
+    pub fn get(&self, hypervisor: &Hypervisor) -> Option<&HypervisorCapabilities> {
+        self.capabilities
+            .iter()
+            .find(|c| &c.hypervisor == hypervisor)
+    }
+
+    /// 已注册的域类型
+    pub fn hypervisors(&self) -> Vec<&Hypervisor> {
+        self.capabilities.iter().map(|c| &c.hypervisor).collect()
+    }
+
+    /// 支持某项能力的域类型，用于错误信息
+    fn supporting(&self, pred: impl Fn(&HypervisorCapabilities) -> bool) -> String {
+        self.capabilities
+            .iter()
+            .filter(|c| pred(c))
+            .map(|c| c.hypervisor.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn lookup(&self, hypervisor: &Hypervisor) -> Result<&HypervisorCapabilities, Vec<String>> {
+        self.get(hypervisor)
+            .ok_or_else(|| vec![format!("Unknown domain type '{}'", hypervisor)])
+    }
+
+    /// 检查生命周期事件与动作是否受支持
+    pub fn validate_lifecycle(
+        &self,
+        hypervisor: &Hypervisor,
+        config: &LifecycleConfig,
+    ) -> Result<(), Vec<String>> {
+        let caps = self.lookup(hypervisor)?;
+        let mut errors = Vec::new();
+        for event in ALL_EVENTS {
+            let Some(action) = config.get_action(event) else {
+                continue;
+            };
+            if !caps.supports_event(event) {
+                errors.push(format!(
+                    "Domain type '{}' does not support {} events",
+                    hypervisor, event
+                ));
+            } else if !caps.supports_action(event, action) {
+                let supported: Vec<String> = caps
+                    .actions_for(event)
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect();
+                errors.push(format!(
+                    "Domain type '{}' does not support action '{}' for {}. Supported actions: {}",
+                    hypervisor,
+                    action,
+                    event,
+                    supported.join(", ")
+                ));
+            }
+        }
+        for [(first_event, first_action), (second_event, second_action)] in
+            &caps.lifecycle_conflicts
+        {
+            if config.get_action(*first_event) == Some(*first_action)
+                && config.get_action(*second_event) == Some(*second_action)
+            {
+                errors.push(format!(
+                    "Domain type '{}' does not support {}={} together with {}={}",
+                    hypervisor, first_event, first_action, second_event, second_action
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 检查是否支持 `<pm>`
+    pub fn validate_power_management(
+        &self,
+        hypervisor: &Hypervisor,
+        pm: &PowerManagement,
+    ) -> Result<(), Vec<String>> {
+        let caps = self.lookup(hypervisor)?;
+        if !caps.power_management && pm != &PowerManagement::default() {
+            return Err(vec![format!(
+                "Power management configuration is not supported by domain type '{}'. Supported domain types: {}",
+                hypervisor,
+                self.supporting(|c| c.power_management)
+            )]);
+        }
+        Ok(())
+    }
+
+    /// 检查是否支持 `<resource>` 中的分区与 Fibre Channel 配置
+    pub fn validate_resource(
+        &self,
+        hypervisor: &Hypervisor,
+        resource: &ResourceConfig,
+    ) -> Result<(), Vec<String>> {
+        let caps = self.lookup(hypervisor)?;
+        let mut errors = Vec::new();
+        if resource.partition.is_some() && !caps.resource_partition {
+            errors.push(format!(
+                "Domain type '{}' does not support resource partitioning. Supported domain types: {}",
+                hypervisor,
+                self.supporting(|c| c.resource_partition)
+            ));
+        }
+        if resource.fibre_channel.is_some() && !caps.fibre_channel {
+            errors.push(format!(
+                "Domain type '{}' does not support <fibrechannel>. Supported domain types: {}",
+                hypervisor,
+                self.supporting(|c| c.fibre_channel)
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 检查 `<os><type>` 是否受支持
+    pub fn validate_os_kind(
+        &self,
+        hypervisor: &Hypervisor,
+        kind: OsKind,
+    ) -> Result<(), Vec<String>> {
+        let caps = self.lookup(hypervisor)?;
+        if !caps.os_kinds.contains(&kind) {
+            return Err(vec![format!(
+                "Domain type '{}' does not support <type>{}</type>. Supported domain types: {}",
+                hypervisor,
+                kind,
+                self.supporting(|c| c.os_kinds.contains(&kind))
+            )]);
+        }
+        Ok(())
+    }
+
+    /// 对域执行全部与域类型相关的检查
+    pub fn validate(&self, domain: &Domain) -> Result<(), Vec<String>> {
+        let hypervisor = &domain.domain_type;
+        self.lookup(hypervisor)?;
+
+        let mut errors = Vec::new();
+        if let Err(mut e) = self.validate_os_kind(hypervisor, domain.os.os_type.value) {
+            errors.append(&mut e);
+        }
+        if let Err(mut e) = self.validate_lifecycle(hypervisor, &domain.lifecycle_config()) {
+            errors.append(&mut e);
+        }
+        if let Some(pm) = &domain.power_management
+            && let Err(mut e) = self.validate_power_management(hypervisor, pm)
+        {
+            errors.append(&mut e);
+        }
+        if let Some(resource) = &domain.resource
+            && let Err(mut e) = self.validate_resource(hypervisor, resource)
+        {
+            errors.append(&mut e);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,108 @@
+mod capabilities;
+
+pub use capabilities::{CapabilityRegistry, HypervisorCapabilities};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// `<domain type>`，即运行域的虚拟化技术，未知类型保留原样
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum Hypervisor {
+    /// QEMU + KVM 硬件加速
+    Kvm,
+    /// QEMU TCG 纯模拟
+    Qemu,
+    /// QEMU + macOS Hypervisor.framework
+    Hvf,
+    Xen,
+    Lxc,
+    Openvz,
+    /// Virtuozzo
+    Vz,
+    Uml,
+    Vmware,
+    Hyperv,
+    Vbox,
+    Bhyve,
+    /// Cloud Hypervisor
+    Ch,
+    /// libvirt 的 test 驱动
+    Test,
+    Other(String),
+}
+
+impl Hypervisor {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Hypervisor::Kvm => "kvm",
+            Hypervisor::Qemu => "qemu",
+            Hypervisor::Hvf => "hvf",
+            Hypervisor::Xen => "xen",
+            Hypervisor::Lxc => "lxc",
+            Hypervisor::Openvz => "openvz",
+            Hypervisor::Vz => "vz",
+            Hypervisor::Uml => "uml",
+            Hypervisor::Vmware => "vmware",
+            Hypervisor::Hyperv => "hyperv",
+            Hypervisor::Vbox => "vbox",
+            Hypervisor::Bhyve => "bhyve",
+            Hypervisor::Ch => "ch",
+            Hypervisor::Test => "test",
+            Hypervisor::Other(name) => name,
+        }
+    }
+
+    /// 处理该类型的 libvirt 驱动名称
+    pub fn driver(&self) -> &str {
+        match self {
+            Hypervisor::Kvm | Hypervisor::Qemu | Hypervisor::Hvf => "qemu",
+            Hypervisor::Xen => "libxl",
+            other => other.as_str(),
+        }
+    }
+
+    /// 由 QEMU 驱动运行
+    pub fn is_qemu(&self) -> bool {
+        matches!(self, Hypervisor::Kvm | Hypervisor::Qemu | Hypervisor::Hvf)
+    }
+}
+
+impl From<&str> for Hypervisor {
+    fn from(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "kvm" => Hypervisor::Kvm,
+            "qemu" => Hypervisor::Qemu,
+            "hvf" => Hypervisor::Hvf,
+            "xen" => Hypervisor::Xen,
+            "lxc" => Hypervisor::Lxc,
+            "openvz" => Hypervisor::Openvz,
+            "vz" => Hypervisor::Vz,
+            "uml" => Hypervisor::Uml,
+            "vmware" => Hypervisor::Vmware,
+            "hyperv" => Hypervisor::Hyperv,
+            "vbox" => Hypervisor::Vbox,
+            "bhyve" => Hypervisor::Bhyve,
+            "ch" => Hypervisor::Ch,
+            "test" => Hypervisor::Test,
+            _ => Hypervisor::Other(name.to_string()),
+        }
+    }
+}
+
+impl From<String> for Hypervisor {
+    fn from(name: String) -> Self {
+        Hypervisor::from(name.as_str())
+    }
+}
+
+impl From<Hypervisor> for String {
+    fn from(hypervisor: Hypervisor) -> Self {
+        hypervisor.as_str().to_string()
+    }
+}
+
+impl fmt::Display for Hypervisor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
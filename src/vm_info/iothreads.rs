@@ -0,0 +1,291 @@
+use super::Domain;
+use super::devices::DriverIothreads;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+// iothreadids
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct IothreadIds {
+    #[serde(rename = "iothread", default)]
+    pub iothreads: Vec<IothreadId>,
+}
+
+/// `<iothreadids><iothread>`，显式声明的 IOThread
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IothreadId {
+    #[serde(rename = "@id")]
+    pub id: u32,
+    #[serde(rename = "@thread_pool_min", skip_serializing_if = "Option::is_none")]
+    pub thread_pool_min: Option<u32>,
+    #[serde(rename = "@thread_pool_max", skip_serializing_if = "Option::is_none")]
+    pub thread_pool_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll: Option<IothreadPoll>,
+}
+
+impl IothreadId {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            thread_pool_min: None,
+            thread_pool_max: None,
+            poll: None,
+        }
+    }
+
+    pub fn with_thread_pool(mut self, min: u32, max: u32) -> Self {
+        self.thread_pool_min = Some(min);
+        self.thread_pool_max = Some(max);
+        self
+    }
+
+    pub fn with_poll(mut self, poll: IothreadPoll) -> Self {
+        self.poll = Some(poll);
+        self
+    }
+}
+
+/// `<poll>`，IOThread 事件循环的轮询参数（单位 ns）
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct IothreadPoll {
+    #[serde(rename = "@max", skip_serializing_if = "Option::is_none")]
+    pub max: Option<u64>,
+    #[serde(rename = "@grow", skip_serializing_if = "Option::is_none")]
+    pub grow: Option<u64>,
+    #[serde(rename = "@shrink", skip_serializing_if = "Option::is_none")]
+    pub shrink: Option<u64>,
+}
+
+/// `<defaultiothread>`，QEMU 主事件循环的线程池
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct DefaultIothread {
+    #[serde(rename = "@thread_pool_min", skip_serializing_if = "Option::is_none")]
+    pub thread_pool_min: Option<u32>,
+    #[serde(rename = "@thread_pool_max", skip_serializing_if = "Option::is_none")]
+    pub thread_pool_max: Option<u32>,
+}
+
+fn validate_thread_pool(what: &str, min: Option<u32>, max: Option<u32>, errors: &mut Vec<String>) {
+    if let Some(max) = max
+        && max == 0
+    {
+        errors.push(format!("{} thread_pool_max must be greater than 0", what));
+    }
+    if let (Some(min), Some(max)) = (min, max)
+        && min > max
+    {
+        errors.push(format!(
+            "{} thread_pool_min ({}) must not exceed thread_pool_max ({})",
+            what, min, max
+        ));
+    }
+}
+
+impl Domain {
+    /// 域中全部 IOThread 的 id
+    ///
+    /// 与 libvirt 一致：`<iothreads>` 多于 `<iothreadids>` 时，
+    /// 其余 IOThread 依次使用最小的未占用 id。
+    pub fn iothread_ids(&self) -> BTreeSet<u32> {
+        let mut ids: BTreeSet<u32> = self
+            .iothread_ids
+            .iter()
+            .flat_map(|ids| ids.iothreads.iter().map(|t| t.id))
+            .collect();
+        let count = self.iothreads.unwrap_or(0) as usize;
+        let mut next = 1;
+        while ids.len() < count {
+            if ids.insert(next) {
+                continue;
+            }
+            next += 1;
+        }
+        ids
+    }
+
+    /// 验证 `<iothreads>`/`<iothreadids>`/`<defaultiothread>` 以及引用 IOThread 的配置
+    ///
+    /// iothreadsched 的 id 由 [`Domain::validate_scheduling`] 检查。
+    pub fn validate_iothreads(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let ids = self.iothread_ids();
+
+        let mut seen = BTreeSet::new();
+        for iothread in self.iothread_ids.iter().flat_map(|ids| &ids.iothreads) {
+            if iothread.id == 0 {
+                errors.push("iothread id must be greater than 0".to_string());
+            }
+            if !seen.insert(iothread.id) {
+                errors.push(format!("Duplicate iothread id {}", iothread.id));
+            }
+            validate_thread_pool(
+                &format!("iothread {}", iothread.id),
+                iothread.thread_pool_min,
+                iothread.thread_pool_max,
+                &mut errors,
+            );
+            if let Some(poll) = &iothread.poll
+                && poll.max == Some(0)
+                && (poll.grow.is_some() || poll.shrink.is_some())
+            {
+                errors.push(format!(
+                    "iothread {} poll grow/shrink have no effect when polling is disabled (max='0')",
+                    iothread.id
+                ));
+            }
+        }
+        // <iothreadids> 多于 <iothreads> 时 libvirt 会把数量调高，不视为错误
+
+        if let Some(default) = &self.default_iothread {
+            validate_thread_pool(
+                "defaultiothread",
+                default.thread_pool_min,
+                default.thread_pool_max,
+                &mut errors,
+            );
+        }
+
+        if let Some(cputune) = &self.cputune {
+            for pin in &cputune.iothread_pins {
+                match pin.iothread.parse::<u32>() {
+                    Ok(id) if !ids.contains(&id) => {
+                        errors.push(format!("iothreadpin references undefined iothread {}", id))
+                    }
+                    Ok(_) => {}
+                    Err(_) => {
+                        errors.push(format!("Invalid iothreadpin iothread '{}'", pin.iothread))
+                    }
+                }
+            }
+        }
+
+        for disk in self.devices.disk.iter().flatten() {
+            let what = format!("Disk '{}'", disk.target.dev);
+            let driver = &disk.driver;
+            if (driver.iothread.is_some() || driver.iothreads.is_some())
+                && disk.target.bus != "virtio"
+            {
+                errors.push(format!(
+                    "{} uses an iothread but only virtio disks support iothreads",
+                    what
+                ));
+            }
+            validate_iothread_refs(
+                &what,
+                driver.iothread,
+                driver.iothreads.as_ref(),
+                driver.queues,
+                &ids,
+                &mut errors,
+            );
+        }
+
+        for controller in self.devices.controllers.iter().flatten() {
+            let Some(driver) = &controller.driver else {
+                continue;
+            };
+            let what = format!(
+                "Controller {}[{}]",
+                controller.controller_type,
+                controller.index.unwrap_or(0)
+            );
+            if (driver.iothread.is_some() || driver.iothreads.is_some())
+                && !(controller.controller_type == "scsi"
+                    && controller.model.as_deref() == Some("virtio-scsi"))
+            {
+                errors.push(format!(
+                    "{} uses an iothread but only virtio-scsi controllers support iothreads",
+                    what
+                ));
+            }
+            validate_iothread_refs(
+                &what,
+                driver.iothread,
+                driver.iothreads.as_ref(),
+                driver.queues,
+                &ids,
+                &mut errors,
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+// 检查 driver 上的 iothread 属性与 <iothreads> 队列映射
+fn validate_iothread_refs(
+    what: &str,
+    iothread: Option<u32>,
+    mapping: Option<&DriverIothreads>,
+    queues: Option<u32>,
+    ids: &BTreeSet<u32>,
+    errors: &mut Vec<String>,
+) {
+    if let Some(id) = iothread
+        && !ids.contains(&id)
+    {
+        errors.push(format!("{} references undefined iothread {}", what, id));
+    }
+
+    let Some(mapping) = mapping else {
+        return;
+    };
+    if iothread.is_some() {
+        errors.push(format!(
+            "{} cannot use both the iothread attribute and <iothreads> mapping",
+            what
+        ));
+    }
+
+    let mut threads = BTreeSet::new();
+    let mut queue_owner: BTreeMap<u32, u32> = BTreeMap::new();
+    for thread in &mapping.iothreads {
+        if !ids.contains(&thread.id) {
+            errors.push(format!("{} maps undefined iothread {}", what, thread.id));
+        }
+        if !threads.insert(thread.id) {
+            errors.push(format!(
+                "{} maps iothread {} more than once",
+                what, thread.id
+            ));
+        }
+        for queue in &thread.queues {
+            if let Some(queues) = queues
+                && queue.id >= queues
+            {
+                errors.push(format!(
+                    "{} maps queue {} but only {} queue(s) are configured",
+                    what, queue.id, queues
+                ));
+            }
+            if let Some(owner) = queue_owner.insert(queue.id, thread.id) {
+                errors.push(format!(
+                    "{} maps queue {} to both iothread {} and {}",
+                    what, queue.id, owner, thread.id
+                ));
+            }
+        }
+    }
+
+    // 只要有一个 iothread 指定了队列，所有队列都必须被映射
+    if !queue_owner.is_empty() {
+        if mapping.iothreads.iter().any(|t| t.queues.is_empty()) {
+            errors.push(format!(
+                "{} must map queues for every iothread once any queue mapping is given",
+                what
+            ));
+        }
+        if let Some(queues) = queues {
+            let unmapped: Vec<u32> = (0..queues)
+                .filter(|q| !queue_owner.contains_key(q))
+                .collect();
+            if !unmapped.is_empty() {
+                errors.push(format!("{} leaves queue(s) {:?} unmapped", what, unmapped));
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::{LintRule, Severity};
+use crate::vm_info::{CapabilityRegistry, Domain};
+
+/// 内置规则
+///
+/// - `V0xx`：包装各 `validate_*()`，默认为 error
+/// - `P0xx`：推荐做法，默认为 warning
+pub(super) fn rules() -> Vec<LintRule> {
+    vec![
+        LintRule::from_validator(
+            "V001",
+            Severity::Error,
+            "Domain type capabilities (os type, lifecycle, pm, resource)",
+            |d| CapabilityRegistry::default().validate(d),
+        ),
+        LintRule::from_validator(
+            "V002",
+            Severity::Error,
+            "Lifecycle event and action pairs",
+            |d| d.lifecycle_config().validate(),
+        ),
+        LintRule::from_validator("V003", Severity::Error, "<os> and bootloader", |d| {
+            d.validate_os()
+        }),
+        LintRule::from_validator("V004", Severity::Error, "Firmware", |d| {
+            d.validate_firmware()
+        }),
+        LintRule::from_validator("V005", Severity::Error, "Boot order", |d| {
+            d.validate_boot_order()
+        }),
+        LintRule::from_validator("V006", Severity::Error, "<sysinfo>", |d| {
+            d.validate_sysinfo()
+        }),
+        LintRule::from_validator("V007", Severity::Error, "<features>", |d| {
+            d.validate_features()
+        }),
+        LintRule::from_validator("V008", Severity::Error, "<clock>", |d| d.validate_clock()),
+        LintRule::from_validator("V009", Severity::Error, "<memtune>", |d| {
+            d.validate_memtune()
+        }),
+        LintRule::from_validator("V010", Severity::Error, "cachetune/memorytune", |d| {
+            d.validate_resctrl(None)
+        }),
+        LintRule::from_validator(
+            "V011",
+            Severity::Error,
+            "vCPU and IOThread scheduling",
+            |d| d.validate_scheduling(),
+        ),
+        LintRule::from_validator("V012", Severity::Error, "IOThreads", |d| {
+            d.validate_iothreads()
+        }),
+        LintRule::from_validator("V013", Severity::Error, "<resource>", |d| {
+            d.resource.as_ref().map_or(Ok(()), |r| r.validate())
+        }),
+        LintRule::from_validator("V014", Severity::Error, "<metadata>", |d| {
+            d.metadata.as_ref().map_or(Ok(()), |m| m.validate())
+        }),
+        LintRule::from_validator(
+            "V015",
+            Severity::Error,
+            "Security labels and launch security",
+            |d| d.validate_security(),
+        ),
+        LintRule::from_validator(
+            "V016",
+            Severity::Error,
+            "TPM, RNG, watchdog, panic and memballoon devices",
+            |d| d.validate_devices(),
+        ),
+        LintRule::from_validator("V017", Severity::Error, "<cpu> model and features", |d| {
+            d.validate_cpu()
+        }),
+        LintRule::new(
+            "P001",
+            Severity::Warning,
+            "Disks should not use cache='unsafe'",
+            disk_cache_unsafe,
+        ),
+        LintRule::new(
+            "P002",
+            Severity::Warning,
+            "Host devices should come with a memtune hard_limit",
+            hostdev_without_hard_limit,
+        ),
+        LintRule::new(
+            "P003",
+            Severity::Warning,
+            "Confidential guests should lock their memory",
+            |d| d.security_recommendations(),
+        ),
+        LintRule::new(
+            "P004",
+            Severity::Warning,
+            "Unusual lifecycle action combinations",
+            |d| d.lifecycle_config().warnings(),
+        ),
+    ]
+}
+
+fn disk_cache_unsafe(domain: &Domain) -> Vec<String> {
+    domain
+        .devices
+        .disk
+        .iter()
+        .flatten()
+        .filter(|disk| disk.driver.cache.as_deref() == Some("unsafe"))
+        .map(|disk| {
+            format!(
+                "Disk '{}' uses cache='unsafe' and may lose data on host crash",
+                disk.target.dev
+            )
+        })
+        .collect()
+}
+
+// 直通设备需要锁定全部客户机内存，没有 hard_limit 时锁定量由 libvirt 估算
+fn hostdev_without_hard_limit(domain: &Domain) -> Vec<String> {
+    let has_hostdev = domain
+        .devices
+        .hostdevs
+        .as_ref()
+        .is_some_and(|h| !h.is_empty());
+    let has_hard_limit = domain
+        .memtune
+        .as_ref()
+        .is_some_and(|m| m.hard_limit.is_some());
+    if has_hostdev && !has_hard_limit {
+        vec!["Domain has <hostdev> devices but no <memtune><hard_limit>".to_string()]
+    } else {
+        Vec::new()
+    }
+}
@@ -0,0 +1,251 @@
+mod builtin;
+mod policy;
+
+use super::Domain;
+pub use policy::{LintPolicy, PathCondition, PathRule, RuleOverride};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// 诊断的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 一条检查结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub code: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+/// 一次检查的全部结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    /// 不低于 `severity` 的诊断
+    pub fn at_least(&self, severity: Severity) -> Vec<&Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity >= severity)
+            .collect()
+    }
+
+    pub fn by_code(&self, code: &str) -> Vec<&Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.code == code).collect()
+    }
+
+    /// 只保留错误，转换为 `validate()` 的返回形式
+    pub fn into_result(self) -> Result<(), Vec<String>> {
+        let errors: Vec<String> = self
+            .diagnostics
+            .into_iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| format!("[{}] {}", d.code, d.message))
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+type RuleCheck = Box<dyn Fn(&Domain) -> Vec<String> + Send + Sync>;
+
+/// 一条检查规则，返回的每条消息生成一个诊断
+pub struct LintRule {
+    /// 稳定的规则代码，用于启用/禁用和覆盖严重程度
+    pub code: String,
+    pub description: String,
+    pub default_severity: Severity,
+    check: RuleCheck,
+}
+
+impl LintRule {
+    pub fn new<F>(code: &str, severity: Severity, description: &str, check: F) -> Self
+    where
+        F: Fn(&Domain) -> Vec<String> + Send + Sync + 'static,
+    {
+        Self {
+            code: code.to_string(),
+            description: description.to_string(),
+            default_severity: severity,
+            check: Box::new(check),
+        }
+    }
+
+    /// 包装现有的 `validate()` 函数
+    pub fn from_validator<F>(code: &str, severity: Severity, description: &str, validate: F) -> Self
+    where
+        F: Fn(&Domain) -> Result<(), Vec<String>> + Send + Sync + 'static,
+    {
+        Self::new(code, severity, description, move |domain| {
+            validate(domain).err().unwrap_or_default()
+        })
+    }
+}
+
+impl fmt::Debug for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LintRule")
+            .field("code", &self.code)
+            .field("description", &self.description)
+            .field("default_severity", &self.default_severity)
+            .finish()
+    }
+}
+
+/// 对 `Domain` 执行一组规则
+///
+/// 内置规则见 [`Linter::new`]，可以按代码禁用规则或覆盖严重程度，
+/// 也可以加入闭包规则或从 TOML/JSON 读取的 [`LintPolicy`]。
+#[derive(Debug)]
+pub struct Linter {
+    rules: Vec<LintRule>,
+    overrides: BTreeMap<String, RuleOverride>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linter {
+    /// 带内置规则的检查器
+    pub fn new() -> Self {
+        Self {
+            rules: builtin::rules(),
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// 没有任何规则的检查器
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// 加入规则，同代码的旧规则会被替换
+    pub fn with_rule(mut self, rule: LintRule) -> Self {
+        self.rules.retain(|r| r.code != rule.code);
+        self.rules.push(rule);
+        self
+    }
+
+    /// 加入闭包规则
+    pub fn with_check<F>(self, code: &str, severity: Severity, description: &str, check: F) -> Self
+    where
+        F: Fn(&Domain) -> Vec<String> + Send + Sync + 'static,
+    {
+        self.with_rule(LintRule::new(code, severity, description, check))
+    }
+
+    pub fn disable(mut self, code: &str) -> Self {
+        self.overrides.entry(code.to_string()).or_default().enabled = Some(false);
+        self
+    }
+
+    pub fn enable(mut self, code: &str) -> Self {
+        self.overrides.entry(code.to_string()).or_default().enabled = Some(true);
+        self
+    }
+
+    pub fn with_severity(mut self, code: &str, severity: Severity) -> Self {
+        self.overrides.entry(code.to_string()).or_default().severity = Some(severity);
+        self
+    }
+
+    /// 应用策略：覆盖设置与声明式规则
+    pub fn with_policy(mut self, policy: LintPolicy) -> Self {
+        for (code, rule_override) in policy.overrides {
+            let entry = self.overrides.entry(code).or_default();
+            if rule_override.enabled.is_some() {
+                entry.enabled = rule_override.enabled;
+            }
+            if rule_override.severity.is_some() {
+                entry.severity = rule_override.severity;
+            }
+        }
+        for rule in policy.rules {
+            self = self.with_rule(rule.into_lint_rule());
+        }
+        self
+    }
+
+    pub fn rules(&self) -> &[LintRule] {
+        &self.rules
+    }
+
+    /// 规则当前生效的严重程度，被禁用时为 None
+    pub fn effective_severity(&self, code: &str) -> Option<Severity> {
+        let rule = self.rules.iter().find(|r| r.code == code)?;
+        let rule_override = self.overrides.get(code);
+        if rule_override.and_then(|o| o.enabled) == Some(false) {
+            return None;
+        }
+        Some(
+            rule_override
+                .and_then(|o| o.severity)
+                .unwrap_or(rule.default_severity),
+        )
+    }
+
+    pub fn lint(&self, domain: &Domain) -> LintReport {
+        let mut report = LintReport::default();
+        for rule in &self.rules {
+            let Some(severity) = self.effective_severity(&rule.code) else {
+                continue;
+            };
+            for message in (rule.check)(domain) {
+                report.diagnostics.push(Diagnostic {
+                    code: rule.code.clone(),
+                    severity,
+                    message,
+                });
+            }
+        }
+        report
+    }
+}
+
+impl Domain {
+    /// 使用内置规则检查域，见 [`Linter`]
+    pub fn lint(&self) -> LintReport {
+        Linter::new().lint(self)
+    }
+}
@@ -0,0 +1,290 @@
+use super::{LintRule, Severity};
+use crate::vm_info::Domain;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 对单条规则的启用状态与严重程度覆盖
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RuleOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+}
+
+/// 从 TOML/JSON 读取的检查策略
+///
+/// ```toml
+/// [overrides.P001]
+/// severity = "error"
+///
+/// [[rules]]
+/// code = "ORG001"
+/// severity = "error"
+/// message = "Production VMs must use on_crash=coredump-restart"
+/// when = [{ path = "title", equals = "production" }]
+/// require = [{ path = "on_crash", equals = "coredump-restart" }]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct LintPolicy {
+    #[serde(default)]
+    pub overrides: BTreeMap<String, RuleOverride>,
+    #[serde(default)]
+    pub rules: Vec<PathRule>,
+}
+
+impl LintPolicy {
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("Invalid lint policy: {}", e))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("Invalid lint policy: {}", e))
+    }
+
+    /// 按扩展名选择格式，`.json` 以外都按 TOML 解析
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&text)
+        } else {
+            Self::from_toml(&text)
+        }
+    }
+}
+
+/// 声明式规则：`when` 全部满足时，`require` 必须全部满足
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PathRule {
+    pub code: String,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    /// 违反时的消息，未设置时根据失败的条件生成
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub when: Vec<PathCondition>,
+    #[serde(default)]
+    pub require: Vec<PathCondition>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Warning
+}
+
+/// 对路径所选值的断言，多个断言需要同时成立
+///
+/// 路径按 `.` 分隔，对应 XML 序列化时的名称（属性带 `@`，文本为 `$text`），
+/// 遇到列表时对每个元素继续匹配，`*` 匹配所有子项。
+/// 例如 `devices.disk.driver.@cache`、`memtune.hard_limit`。
+///
+/// 路径选中元素时比较其文本（`$text` 或 `$value`）。带单位的值按写法比较数字，
+/// 不做单位换算，需要时用 `memtune.hard_limit.@unit` 另外限定单位。
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct PathCondition {
+    pub path: String,
+    /// 路径是否存在
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exists: Option<bool>,
+    /// 存在且所有值都等于
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<String>,
+    /// 没有任何值等于
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_equals: Option<String>,
+    /// 存在且所有值都在列表中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<String>>,
+    /// 没有任何值在列表中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_one_of: Option<Vec<String>>,
+}
+
+impl PathCondition {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn exists(mut self, exists: bool) -> Self {
+        self.exists = Some(exists);
+        self
+    }
+
+    pub fn equals(mut self, value: &str) -> Self {
+        self.equals = Some(value.to_string());
+        self
+    }
+
+    pub fn not_equals(mut self, value: &str) -> Self {
+        self.not_equals = Some(value.to_string());
+        self
+    }
+
+    fn matches(&self, root: &Value) -> bool {
+        let values = select(root, &self.path);
+        let texts: Vec<String> = values.iter().filter_map(|v| scalar(v)).collect();
+
+        if let Some(exists) = self.exists
+            && exists == values.is_empty()
+        {
+            return false;
+        }
+        if let Some(expected) = &self.equals
+            && (texts.is_empty() || texts.iter().any(|t| t != expected))
+        {
+            return false;
+        }
+        if let Some(unexpected) = &self.not_equals
+            && texts.iter().any(|t| t == unexpected)
+        {
+            return false;
+        }
+        if let Some(allowed) = &self.one_of
+            && (texts.is_empty() || texts.iter().any(|t| !allowed.contains(t)))
+        {
+            return false;
+        }
+        if let Some(denied) = &self.not_one_of
+            && texts.iter().any(|t| denied.contains(t))
+        {
+            return false;
+        }
+        true
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(exists) = self.exists {
+            parts.push(if exists { "exists" } else { "is absent" }.to_string());
+        }
+        if let Some(v) = &self.equals {
+            parts.push(format!("equals '{}'", v));
+        }
+        if let Some(v) = &self.not_equals {
+            parts.push(format!("is not '{}'", v));
+        }
+        if let Some(v) = &self.one_of {
+            parts.push(format!("is one of {:?}", v));
+        }
+        if let Some(v) = &self.not_one_of {
+            parts.push(format!("is none of {:?}", v));
+        }
+        format!("'{}' {}", self.path, parts.join(" and "))
+    }
+}
+
+impl PathRule {
+    pub fn new(code: &str, severity: Severity) -> Self {
+        Self {
+            code: code.to_string(),
+            severity,
+            message: None,
+            when: Vec::new(),
+            require: Vec::new(),
+        }
+    }
+
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    pub fn when(mut self, condition: PathCondition) -> Self {
+        self.when.push(condition);
+        self
+    }
+
+    pub fn require(mut self, condition: PathCondition) -> Self {
+        self.require.push(condition);
+        self
+    }
+
+    /// 检查域，返回违反时的消息
+    pub fn check(&self, domain: &Domain) -> Vec<String> {
+        let root = match serde_json::to_value(domain) {
+            Ok(root) => root,
+            Err(e) => return vec![format!("Failed to evaluate rule {}: {}", self.code, e)],
+        };
+        if !self.when.iter().all(|c| c.matches(&root)) {
+            return Vec::new();
+        }
+        self.require
+            .iter()
+            .filter(|c| !c.matches(&root))
+            .map(|c| match &self.message {
+                Some(message) => message.clone(),
+                None => format!("Expected {}", c.describe()),
+            })
+            .take(if self.message.is_some() {
+                1
+            } else {
+                usize::MAX
+            })
+            .collect()
+    }
+
+    pub(super) fn into_lint_rule(self) -> LintRule {
+        let code = self.code.clone();
+        let severity = self.severity;
+        let description = self
+            .message
+            .clone()
+            .unwrap_or_else(|| format!("Policy rule {}", code));
+        LintRule::new(&code, severity, &description, move |domain| {
+            self.check(domain)
+        })
+    }
+}
+
+// 按路径选出所有值
+fn select<'a>(root: &'a Value, path: &str) -> Vec<&'a Value> {
+    let mut current = vec![root];
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let mut next = Vec::new();
+        for value in current {
+            collect(value, segment, &mut next);
+        }
+        current = next;
+    }
+    current.into_iter().filter(|v| !v.is_null()).collect()
+}
+
+fn collect<'a>(value: &'a Value, segment: &str, out: &mut Vec<&'a Value>) {
+    match value {
+        Value::Array(items) if segment == "*" => out.extend(items),
+        Value::Array(items) => {
+            for item in items {
+                collect(item, segment, out);
+            }
+        }
+        Value::Object(map) if segment == "*" => out.extend(map.values()),
+        Value::Object(map) => {
+            if let Some(child) = map.get(segment) {
+                out.push(child);
+            }
+        }
+        _ => {}
+    }
+}
+
+// 标量转为字符串，带 `$text` 或 `$value` 的元素取其文本
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Object(map) => map
+            .get("$text")
+            .or_else(|| map.get("$value"))
+            .and_then(scalar),
+        _ => None,
+    }
+}
@@ -0,0 +1,129 @@
+use super::Domain;
+
+/// 运行中由 libvirt 创建的 tap/macvtap 设备名前缀
+const AUTO_TARGET_PREFIXES: [&str; 3] = ["vnet", "macvtap", "macvlan"];
+
+/// qemu 驱动启动时补充的默认资源分区
+const DEFAULT_PARTITION: &str = "/machine";
+
+impl Domain {
+    /// 是否为运行中的域导出的 XML
+    pub fn is_live(&self) -> bool {
+        self.id.is_some()
+    }
+
+    /// 当前配置中仅运行时存在的字段，如 `id`、`devices/disk[0]/alias`
+    ///
+    /// 活动 XML 中的设备地址大多由 libvirt 自动分配，作为定义模板时会与其他设备冲突，
+    /// 因此也列出，但只由 [`Domain::to_inactive`] 去掉。
+    pub fn runtime_fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        if self.id.is_some() {
+            fields.push("id".to_string());
+        }
+        for (device, alias, address) in self.devices.aliases_and_addresses() {
+            if alias.is_some_and(|a| !a.is_user_defined()) {
+                fields.push(format!("devices/{}/alias", device));
+            }
+            if address.is_some() && self.is_live() {
+                fields.push(format!("devices/{}/address", device));
+            }
+        }
+        for (i, iface) in self.devices.interfaces.iter().flatten().enumerate() {
+            if iface
+                .target
+                .as_ref()
+                .is_some_and(|t| is_auto_target(&t.dev))
+            {
+                fields.push(format!("devices/interface[{}]/target", i));
+            }
+            if let Some(source) = &iface.source {
+                if source.portid.is_some() {
+                    fields.push(format!("devices/interface[{}]/source/portid", i));
+                }
+                if iface.interface_type == "network" && source.bridge.is_some() {
+                    fields.push(format!("devices/interface[{}]/source/bridge", i));
+                }
+            }
+        }
+        for (i, seclabel) in self.seclabels.iter().flatten().enumerate() {
+            for field in seclabel.runtime_fields() {
+                fields.push(format!("seclabel[{}]/{}", i, field));
+            }
+        }
+        if self.has_default_partition() {
+            fields.push("resource/partition".to_string());
+        }
+        fields
+    }
+
+    /// 就地去掉运行时字段，见 [`Domain::runtime_fields`]
+    ///
+    /// 用户定义的别名（`ua-` 前缀）和设备地址会保留。
+    pub fn strip_runtime(&mut self) {
+        if self.has_default_partition()
+            && let Some(resource) = &mut self.resource
+        {
+            resource.partition = None;
+            if resource.fibre_channel.is_none() {
+                self.resource = None;
+            }
+        }
+        self.id = None;
+        for (_, alias, _) in self.devices.aliases_and_addresses_mut() {
+            if alias.as_ref().is_some_and(|a| !a.is_user_defined()) {
+                *alias = None;
+            }
+        }
+        for iface in self.devices.interfaces.iter_mut().flatten() {
+            if iface
+                .target
+                .as_ref()
+                .is_some_and(|t| is_auto_target(&t.dev))
+            {
+                iface.target = None;
+            }
+            // network 类型的 bridge 由 libvirt 按网络定义填写
+            let network = iface.interface_type == "network";
+            if let Some(source) = &mut iface.source {
+                source.portid = None;
+                if network {
+                    source.bridge = None;
+                }
+            }
+        }
+        for seclabel in self.seclabels.iter_mut().flatten() {
+            seclabel.strip_runtime();
+        }
+    }
+
+    /// 从活动 XML 得到可用于定义的非活动配置，不修改自身
+    ///
+    /// 除 [`Domain::strip_runtime`] 外，活动 XML 中的设备地址也会去掉，由 libvirt 重新分配。
+    pub fn to_inactive(&self) -> Result<Domain, String> {
+        let mut inactive = Domain::from_xml(&self.to_xml()?)?;
+        if self.is_live() {
+            for (_, _, address) in inactive.devices.aliases_and_addresses_mut() {
+                *address = None;
+            }
+        }
+        inactive.strip_runtime();
+        Ok(inactive)
+    }
+
+    fn has_default_partition(&self) -> bool {
+        self.is_live()
+            && self
+                .resource
+                .as_ref()
+                .and_then(|r| r.partition.as_ref())
+                .is_some_and(|p| p.get_path() == DEFAULT_PARTITION)
+    }
+}
+
+fn is_auto_target(dev: &str) -> bool {
+    AUTO_TARGET_PREFIXES.iter().any(|prefix| {
+        dev.strip_prefix(prefix)
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
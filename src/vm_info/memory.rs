@@ -1,3 +1,4 @@
+use crate::MemoryUnit;
 use serde::{Deserialize, Serialize};
 
 // 在引导时为客户机分配的最大内存。内存分配包括在启动时指定或稍后热插拔时指定的可能的附加内存设备。
@@ -9,6 +10,15 @@ pub struct Memory {
     #[serde(rename = "$text")]
     pub value: u64,
 }
+
+impl Memory {
+    /// 转换为 KiB，无法识别的单位按 KiB 处理
+    pub fn to_kib(&self) -> u64 {
+        let unit = self.unit.parse::<MemoryUnit>().unwrap_or_default();
+        unit.to_bytes(self.value) / 1024
+    }
+}
+
 // 运行时最大内存分配。 <memory>元素或NUMA单元大小配置指定的初始内存可以通过将内存热插拔到该元素指定的限制来增加。
 // unit属性的行为与<memory>相同。
 // slots属性指定可用于向来宾添加内存的插槽数量。边界是特定于管理程序的
@@ -23,6 +33,14 @@ pub struct MaxMemory {
     pub value: u64,
 }
 
+impl MaxMemory {
+    /// 转换为 KiB，无法识别的单位按 KiB 处理
+    pub fn to_kib(&self) -> u64 {
+        let unit = self.unit.parse::<MemoryUnit>().unwrap_or_default();
+        unit.to_bytes(self.value) / 1024
+    }
+}
+
 // 为来宾分配的实际内存。这个值可以小于最大分配值，以便动态地增加来宾内存。如果省略，则默认为与内存元素相同的值。unit属性的行为与内存相同。
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CurrentMemory {
@@ -31,3 +49,11 @@ pub struct CurrentMemory {
     #[serde(rename = "$text")]
     pub value: u64,
 }
+
+impl CurrentMemory {
+    /// 转换为 KiB，无法识别的单位按 KiB 处理
+    pub fn to_kib(&self) -> u64 {
+        let unit = self.unit.parse::<MemoryUnit>().unwrap_or_default();
+        unit.to_bytes(self.value) / 1024
+    }
+}
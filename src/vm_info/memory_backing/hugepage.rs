@@ -31,6 +31,11 @@ impl HugePages {
 }
 
 impl HugePage {
+    /// 页大小（KiB）
+    pub fn size_kib(&self) -> u64 {
+        self.unit.unwrap_or_default().to_bytes(self.size) / 1024
+    }
+
     pub fn new(size: u64, unit: Option<MemoryUnit>, nodeset: Option<String>) -> Self {
         Self {
             size,
@@ -2,10 +2,12 @@ use quick_xml::se::to_string;
 use serde::{Deserialize, Serialize};
 
 mod hugepage;
-use hugepage::{HugePage, HugePages};
+mod planner;
+pub use hugepage::{HugePage, HugePages};
+pub use planner::{HostHugepages, HugepageDemand, HugepagePlan, HugepagePool, HugepageShortfall};
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct EmptyElement;
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct MemoryBacking {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hugepages: Option<HugePages>,
@@ -77,15 +79,7 @@ pub enum MemoryAllocationMode {
 // Builder pattern for easy construction
 impl MemoryBacking {
     pub fn new() -> Self {
-        Self {
-            hugepages: None,
-            nosharepages: None,
-            locked: None,
-            source: None,
-            access: None,
-            allocation: None,
-            discard: None,
-        }
+        Self::default()
     }
 
     pub fn with_hugepages(mut self, hugepages: HugePages) -> Self {
@@ -0,0 +1,418 @@
+use super::hugepage::NumaNodeSet;
+use crate::vm_info::Domain;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// 宿主机某个节点上某种尺寸的大页池
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HugepagePool {
+    /// nr_hugepages
+    pub total: u64,
+    /// free_hugepages
+    pub free: u64,
+    /// surplus_hugepages
+    pub surplus: u64,
+}
+
+/// 宿主机大页池信息，按 NUMA 节点和页大小（KiB）组织
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HostHugepages {
+    /// /proc/meminfo 中的 Hugepagesize
+    pub default_size_kib: u64,
+    pub nodes: BTreeMap<u32, BTreeMap<u64, HugepagePool>>,
+}
+
+impl HostHugepages {
+    pub fn new(default_size_kib: u64) -> Self {
+        Self {
+            default_size_kib,
+            nodes: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_pool(mut self, node: u32, size_kib: u64, pool: HugepagePool) -> Self {
+        self.nodes.entry(node).or_default().insert(size_kib, pool);
+        self
+    }
+
+    /// 读取本机的 /proc/meminfo 与 /sys
+    pub fn from_host() -> Result<Self, String> {
+        Self::from_root("/")
+    }
+
+    /// 从指定根目录读取，便于使用测试夹具
+    ///
+    /// 优先读取 sys/devices/system/node/node*/hugepages，没有 NUMA 信息时
+    /// 将 sys/kernel/mm/hugepages 视为节点 0。
+    pub fn from_root<P: AsRef<Path>>(root: P) -> Result<Self, String> {
+        let root = root.as_ref();
+
+        let default_size_kib = fs::read_to_string(root.join("proc/meminfo"))
+            .ok()
+            .and_then(|meminfo| {
+                meminfo.lines().find_map(|line| {
+                    let value = line.strip_prefix("Hugepagesize:")?;
+                    value.split_whitespace().next()?.parse().ok()
+                })
+            })
+            .unwrap_or(2048);
+        let mut host = Self::new(default_size_kib);
+
+        let node_root = root.join("sys/devices/system/node");
+        if let Ok(entries) = fs::read_dir(&node_root) {
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let Some(node) = name
+                    .to_str()
+                    .and_then(|n| n.strip_prefix("node"))
+                    .and_then(|n| n.parse::<u32>().ok())
+                else {
+                    continue;
+                };
+                let pools = read_pools(&entry.path().join("hugepages"))?;
+                host.nodes.insert(node, pools);
+            }
+        }
+
+        if host.nodes.is_empty() {
+            let pools = read_pools(&root.join("sys/kernel/mm/hugepages"))?;
+            if pools.is_empty() {
+                return Err(format!(
+                    "No hugepage information found under {}",
+                    root.display()
+                ));
+            }
+            host.nodes.insert(0, pools);
+        }
+
+        Ok(host)
+    }
+
+    pub fn pool(&self, node: u32, size_kib: u64) -> Option<&HugepagePool> {
+        self.nodes.get(&node).and_then(|pools| pools.get(&size_kib))
+    }
+
+    /// 宿主机支持的全部页大小
+    pub fn sizes(&self) -> BTreeSet<u64> {
+        self.nodes
+            .values()
+            .flat_map(|p| p.keys().copied())
+            .collect()
+    }
+}
+
+// 读取 hugepages-<size>kB 目录
+fn read_pools(dir: &Path) -> Result<BTreeMap<u64, HugepagePool>, String> {
+    let mut pools = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(pools);
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(size_kib) = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("hugepages-"))
+            .and_then(|n| n.strip_suffix("kB"))
+            .and_then(|n| n.parse::<u64>().ok())
+        else {
+            continue;
+        };
+
+        let path = entry.path();
+        let read = |file: &str| -> Result<u64, String> {
+            let file = path.join(file);
+            match fs::read_to_string(&file) {
+                Ok(value) => value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid value in {}", file.display())),
+                Err(_) => Ok(0),
+            }
+        };
+        pools.insert(
+            size_kib,
+            HugepagePool {
+                total: read("nr_hugepages")?,
+                free: read("free_hugepages")?,
+                surplus: read("surplus_hugepages")?,
+            },
+        );
+    }
+
+    Ok(pools)
+}
+
+/// 客户机某个 NUMA 单元对大页的需求
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HugepageDemand {
+    pub domain: String,
+    pub guest_cell: u32,
+    pub size_kib: u64,
+    pub pages: u64,
+    /// numatune 限定的宿主机节点，None 表示可从任意节点分配
+    pub host_nodes: Option<BTreeSet<u32>>,
+}
+
+/// 某个宿主机节点上某种页大小的缺口
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HugepageShortfall {
+    pub node: u32,
+    pub size_kib: u64,
+    pub needed: u64,
+    pub free: u64,
+    pub missing: u64,
+}
+
+/// 大页可行性规划结果
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HugepagePlan {
+    pub demands: Vec<HugepageDemand>,
+    /// 每个 (节点, 页大小) 分配到的页数
+    pub allocation: BTreeMap<(u32, u64), u64>,
+    pub shortfalls: Vec<HugepageShortfall>,
+    /// 补足缺口所需的 sysfs/sysctl/内核命令行修改
+    pub suggestions: Vec<String>,
+}
+
+impl fmt::Display for HugepagePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for demand in &self.demands {
+            let nodes = match &demand.host_nodes {
+                Some(nodes) => nodes
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+                None => "any".to_string(),
+            };
+            writeln!(
+                f,
+                "{} cell {}: {} x {} KiB on host node(s) {}",
+                demand.domain, demand.guest_cell, demand.pages, demand.size_kib, nodes
+            )?;
+        }
+        for s in &self.shortfalls {
+            writeln!(
+                f,
+                "shortfall: node {} {} KiB needs {} pages, {} free, {} missing",
+                s.node, s.size_kib, s.needed, s.free, s.missing
+            )?;
+        }
+        for suggestion in &self.suggestions {
+            writeln!(f, "  {}", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl HugepagePlan {
+    /// 计算一组域的大页需求并与宿主机大页池比较
+    pub fn compute(domains: &[&Domain], host: &HostHugepages) -> Self {
+        let mut plan = Self::default();
+        for domain in domains {
+            plan.demands
+                .extend(domain_demands(domain, host.default_size_kib));
+        }
+
+        let mut free: BTreeMap<(u32, u64), u64> = host
+            .nodes
+            .iter()
+            .flat_map(|(node, pools)| {
+                pools
+                    .iter()
+                    .map(move |(size, pool)| ((*node, *size), pool.free))
+            })
+            .collect();
+        let all_nodes: BTreeSet<u32> = host.nodes.keys().copied().collect();
+        let mut needed: BTreeMap<(u32, u64), u64> = BTreeMap::new();
+        let mut missing: BTreeMap<(u32, u64), u64> = BTreeMap::new();
+
+        // 限定节点越少的需求越先分配，未绑定节点的需求最后分配
+        let mut order: Vec<&HugepageDemand> = plan.demands.iter().collect();
+        order.sort_by_key(|d| d.host_nodes.as_ref().map_or(usize::MAX, BTreeSet::len));
+
+        for demand in order {
+            let allowed = demand.host_nodes.as_ref().unwrap_or(&all_nodes);
+            let mut remaining = demand.pages;
+            for node in allowed {
+                if remaining == 0 {
+                    break;
+                }
+                let available = free.entry((*node, demand.size_kib)).or_default();
+                let take = remaining.min(*available);
+                if take > 0 {
+                    *available -= take;
+                    *plan.allocation.entry((*node, demand.size_kib)).or_default() += take;
+                    *needed.entry((*node, demand.size_kib)).or_default() += take;
+                    remaining -= take;
+                }
+            }
+            if remaining > 0 {
+                let node = allowed.iter().next().copied().unwrap_or(0);
+                *needed.entry((node, demand.size_kib)).or_default() += remaining;
+                *missing.entry((node, demand.size_kib)).or_default() += remaining;
+            }
+        }
+
+        for ((node, size_kib), missing) in missing {
+            let pool = host.pool(node, size_kib).copied().unwrap_or_default();
+            plan.shortfalls.push(HugepageShortfall {
+                node,
+                size_kib,
+                needed: needed[&(node, size_kib)],
+                free: pool.free,
+                missing,
+            });
+        }
+        plan.suggestions = suggestions(&plan.shortfalls, host);
+        plan
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.shortfalls.is_empty()
+    }
+}
+
+// 按客户机 NUMA 单元拆分域的大页需求
+fn domain_demands(domain: &Domain, default_size_kib: u64) -> Vec<HugepageDemand> {
+    let Some(hugepages) = domain
+        .memory_backing
+        .as_ref()
+        .and_then(|mb| mb.hugepages.as_ref())
+    else {
+        return Vec::new();
+    };
+
+    let cells: Vec<(u32, u64)> = match domain
+        .cpu
+        .as_ref()
+        .and_then(|cpu| cpu.numa_topology.as_ref())
+    {
+        Some(numa) if !numa.cells.is_empty() => numa
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let id = cell.id.unwrap_or(index as u32);
+                (
+                    id,
+                    cell.unit.unwrap_or_default().to_bytes(cell.memory) / 1024,
+                )
+            })
+            .collect(),
+        _ => vec![(0, domain.memory.to_kib())],
+    };
+
+    let numatune = domain.numatune.as_ref();
+    let global_nodes = numatune
+        .and_then(|nt| nt.memory.as_ref())
+        .and_then(|m| m.nodeset.as_ref())
+        .and_then(|ns| ns.parse().ok());
+
+    cells
+        .into_iter()
+        .map(|(cell, memory_kib)| {
+            // 带 nodeset 的 page 优先，其次是不带 nodeset 的默认 page
+            let page = hugepages
+                .pages
+                .iter()
+                .find(|p| {
+                    p.nodeset.as_deref().is_some_and(|ns| {
+                        NumaNodeSet::from_string(ns).is_ok_and(|set| set.contains(cell))
+                    })
+                })
+                .or_else(|| hugepages.pages.iter().find(|p| p.nodeset.is_none()));
+            let size_kib = page.map_or(default_size_kib, |p| p.size_kib()).max(1);
+
+            let host_nodes = numatune
+                .and_then(|nt| nt.get_memnode(cell))
+                .and_then(|mn| mn.nodeset.parse().ok())
+                .or_else(|| global_nodes.clone())
+                .map(|nodes| nodes.into_iter().collect());
+
+            HugepageDemand {
+                domain: domain.name.clone(),
+                guest_cell: cell,
+                size_kib,
+                pages: memory_kib.div_ceil(size_kib),
+                host_nodes,
+            }
+        })
+        .collect()
+}
+
+// 1G 等巨型页在运行时往往无法分配连续内存，需要通过内核命令行预留
+fn suggestions(shortfalls: &[HugepageShortfall], host: &HostHugepages) -> Vec<String> {
+    let mut suggestions = Vec::new();
+    let mut cmdline: BTreeMap<u64, BTreeMap<u32, u64>> = BTreeMap::new();
+    let single_node = host.nodes.len() <= 1;
+
+    for s in shortfalls {
+        if !host.nodes.contains_key(&s.node) {
+            suggestions.push(format!(
+                "host has no NUMA node {}; adjust the numatune nodeset",
+                s.node
+            ));
+            continue;
+        }
+        let pool = host.pool(s.node, s.size_kib);
+        let target = pool.map_or(0, |p| p.total) + s.missing;
+        if pool.is_none() || s.size_kib >= 1024 * 1024 {
+            cmdline
+                .entry(s.size_kib)
+                .or_default()
+                .insert(s.node, target);
+        } else if single_node && s.size_kib == host.default_size_kib {
+            suggestions.push(format!("sysctl -w vm.nr_hugepages={}", target));
+        } else if single_node {
+            suggestions.push(format!(
+                "echo {} > /sys/kernel/mm/hugepages/hugepages-{}kB/nr_hugepages",
+                target, s.size_kib
+            ));
+        } else {
+            suggestions.push(format!(
+                "echo {} > /sys/devices/system/node/node{}/hugepages/hugepages-{}kB/nr_hugepages",
+                target, s.node, s.size_kib
+            ));
+        }
+    }
+
+    // 命令行会覆盖所有节点，未缺页的节点保持现有数量
+    for (size_kib, targets) in cmdline {
+        let count = if single_node {
+            targets.values().sum::<u64>().to_string()
+        } else {
+            host.nodes
+                .keys()
+                .map(|node| {
+                    let n = targets
+                        .get(node)
+                        .copied()
+                        .unwrap_or_else(|| host.pool(*node, size_kib).map_or(0, |p| p.total));
+                    format!("{}:{}", node, n)
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        suggestions.push(format!(
+            "kernel cmdline: hugepagesz={} hugepages={}",
+            kernel_size(size_kib),
+            count
+        ));
+    }
+
+    suggestions
+}
+
+fn kernel_size(size_kib: u64) -> String {
+    if size_kib.is_multiple_of(1024 * 1024) {
+        format!("{}G", size_kib / (1024 * 1024))
+    } else if size_kib.is_multiple_of(1024) {
+        format!("{}M", size_kib / 1024)
+    } else {
+        format!("{}K", size_kib)
+    }
+}
@@ -0,0 +1,203 @@
+use super::MemTune;
+use crate::vm_info::Domain;
+use crate::{MemoryUnit, MemoryValue};
+use std::fmt;
+
+/// QEMU 进程开销模型，各项均为经验值，可按实际环境调整
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverheadModel {
+    /// QEMU 进程本身（代码、堆、设备模型）
+    pub base_kib: u64,
+    /// 每个 vCPU 线程（栈、KVM 运行结构）
+    pub per_vcpu_kib: u64,
+    /// 每个 IOThread
+    pub per_iothread_kib: u64,
+    /// 页表等与客户机内存成比例的开销：guest / ratio
+    pub page_table_ratio: u64,
+    /// VFIO 直通时 libvirt 在客户机内存之外额外锁定的内存
+    pub vfio_extra_kib: u64,
+}
+
+impl Default for OverheadModel {
+    fn default() -> Self {
+        Self {
+            base_kib: 200 * 1024,
+            per_vcpu_kib: 8 * 1024,
+            per_iothread_kib: 8 * 1024,
+            page_table_ratio: 512,
+            vfio_extra_kib: 1024 * 1024,
+        }
+    }
+}
+
+/// 域的内存核算结果（KiB）
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MemoryEstimate {
+    /// `<memory>`，已包含启动时的内存设备
+    pub guest_kib: u64,
+    /// 其中内存设备（dimm 等）的容量
+    pub memory_devices_kib: u64,
+    /// maxMemory 与 `<memory>` 的差值，为内存热插拔预留
+    pub hotplug_kib: u64,
+    /// 由大页提供的客户机内存（含热插拔预留），计入 hugetlb cgroup 而不计入 hard_limit
+    pub hugepage_backed_kib: u64,
+    pub video_kib: u64,
+    pub vcpus: u32,
+    pub iothreads: u32,
+    /// QEMU 进程开销（基础、vCPU、IOThread、页表）
+    pub overhead_kib: u64,
+    /// 需要锁定的内存（`<locked/>` 或 hostdev 直通），不需要时为 None
+    pub locked_kib: Option<u64>,
+    /// 推荐的 hard_limit
+    pub hard_limit_kib: u64,
+    /// 推荐的 min_guarantee
+    pub min_guarantee_kib: u64,
+}
+
+impl fmt::Display for MemoryEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "guest memory:      {} KiB", self.guest_kib)?;
+        writeln!(f, "  memory devices:  {} KiB", self.memory_devices_kib)?;
+        writeln!(f, "  hugepage backed: {} KiB", self.hugepage_backed_kib)?;
+        writeln!(f, "hotplug headroom:  {} KiB", self.hotplug_kib)?;
+        writeln!(f, "video memory:      {} KiB", self.video_kib)?;
+        writeln!(
+            f,
+            "QEMU overhead:     {} KiB ({} vcpus, {} iothreads)",
+            self.overhead_kib, self.vcpus, self.iothreads
+        )?;
+        if let Some(locked) = self.locked_kib {
+            writeln!(f, "locked memory:     {} KiB", locked)?;
+        }
+        writeln!(f, "hard_limit:        {} KiB", self.hard_limit_kib)?;
+        write!(f, "min_guarantee:     {} KiB", self.min_guarantee_kib)
+    }
+}
+
+impl MemoryEstimate {
+    /// 使用默认开销模型估算
+    pub fn from_domain(domain: &Domain) -> Self {
+        Self::with_model(domain, &OverheadModel::default())
+    }
+
+    pub fn with_model(domain: &Domain, model: &OverheadModel) -> Self {
+        let guest_kib = domain.memory.to_kib();
+        let memory_devices_kib: u64 = domain
+            .devices
+            .memory_devices
+            .iter()
+            .flatten()
+            .map(|d| d.size_kib())
+            .sum();
+        let hotplug_kib = domain
+            .max_memory
+            .as_ref()
+            .map(|m| {
+                let unit = m.unit.parse::<MemoryUnit>().unwrap_or_default();
+                (unit.to_bytes(m.value) / 1024).saturating_sub(guest_kib)
+            })
+            .unwrap_or(0);
+
+        let memory_backing = domain.memory_backing.as_ref();
+        let hugepages = memory_backing.is_some_and(|mb| mb.hugepages.is_some());
+        let video_kib: u64 = domain
+            .devices
+            .videos
+            .iter()
+            .flatten()
+            .map(|v| v.memory_kib())
+            .sum();
+
+        let vcpus = domain.vcpu.vcpu_count;
+        let iothreads = domain.iothread_ids().len() as u32;
+
+        let total_guest = guest_kib + hotplug_kib;
+        let hugepage_backed_kib = if hugepages { total_guest } else { 0 };
+        let overhead_kib = model.base_kib
+            + model.per_vcpu_kib * u64::from(vcpus)
+            + model.per_iothread_kib * u64::from(iothreads)
+            + total_guest / model.page_table_ratio.max(1);
+
+        let has_hostdev = domain
+            .devices
+            .hostdevs
+            .as_ref()
+            .is_some_and(|h| !h.is_empty());
+        let locked = memory_backing.is_some_and(|mb| mb.locked.is_some());
+        let locked_kib = if has_hostdev {
+            Some(total_guest + model.vfio_extra_kib)
+        } else if locked {
+            Some(total_guest)
+        } else {
+            None
+        };
+
+        // 大页内存不计入 memory cgroup，hard_limit 只需覆盖其余部分
+        let mut hard_limit_kib = total_guest - hugepage_backed_kib + video_kib + overhead_kib;
+        // 锁定的内存同样计入 memory cgroup
+        if let Some(locked) = locked_kib
+            && !hugepages
+        {
+            hard_limit_kib = hard_limit_kib.max(locked + video_kib + overhead_kib);
+        }
+
+        Self {
+            guest_kib,
+            memory_devices_kib,
+            hotplug_kib,
+            hugepage_backed_kib,
+            video_kib,
+            vcpus,
+            iothreads,
+            overhead_kib,
+            locked_kib,
+            hard_limit_kib,
+            min_guarantee_kib: if hugepages { 0 } else { guest_kib },
+        }
+    }
+
+    /// 生成包含推荐 hard_limit/min_guarantee 的 MemTune
+    pub fn to_memtune(&self) -> MemTune {
+        let mut memtune = MemTune::new().with_hard_limit(self.hard_limit_kib, MemoryUnit::KiB);
+        if self.min_guarantee_kib > 0 {
+            memtune.min_guarantee = Some(MemoryValue::from_kib(self.min_guarantee_kib));
+        }
+        memtune
+    }
+}
+
+impl MemTune {
+    /// 在 validate 的基础上，检查 hard_limit 是否低于估算值
+    pub fn validate_for(&self, domain: &Domain) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        if let Err(mut e) = self.validate() {
+            errors.append(&mut e);
+        }
+
+        let estimate = MemoryEstimate::from_domain(domain);
+        if let Some(hard_limit) = &self.hard_limit {
+            let hard_kib = hard_limit.to_kib();
+            if hard_kib < estimate.hard_limit_kib {
+                errors.push(format!(
+                    "hard_limit ({}) is below the estimated requirement of {} KiB; the guest may be OOM-killed",
+                    hard_limit.to_human_readable(),
+                    estimate.hard_limit_kib
+                ));
+            }
+        } else if domain
+            .memory_backing
+            .as_ref()
+            .is_some_and(|mb| mb.locked.is_some())
+        {
+            errors.push(
+                "<memoryBacking><locked/> requires <memtune><hard_limit> to be set".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
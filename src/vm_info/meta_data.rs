@@ -1,30 +1,530 @@
-use serde::{Deserialize, Serialize};
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use serde::de::{self, DeserializeOwned, IgnoredAny, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::Range;
 
-#[derive(Debug, Deserialize, Serialize)]
+/// `<metadata>`，每个应用在自己的命名空间下保存一个元素
+///
+/// 解析时 quick-xml 会去掉元素名的前缀，前缀从元素上的 `xmlns:<prefix>` 属性恢复，
+/// 写回时子元素沿用根元素的前缀。通过 [`Domain::from_xml`](crate::Domain::from_xml)
+/// 解析的元素还保存原文（包括注释和混合文本），未修改时由
+/// [`Domain::to_xml`](crate::Domain::to_xml) 按原文写回。
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct MetaData {
-    #[serde(
-        rename = "{http://app1.org/app1}foo",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub app1_foo: Option<GenericElement>,
-
-    #[serde(
-        rename = "{http://app1.org/app2}bar",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub app2_bar: Option<GenericElement>,
-
-    // 允许其他未指定的元数据元素
-    #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    pub other: Option<std::collections::HashMap<String, GenericElement>>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-pub struct GenericElement {
-    #[serde(rename = "$text")]
-    pub content: String,
-
-    // 捕获所有属性
-    #[serde(flatten)]
-    pub attributes: std::collections::HashMap<String, String>,
+    pub elements: Vec<MetadataElement>,
+}
+
+/// `<metadata>` 下的任意 XML 元素
+#[derive(Debug, Clone, Default)]
+pub struct MetadataElement {
+    /// 不含前缀的元素名
+    pub name: String,
+    /// 属性，包括 `xmlns:<prefix>` 声明
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<MetadataElement>,
+    pub text: Option<String>,
+    // 源 XML 中的原文，不参与比较
+    raw: Option<String>,
+}
+
+impl PartialEq for MetadataElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.attributes == other.attributes
+            && self.children == other.children
+            && self.text == other.text
+    }
+}
+
+/// 可以保存在 `<metadata>` 中的类型
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Ownership { team: String }
+///
+/// impl MetadataType for Ownership {
+///     const NAMESPACE: &'static str = "http://example.org/ownership/1.0";
+///     const PREFIX: &'static str = "own";
+///     const ELEMENT: &'static str = "ownership";
+/// }
+/// ```
+pub trait MetadataType: Serialize + DeserializeOwned {
+    const NAMESPACE: &'static str;
+    const PREFIX: &'static str;
+    const ELEMENT: &'static str;
+}
+
+impl MetaData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按命名空间查找元素
+    pub fn get_raw(&self, namespace: &str) -> Option<&MetadataElement> {
+        self.elements
+            .iter()
+            .find(|e| e.namespace() == Some(namespace))
+    }
+
+    /// 设置元素，同一命名空间的旧元素会被替换
+    pub fn set_raw(&mut self, element: MetadataElement) {
+        match element
+            .namespace()
+            .and_then(|ns| self.elements.iter().position(|e| e.namespace() == Some(ns)))
+        {
+            Some(i) => self.elements[i] = element,
+            None => self.elements.push(element),
+        }
+    }
+
+    pub fn remove_raw(&mut self, namespace: &str) -> Option<MetadataElement> {
+        let index = self
+            .elements
+            .iter()
+            .position(|e| e.namespace() == Some(namespace))?;
+        Some(self.elements.remove(index))
+    }
+
+    /// 读取 `T` 对应命名空间的数据
+    pub fn get<T: MetadataType>(&self) -> Result<Option<T>, String> {
+        let Some(element) = self.get_raw(T::NAMESPACE) else {
+            return Ok(None);
+        };
+        quick_xml::de::from_str(&element.to_xml())
+            .map(Some)
+            .map_err(|e| format!("Invalid metadata in namespace '{}': {}", T::NAMESPACE, e))
+    }
+
+    /// 写入 `T`，替换同一命名空间的旧数据
+    pub fn set<T: MetadataType>(&mut self, value: &T) -> Result<(), String> {
+        let xml = quick_xml::se::to_string_with_root(T::ELEMENT, value)
+            .map_err(|e| format!("Failed to serialize metadata '{}': {}", T::ELEMENT, e))?;
+        let mut element = MetadataElement::from_xml(&xml)?;
+        element.name = T::ELEMENT.to_string();
+        element.attributes.insert(
+            0,
+            (format!("xmlns:{}", T::PREFIX), T::NAMESPACE.to_string()),
+        );
+        self.set_raw(element);
+        Ok(())
+    }
+
+    pub fn remove<T: MetadataType>(&mut self) -> bool {
+        self.remove_raw(T::NAMESPACE).is_some()
+    }
+
+    /// 记录各元素在源 XML 中的原文，元素数量对不上时不记录
+    pub(crate) fn capture_raw(&mut self, xml: &str) -> Result<(), String> {
+        let Some((_, spans)) = metadata_spans(xml)? else {
+            return Ok(());
+        };
+        if spans.len() == self.elements.len() {
+            for (element, span) in self.elements.iter_mut().zip(spans) {
+                element.raw = Some(xml[span].to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// 把序列化结果中的 `<metadata>` 换成保留原文的版本
+    pub(crate) fn restore_raw(&self, mut xml: String) -> Result<String, String> {
+        if self.elements.iter().all(|e| e.raw().is_none()) {
+            return Ok(xml);
+        }
+        let Some((span, _)) = metadata_spans(&xml)? else {
+            return Ok(xml);
+        };
+        let mut metadata = String::from("<metadata>");
+        for element in &self.elements {
+            match element.raw() {
+                Some(raw) => metadata.push_str(raw),
+                None => metadata.push_str(&element.to_xml()),
+            }
+        }
+        metadata.push_str("</metadata>");
+        xml.replace_range(span, &metadata);
+        Ok(xml)
+    }
+
+    /// 每个元素必须声明自己的命名空间，同一命名空间只能出现一次
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut seen = BTreeSet::new();
+        for element in &self.elements {
+            match element.namespace() {
+                None => errors.push(format!(
+                    "Metadata element <{}> must declare a namespace",
+                    element.name
+                )),
+                Some(ns) if !seen.insert(ns) => {
+                    errors.push(format!("Duplicate metadata namespace '{}'", ns))
+                }
+                Some(_) => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl MetadataElement {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_attribute(mut self, name: &str, value: &str) -> Self {
+        self.attributes.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_child(mut self, child: MetadataElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 元素声明的命名空间 URI
+    pub fn namespace(&self) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == "xmlns" || n.starts_with("xmlns:"))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 元素声明的命名空间前缀
+    pub fn prefix(&self) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find_map(|(n, _)| n.strip_prefix("xmlns:"))
+    }
+
+    /// 解析单个 XML 元素
+    pub fn from_xml(xml: &str) -> Result<Self, String> {
+        let mut element: MetadataElement =
+            quick_xml::de::from_str(xml).map_err(|e| format!("Invalid metadata XML: {}", e))?;
+        // 根元素名不会交给 Deserialize，单独读取
+        let start = xml.trim_start().trim_start_matches('<');
+        let name: String = start
+            .chars()
+            .take_while(|c| !c.is_whitespace() && *c != '>' && *c != '/')
+            .collect();
+        element.name = match name.split_once(':') {
+            Some((_, local)) => local.to_string(),
+            None => name,
+        };
+        Ok(element)
+    }
+
+    /// 源 XML 中的原文，元素被修改过时返回 None
+    pub fn raw(&self) -> Option<&str> {
+        let raw = self.raw.as_deref()?;
+        let parsed = MetadataElement::from_xml(raw).ok()?;
+        (parsed == *self).then_some(raw)
+    }
+
+    /// 按结构序列化为 XML 文本
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        self.write_xml(None, &mut xml);
+        xml
+    }
+
+    fn write_xml(&self, inherited: Option<&str>, xml: &mut String) {
+        let name = self.qualified_name(inherited);
+        xml.push('<');
+        xml.push_str(&name);
+        for (attr, value) in &self.attributes {
+            xml.push_str(&format!(" {}=\"{}\"", attr, escape(value.as_str())));
+        }
+        if self.children.is_empty() && self.text.is_none() {
+            xml.push_str("/>");
+            return;
+        }
+        xml.push('>');
+        let prefix = self.prefix().or(inherited);
+        for child in &self.children {
+            child.write_xml(prefix, xml);
+        }
+        if let Some(text) = &self.text {
+            xml.push_str(&escape(text.as_str()));
+        }
+        xml.push_str(&format!("</{}>", name));
+    }
+
+    fn qualified_name(&self, inherited: Option<&str>) -> String {
+        match self.prefix().or(inherited) {
+            Some(prefix) => format!("{}:{}", prefix, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+// `<metadata>` 的字节范围，以及其中各子元素的范围
+type MetadataSpans = (Range<usize>, Vec<Range<usize>>);
+
+// 在根元素下查找 `<metadata>`
+fn metadata_spans(xml: &str) -> Result<Option<MetadataSpans>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut depth = 0;
+    let mut metadata = None;
+    let mut children = Vec::new();
+    let mut child_start = 0;
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Invalid XML at {}: {}", reader.error_position(), e))?;
+        let end = reader.buffer_position() as usize;
+        match event {
+            Event::Start(e) => {
+                depth += 1;
+                if depth == 2 && e.name().as_ref() == b"metadata" {
+                    metadata = Some(start);
+                } else if depth == 3 && metadata.is_some() {
+                    child_start = start;
+                }
+            }
+            Event::Empty(e) => {
+                if depth == 1 && e.name().as_ref() == b"metadata" {
+                    return Ok(Some((start..end, Vec::new())));
+                } else if depth == 2 && metadata.is_some() {
+                    children.push(start..end);
+                }
+            }
+            Event::End(_) => {
+                if depth == 3 && metadata.is_some() {
+                    children.push(child_start..end);
+                } else if depth == 2
+                    && let Some(metadata) = metadata
+                {
+                    return Ok(Some((metadata..end, children)));
+                }
+                depth -= 1;
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+// 序列化时带上前缀
+struct PrefixedElement<'a> {
+    element: &'a MetadataElement,
+    prefix: Option<&'a str>,
+}
+
+impl Serialize for PrefixedElement<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let element = self.element;
+        let prefix = element.prefix().or(self.prefix);
+        let mut map = serializer.serialize_map(None)?;
+        for (name, value) in &element.attributes {
+            map.serialize_entry(&format!("@{}", name), value)?;
+        }
+        for child in &element.children {
+            map.serialize_entry(
+                &child.qualified_name(prefix),
+                &PrefixedElement {
+                    element: child,
+                    prefix,
+                },
+            )?;
+        }
+        if let Some(text) = &element.text {
+            map.serialize_entry("$text", text)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for MetaData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.elements.len()))?;
+        for element in &self.elements {
+            map.serialize_entry(
+                &element.qualified_name(None),
+                &PrefixedElement {
+                    element,
+                    prefix: None,
+                },
+            )?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MetaData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MetaDataVisitor;
+
+        impl<'de> Visitor<'de> for MetaDataVisitor {
+            type Value = MetaData;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "<metadata> elements")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<MetaData, A::Error> {
+                let mut elements = Vec::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    if key.starts_with('@') || key == "$text" {
+                        map.next_value::<IgnoredAny>()?;
+                        continue;
+                    }
+                    let mut element: MetadataElement = map.next_value()?;
+                    element.name = key;
+                    elements.push(element);
+                }
+                Ok(MetaData { elements })
+            }
+
+            fn visit_str<E: de::Error>(self, _: &str) -> Result<MetaData, E> {
+                Ok(MetaData::default())
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<MetaData, E> {
+                Ok(MetaData::default())
+            }
+        }
+
+        deserializer.deserialize_map(MetaDataVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for MetadataElement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ElementVisitor;
+
+        impl<'de> Visitor<'de> for ElementVisitor {
+            type Value = MetadataElement;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an XML element")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<MetadataElement, A::Error> {
+                let mut element = MetadataElement::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    if let Some(attr) = key.strip_prefix('@') {
+                        element
+                            .attributes
+                            .push((attr.to_string(), map.next_value()?));
+                    } else if key == "$text" {
+                        element.text = Some(map.next_value()?);
+                    } else {
+                        let mut child: MetadataElement = map.next_value()?;
+                        child.name = key;
+                        element.children.push(child);
+                    }
+                }
+                Ok(element)
+            }
+
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<MetadataElement, E> {
+                Ok(MetadataElement::default().with_text(text))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<MetadataElement, E> {
+                Ok(MetadataElement::default())
+            }
+        }
+
+        deserializer.deserialize_map(ElementVisitor)
+    }
+}
+
+/// 已注册的命名空间
+#[derive(Debug, Clone)]
+pub struct RegisteredNamespace {
+    pub namespace: &'static str,
+    pub prefix: &'static str,
+    pub element: &'static str,
+    pub type_name: &'static str,
+    check: fn(&MetaData) -> Result<(), String>,
+}
+
+/// 应用注册的元数据命名空间，用于验证 `<metadata>` 中的已知数据
+#[derive(Debug, Clone, Default)]
+pub struct MetadataRegistry {
+    namespaces: Vec<RegisteredNamespace>,
+}
+
+impl MetadataRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册 `T`，同一命名空间重复注册时以后者为准
+    pub fn register<T: MetadataType>(mut self) -> Self {
+        self.namespaces.retain(|ns| ns.namespace != T::NAMESPACE);
+        self.namespaces.push(RegisteredNamespace {
+            namespace: T::NAMESPACE,
+            prefix: T::PREFIX,
+            element: T::ELEMENT,
+            type_name: std::any::type_name::<T>(),
+            check: |metadata| metadata.get::<T>().map(|_| ()),
+        });
+        self
+    }
+
+    pub fn lookup(&self, namespace: &str) -> Option<&RegisteredNamespace> {
+        self.namespaces.iter().find(|ns| ns.namespace == namespace)
+    }
+
+    pub fn namespaces(&self) -> &[RegisteredNamespace] {
+        &self.namespaces
+    }
+
+    /// 在 [`MetaData::validate`] 的基础上检查已注册命名空间的元素名和内容
+    pub fn validate(&self, metadata: &MetaData) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        if let Err(mut e) = metadata.validate() {
+            errors.append(&mut e);
+        }
+
+        for element in &metadata.elements {
+            let Some(registered) = element.namespace().and_then(|ns| self.lookup(ns)) else {
+                continue;
+            };
+            if element.name != registered.element {
+                errors.push(format!(
+                    "Metadata namespace '{}' expects <{}>, found <{}>",
+                    registered.namespace, registered.element, element.name
+                ));
+                continue;
+            }
+            if let Err(e) = (registered.check)(metadata) {
+                errors.push(format!("{} ({})", e, registered.type_name));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
@@ -0,0 +1,337 @@
+use crate::vm_info::cpu::MaxPhysAddrMode;
+use crate::vm_info::{CpuCompareResult, CpuMode, Domain, HostCpuInfo};
+use std::fmt;
+
+/// 迁移目标宿主机的描述
+#[derive(Debug, Clone, Default)]
+pub struct DestinationDescription {
+    /// 目标宿主机 CPU，提供时会与 custom CPU 比较
+    pub host_cpu: Option<HostCpuInfo>,
+    /// 目标宿主机 NUMA 节点数
+    pub numa_nodes: Option<u32>,
+    /// 目标宿主机支持的大页尺寸（KiB）
+    pub hugepage_sizes_kib: Vec<u64>,
+    /// 源与目标共享的存储路径前缀（NFS、集群文件系统等）
+    pub shared_storage: Vec<String>,
+    /// 目标宿主机允许锁定的内存上限（KiB），None 表示不限制
+    pub locked_memory_limit_kib: Option<u64>,
+    /// 目标宿主机的物理地址位数
+    pub phys_addr_bits: Option<u32>,
+    /// 源宿主机的物理地址位数，用于比较 maxphysaddr passthrough
+    pub source_phys_addr_bits: Option<u32>,
+}
+
+impl DestinationDescription {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_host_cpu(mut self, host_cpu: HostCpuInfo) -> Self {
+        self.host_cpu = Some(host_cpu);
+        self
+    }
+
+    pub fn with_numa_nodes(mut self, numa_nodes: u32) -> Self {
+        self.numa_nodes = Some(numa_nodes);
+        self
+    }
+
+    pub fn with_hugepage_size(mut self, size_kib: u64) -> Self {
+        if !self.hugepage_sizes_kib.contains(&size_kib) {
+            self.hugepage_sizes_kib.push(size_kib);
+        }
+        self
+    }
+
+    pub fn with_shared_storage(mut self, prefix: &str) -> Self {
+        self.shared_storage.push(prefix.to_string());
+        self
+    }
+
+    pub fn with_locked_memory_limit(mut self, limit_kib: u64) -> Self {
+        self.locked_memory_limit_kib = Some(limit_kib);
+        self
+    }
+
+    pub fn with_phys_addr_bits(mut self, bits: u32) -> Self {
+        self.phys_addr_bits = Some(bits);
+        self
+    }
+
+    pub fn with_source_phys_addr_bits(mut self, bits: u32) -> Self {
+        self.source_phys_addr_bits = Some(bits);
+        self
+    }
+
+    /// 路径是否位于共享存储上
+    pub fn is_shared_path(&self, path: &str) -> bool {
+        self.shared_storage.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+            path == prefix
+                || path
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// 热迁移兼容性检查结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationCheck {
+    /// 阻止迁移的问题
+    pub blockers: Vec<String>,
+    /// 不阻止迁移但无法确认或需要注意的问题
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for MigrationCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_migratable() {
+            writeln!(f, "migratable")?;
+        } else {
+            writeln!(f, "not migratable")?;
+        }
+        for blocker in &self.blockers {
+            writeln!(f, "  blocker: {}", blocker)?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "  warning: {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+impl MigrationCheck {
+    /// 检查域能否热迁移到目标宿主机
+    pub fn run(domain: &Domain, dest: &DestinationDescription) -> Self {
+        let mut check = Self::default();
+        check.check_cpu(domain, dest);
+        check.check_devices(domain, dest);
+        check.check_hugepages(domain, dest);
+        check.check_numatune(domain, dest);
+        check.check_max_phys_addr(domain, dest);
+        check.check_locked_memory(domain, dest);
+        check
+    }
+
+    pub fn is_migratable(&self) -> bool {
+        self.blockers.is_empty()
+    }
+
+    /// 转换为与 validate 一致的结果，Err 中为全部阻止项
+    pub fn into_result(self) -> Result<(), Vec<String>> {
+        if self.blockers.is_empty() {
+            Ok(())
+        } else {
+            Err(self.blockers)
+        }
+    }
+
+    fn check_cpu(&mut self, domain: &Domain, dest: &DestinationDescription) {
+        let Some(cpu) = &domain.cpu else {
+            return;
+        };
+
+        let mode = cpu.mode.unwrap_or_default();
+        if matches!(mode, CpuMode::HostPassthrough | CpuMode::Maximum) {
+            self.blockers.push(format!(
+                "CPU mode '{}' exposes source host features and cannot be migrated safely",
+                mode
+            ));
+        } else if cpu.migratable == Some(false) {
+            self.blockers.push(
+                "CPU is marked migratable='off' and includes non-migratable features".to_string(),
+            );
+        }
+
+        if mode == CpuMode::Custom
+            && cpu.model.is_some()
+            && let Some(host) = &dest.host_cpu
+        {
+            match cpu.compare(host) {
+                Ok(cmp) if cmp.result == CpuCompareResult::Incompatible => {
+                    if !cmp.missing.is_empty() {
+                        self.blockers.push(format!(
+                            "Destination CPU lacks required features: {}",
+                            cmp.missing.join(", ")
+                        ));
+                    }
+                    if !cmp.forbidden.is_empty() {
+                        self.blockers.push(format!(
+                            "Destination CPU provides forbidden features: {}",
+                            cmp.forbidden.join(", ")
+                        ));
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => self.blockers.push(e),
+            }
+        }
+    }
+
+    fn check_devices(&mut self, domain: &Domain, dest: &DestinationDescription) {
+        for hostdev in domain.devices.hostdevs.iter().flatten() {
+            self.blockers.push(format!(
+                "Host device {} is passed through and cannot be migrated",
+                hostdev.describe()
+            ));
+        }
+
+        for disk in domain.devices.disk.iter().flatten() {
+            if disk.disk_type == "network" {
+                continue;
+            }
+            // 空光驱没有需要迁移的存储
+            let Some(path) = disk.source.path() else {
+                continue;
+            };
+            if !dest.is_shared_path(path) {
+                self.blockers.push(format!(
+                    "Disk '{}' uses local storage '{}' which is not shared with the destination",
+                    disk.target.dev, path
+                ));
+            }
+        }
+    }
+
+    fn check_hugepages(&mut self, domain: &Domain, dest: &DestinationDescription) {
+        let Some(hugepages) = domain
+            .memory_backing
+            .as_ref()
+            .and_then(|mb| mb.hugepages.as_ref())
+        else {
+            return;
+        };
+
+        if hugepages.pages.is_empty() {
+            if dest.hugepage_sizes_kib.is_empty() {
+                self.blockers.push(
+                    "Domain uses hugepages but the destination has none configured".to_string(),
+                );
+            }
+            return;
+        }
+
+        for page in &hugepages.pages {
+            let size = page.size_kib();
+            if !dest.hugepage_sizes_kib.contains(&size) {
+                self.blockers.push(format!(
+                    "Hugepage size {} KiB is not available on the destination",
+                    size
+                ));
+            }
+        }
+    }
+
+    fn check_numatune(&mut self, domain: &Domain, dest: &DestinationDescription) {
+        let Some(numatune) = &domain.numatune else {
+            return;
+        };
+
+        let mut nodesets = Vec::new();
+        if let Some(nodeset) = numatune.memory.as_ref().and_then(|m| m.nodeset.as_ref()) {
+            nodesets.push(("numatune memory".to_string(), nodeset));
+        }
+        for memnode in numatune.memnodes.iter().flatten() {
+            nodesets.push((
+                format!("numatune memnode {}", memnode.cell_id),
+                &memnode.nodeset,
+            ));
+        }
+
+        for (what, nodeset) in nodesets {
+            let nodes = match nodeset.parse() {
+                Ok(nodes) => nodes,
+                Err(e) => {
+                    self.blockers
+                        .push(format!("Invalid {} nodeset: {}", what, e));
+                    continue;
+                }
+            };
+            let Some(count) = dest.numa_nodes else {
+                self.warnings.push(format!(
+                    "Destination NUMA node count is unknown, cannot verify {} nodeset '{}'",
+                    what, nodeset.expression
+                ));
+                continue;
+            };
+            let mut outside: Vec<u32> = nodes.into_iter().filter(|&n| n >= count).collect();
+            outside.sort_unstable();
+            if !outside.is_empty() {
+                self.blockers.push(format!(
+                    "{} nodeset '{}' references node(s) {:?} but the destination has {} NUMA node(s)",
+                    what, nodeset.expression, outside, count
+                ));
+            }
+        }
+    }
+
+    fn check_max_phys_addr(&mut self, domain: &Domain, dest: &DestinationDescription) {
+        let Some(max_phys_addr) = domain.cpu.as_ref().and_then(|c| c.max_phys_addr.as_ref()) else {
+            return;
+        };
+
+        match max_phys_addr.mode {
+            MaxPhysAddrMode::Passthrough => {
+                let effective = |host: Option<u32>| match (host, max_phys_addr.limit) {
+                    (Some(host), Some(limit)) => Some(host.min(limit)),
+                    (host, _) => host,
+                };
+                match (
+                    effective(dest.source_phys_addr_bits),
+                    effective(dest.phys_addr_bits),
+                ) {
+                    (Some(source), Some(target)) if source != target => {
+                        self.blockers.push(format!(
+                            "maxphysaddr mode='passthrough' gives the guest {} bits on the source but {} bits on the destination",
+                            source, target
+                        ));
+                    }
+                    (Some(_), Some(_)) => {}
+                    _ => self.warnings.push(
+                        "maxphysaddr mode='passthrough' cannot be verified without the source and destination address widths"
+                            .to_string(),
+                    ),
+                }
+            }
+            MaxPhysAddrMode::Emulate => {
+                if let (Some(bits), Some(target)) = (max_phys_addr.bits, dest.phys_addr_bits)
+                    && bits > target
+                {
+                    self.blockers.push(format!(
+                        "maxphysaddr requires {} bits but the destination supports only {}",
+                        bits, target
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_locked_memory(&mut self, domain: &Domain, dest: &DestinationDescription) {
+        let locked = domain
+            .memory_backing
+            .as_ref()
+            .is_some_and(|mb| mb.locked.is_some());
+        if !locked {
+            return;
+        }
+        let Some(limit) = dest.locked_memory_limit_kib else {
+            return;
+        };
+
+        // 有 hard_limit 时 libvirt 以其作为锁定上限，否则需要锁定全部客户机内存
+        let required = domain
+            .memtune
+            .as_ref()
+            .and_then(|mt| mt.hard_limit.as_ref())
+            .map(|hl| hl.to_kib())
+            .unwrap_or_else(|| domain.memory.to_kib());
+
+        if limit < required {
+            self.blockers.push(format!(
+                "Domain locks {} KiB of memory but the destination allows only {} KiB",
+                required, limit
+            ));
+        }
+    }
+}
@@ -1,39 +1,104 @@
 mod blkiotune;
+mod canonical;
+mod clock;
 mod cpu;
 mod cputune;
 mod devices;
 mod domain;
 mod events;
 mod features;
+mod hypervisor;
+mod iothreads;
+mod lint;
+mod live;
 mod memory;
 mod memory_backing;
 mod memtune;
 mod meta_data;
+mod migration;
 mod numatune;
 mod os;
 mod pm;
+mod qemu_argv;
 mod resource;
+mod security;
 mod sysinfo;
 mod throttlegroups;
 mod utils;
 mod vcpu;
 
 use blkiotune::BlkioTune;
-use cpu::CpuConfig;
-use cputune::Cputune;
-pub use devices::Devices;
+pub use canonical::CanonicalOptions;
+pub use clock::{
+    Clock, ClockBasis, ClockOffset, TickPolicy, Timer, TimerCatchup, TimerName, TimerTrack, TscMode,
+};
+pub use cpu::{
+    CpuArch, CpuCompareResult, CpuComparison, CpuConfig, CpuFeature, CpuMap, CpuMatch, CpuMode,
+    CpuModel, CpuModelDef, FallbackPolicy, FeaturePolicy, HostCpuInfo, MaxPhysAddr,
+    MaxPhysAddrMode,
+};
+pub use cputune::{
+    CacheAllocation, CachePlan, Cputune, Emulatorpin, Iothreadpin, Iothreadsched, RealtimeProfile,
+    ResctrlCacheInfo, ResctrlCacheType, ResctrlInfo, ResctrlMemBwInfo, ResctrlMonitorInfo,
+    SchedulerPolicy, Vcpupin, Vcpusched,
+};
+pub use devices::{
+    BootDevice, BootOrder, Controller, ControllerDriver, DeviceAddress, DeviceAlias, Devices,
+    DriverIothread, DriverIothreads, Hostdev, HostdevAddress, HostdevSource, Interface,
+    InterfaceSource, IothreadQueue, MemBalloon, MemBalloonStats, MemoryDevice, MemoryDeviceTarget,
+    Panic, PcrBanks, Redirdev, Rng, RngBackend, RngProtocol, RngRate, RngSource, Tpm, TpmBackend,
+    TpmDevice, TpmEncryption, Video, VideoModel, VirtioDriver, Watchdog,
+};
 pub use domain::Domain;
-use events::LifecycleAction;
-use features::Features;
+pub use events::{
+    DomainEvent, DomainState, DomainStateMachine, LifecycleAction, LifecycleConfig, LifecycleEvent,
+    LifecycleManager, SideEffect, Transition,
+};
+pub use features::{
+    AiaConfig, AiaValue, DirtyRingConfig, EmptyElement, EnabledAttribute, FeatureState, Features,
+    GicConfig, HptConfig, HptResizing, HypervFeatures, HypervMode, IbsConfig, IbsValue,
+    IoApicConfig, IoApicDriver, KvmFeatures, MsrsConfig, MsrsUnknown, PageSizeConfig,
+    PassthroughConfig, SmmConfig, SpeculationConfig, SpeculationValue, SpinlocksConfig,
+    StimerConfig, TcgConfig, TlbFlushConfig, VendorIdConfig, XenFeatures, XenPassthroughMode,
+};
+pub use hypervisor::{CapabilityRegistry, Hypervisor, HypervisorCapabilities};
+pub use iothreads::{DefaultIothread, IothreadId, IothreadIds, IothreadPoll};
+pub use lint::{
+    Diagnostic, LintPolicy, LintReport, LintRule, Linter, PathCondition, PathRule, RuleOverride,
+    Severity,
+};
 use memory::Memory;
-use memory_backing::MemoryBacking;
-use memtune::MemTune;
-use meta_data::MetaData;
+pub use memory_backing::{
+    HostHugepages, HugePage, HugePages, HugepageDemand, HugepagePlan, HugepagePool,
+    HugepageShortfall, MemoryBacking,
+};
+pub use memtune::{MemTune, MemoryEstimate, OverheadModel};
+pub use meta_data::{
+    MetaData, MetadataElement, MetadataRegistry, MetadataType, RegisteredNamespace,
+};
+pub use migration::{DestinationDescription, MigrationCheck};
 use numatune::NumaTune;
-use os::Os;
+pub use os::{
+    Acpi, AcpiTable, FirmwareDescriptor, FirmwareFeature, FirmwareFeatures, FirmwareFile,
+    FirmwareMapping, FirmwareRequest, FirmwareResolver, FirmwareTarget, InitEnv, MachineType,
+    OsArch, OsKind, Smbios, SmbiosMode,
+};
+use os::{Os, OsType};
 use pm::PowerManagement;
+pub use pm::PowerManagementManager;
+pub use qemu_argv::QemuArgvImport;
 use resource::ResourceConfig;
-use sysinfo::Sysinfo;
+pub use resource::ResourceManager;
+pub use security::{
+    KeyWrap, KeyWrapCipher, LaunchSecurity, Seclabel, SeclabelType, SevLaunchSecurity,
+    SevSnpLaunchSecurity, TdxLaunchSecurity,
+};
+pub use sysinfo::{
+    BaseBoardEntry, BaseBoardInfo, BiosEntry, BiosInfo, ChassisEntry, ChassisInfo, DMI_DIR,
+    DmiRedaction, FWCFG_NAME_MAX, FwcfgSysinfo, HostDmi, IGNITION_FWCFG_NAME,
+    NOCLOUD_SERIAL_PREFIX, OemStringEntry, OemStringsInfo, ProvisionPayload, Provisioning,
+    SmbiosEntryName, SmbiosSysinfo, Sysinfo, SysinfoEntry, SystemEntry, SystemInfo,
+};
 use throttlegroups::ThrottleGroups;
 pub use utils::*;
 use vcpu::{Vcpu, Vcpus};
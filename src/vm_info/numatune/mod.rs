@@ -4,7 +4,7 @@ mod node_set;
 
 use mem_node::MemNode;
 use memory::{NumaMemory, PlacementMode};
-use node_set::NodeSet;
+pub use node_set::NodeSet;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 // 内存分配模式枚举
@@ -140,4 +140,26 @@ impl NodeSet {
     pub fn node_count(&self) -> Result<usize, String> {
         self.parse().map(|nodes| nodes.len())
     }
+
+    /// 规范形式：升序、合并连续范围、去掉排除标记，如 "0-3,^2,8" 变为 "0-1,3,8"
+    pub fn to_canonical(&self) -> Result<String, String> {
+        let mut nodes: Vec<u32> = self.parse()?.into_iter().collect();
+        nodes.sort_unstable();
+
+        let mut ranges: Vec<String> = Vec::new();
+        let mut iter = nodes.into_iter().peekable();
+        while let Some(start) = iter.next() {
+            let mut end = start;
+            while iter.peek() == Some(&(end + 1)) {
+                end += 1;
+                iter.next();
+            }
+            ranges.push(if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
+            });
+        }
+        Ok(ranges.join(","))
+    }
 }
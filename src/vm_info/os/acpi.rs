@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+// ACPI 表，type 为 raw、rawset、slic 或 msdm，内容为宿主机上的表文件路径
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Acpi {
+    #[serde(rename = "table", default)]
+    pub tables: Vec<AcpiTable>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AcpiTable {
+    #[serde(rename = "@type")]
+    pub table_type: String,
+
+    #[serde(rename = "$text")]
+    pub path: String,
+}
+
+impl AcpiTable {
+    pub fn new(table_type: &str, path: &str) -> Self {
+        Self {
+            table_type: table_type.to_string(),
+            path: path.to_string(),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+// 容器 init 进程的环境变量
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InitEnv {
+    #[serde(rename = "@name")]
+    pub name: String,
+
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+
+impl InitEnv {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use super::{Loader, MachineType, Nvram, Os, OsArch};
+use crate::vm_info::{Domain, Features, SmmConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// QEMU 固件描述文件的默认搜索目录，靠前的目录中的同名文件优先
+pub const FIRMWARE_DIRS: &[&str] = &["/etc/qemu/firmware", "/usr/share/qemu/firmware"];
+
+/// libvirt 为每个域创建 NVRAM 文件的默认目录
+pub const NVRAM_DIR: &str = "/var/lib/libvirt/qemu/nvram";
+
+/// `<os><firmware>`，固件自动选择时要求（enabled='yes'）或排除（enabled='no'）的特性
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct FirmwareFeatures {
+    #[serde(rename = "feature", default)]
+    pub features: Vec<FirmwareFeature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FirmwareFeature {
+    #[serde(rename = "@enabled")]
+    pub enabled: String,
+    /// enrolled-keys 或 secure-boot
+    #[serde(rename = "@name")]
+    pub name: String,
+}
+
+impl FirmwareFeatures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_feature(mut self, name: &str, enabled: bool) -> Self {
+        self.features.push(FirmwareFeature {
+            enabled: yes_no(enabled),
+            name: name.to_string(),
+        });
+        self
+    }
+
+    /// 特性的要求，未指定时为 None
+    pub fn get(&self, name: &str) -> Option<bool> {
+        self.features
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| f.enabled == "yes")
+    }
+
+    pub fn validate(
+        &self,
+        firmware: Option<&str>,
+        loader: Option<&Loader>,
+    ) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut seen = Vec::new();
+        for feature in &self.features {
+            if feature.name != "enrolled-keys" && feature.name != "secure-boot" {
+                errors.push(format!(
+                    "Unknown firmware feature '{}', expected enrolled-keys or secure-boot",
+                    feature.name
+                ));
+            }
+            if feature.enabled != "yes" && feature.enabled != "no" {
+                errors.push(format!(
+                    "Firmware feature '{}' enabled must be 'yes' or 'no', got '{}'",
+                    feature.name, feature.enabled
+                ));
+            }
+            if seen.contains(&feature.name.as_str()) {
+                errors.push(format!("Duplicate firmware feature '{}'", feature.name));
+            }
+            seen.push(&feature.name);
+        }
+
+        if firmware != Some("efi") {
+            errors.push("<firmware><feature> requires <os firmware='efi'>".to_string());
+        }
+        if self.get("enrolled-keys") == Some(true) && self.get("secure-boot") == Some(false) {
+            errors.push("Firmware feature enrolled-keys requires secure-boot".to_string());
+        }
+        if let (Some(secure), Some(loader_secure)) = (
+            self.get("secure-boot"),
+            loader.and_then(|l| l.secure.as_deref()),
+        ) && secure != (loader_secure == "yes")
+        {
+            errors.push(format!(
+                "Firmware feature secure-boot conflicts with <loader secure='{}'>",
+                loader_secure
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// QEMU 固件描述文件（docs/interop/firmware.json）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FirmwareDescriptor {
+    #[serde(default)]
+    pub description: String,
+    /// bios、uefi 等
+    #[serde(default)]
+    pub interface_types: Vec<String>,
+    pub mapping: FirmwareMapping,
+    #[serde(default)]
+    pub targets: Vec<FirmwareTarget>,
+    /// secure-boot、enrolled-keys、requires-smm、amd-sev 等
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// 固件映射方式：flash（pflash）、memory（rom）或 kernel
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FirmwareMapping {
+    pub device: String,
+    /// flash 设备的 split、combined 或 stateless
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub executable: Option<FirmwareFile>,
+    #[serde(default)]
+    pub nvram_template: Option<FirmwareFile>,
+    /// memory 设备的固件路径
+    #[serde(default)]
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FirmwareFile {
+    pub filename: String,
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FirmwareTarget {
+    pub architecture: String,
+    /// 机器类型的通配模式，如 "pc-q35-*"
+    #[serde(default)]
+    pub machines: Vec<String>,
+}
+
+impl FirmwareDescriptor {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+
+    /// 是否支持给定体系结构和机器类型
+    pub fn supports(&self, arch: &str, machine: &str) -> bool {
+        let machine = canonical_machine(machine);
+        self.targets
+            .iter()
+            .any(|t| t.architecture == arch && t.machines.iter().any(|m| glob_match(m, &machine)))
+    }
+
+    /// 固件可执行文件路径
+    pub fn executable(&self) -> Option<&str> {
+        match self.mapping.device.as_str() {
+            "flash" => self
+                .mapping
+                .executable
+                .as_ref()
+                .map(|f| f.filename.as_str()),
+            "memory" => self.mapping.filename.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn is_stateless(&self) -> bool {
+        self.mapping.device == "flash" && self.mapping.mode.as_deref() == Some("stateless")
+    }
+}
+
+/// 固件选择条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirmwareRequest {
+    /// bios 或 uefi
+    pub interface: String,
+    pub arch: String,
+    pub machine: String,
+    /// 特性名到是否需要，false 表示必须不具备
+    pub features: BTreeMap<String, bool>,
+    /// 为 Some 时要求固件映射为 stateless（true）或带 NVRAM（false）
+    pub stateless: Option<bool>,
+}
+
+impl FirmwareRequest {
+    pub fn new(interface: &str, arch: &str, machine: &str) -> Self {
+        Self {
+            interface: interface.to_string(),
+            arch: arch.to_string(),
+            machine: machine.to_string(),
+            features: BTreeMap::new(),
+            stateless: None,
+        }
+    }
+
+    pub fn with_feature(mut self, feature: &str, enabled: bool) -> Self {
+        self.features.insert(feature.to_string(), enabled);
+        self
+    }
+
+    pub fn with_stateless(mut self, stateless: bool) -> Self {
+        self.stateless = Some(stateless);
+        self
+    }
+
+    /// 按 `<os firmware>`、`<type arch machine>` 与 `<loader secure stateless>` 生成选择条件
+    ///
+    /// `firmware='efi'` 或 pflash 类型的 `<loader>` 请求 UEFI，`firmware='bios'` 请求 BIOS；
+    /// 两者都没有时 libvirt 不做自动选择，返回错误。
+    /// 未指定时体系结构按 x86_64、机器类型按 pc 处理；`<features><smm state='off'/>`
+    /// 会排除需要 SMM 的固件，`<launchSecurity>` 会要求 amd-sev、amd-sev-snp 或 intel-tdx。
+    pub fn from_domain(domain: &Domain) -> Result<Self, String> {
+        let os = &domain.os;
+        let pflash = os
+            .loader
+            .as_ref()
+            .is_some_and(|l| l.loader_type.as_deref() == Some("pflash"));
+        let interface = match os.firmware.as_deref() {
+            Some("efi") => "uefi",
+            Some("bios") => "bios",
+            Some(other) => return Err(format!("Unsupported firmware type '{}'", other)),
+            None if pflash => "uefi",
+            None => {
+                return Err(format!(
+                    "Domain '{}' does not request firmware autoselection, set <os firmware='efi'> or <os firmware='bios'>",
+                    domain.name
+                ));
+            }
+        };
+        let mut request = Self::new(
+            interface,
+            os.os_type.arch.as_ref().map_or("x86_64", OsArch::as_str),
+            &os.os_type
+                .machine
+                .as_ref()
+                .map_or("pc".to_string(), MachineType::to_string),
+        );
+        if let Some(loader) = &os.loader {
+            match loader.secure.as_deref() {
+                Some("yes") => request = request.with_feature("secure-boot", true),
+                Some("no") => request = request.with_feature("secure-boot", false),
+                _ => {}
+            }
+            match loader.stateless.as_deref() {
+                Some("yes") => request = request.with_stateless(true),
+                Some("no") => request = request.with_stateless(false),
+                _ => {}
+            }
+        }
+        for feature in os.firmware_features.iter().flat_map(|f| &f.features) {
+            request = request.with_feature(&feature.name, feature.enabled == "yes");
+        }
+        if domain
+            .features
+            .as_ref()
+            .and_then(|f| f.smm.as_ref())
+            .is_some_and(|smm| smm.state == "off")
+        {
+            request = request.with_feature("requires-smm", false);
+        }
+        for feature in domain
+            .launch_security
+            .iter()
+            .flat_map(|ls| ls.firmware_features())
+        {
+            request = request.with_feature(feature, true);
+        }
+        Ok(request)
+    }
+
+    fn matches(&self, descriptor: &FirmwareDescriptor) -> bool {
+        if !descriptor.interface_types.contains(&self.interface)
+            || !descriptor.supports(&self.arch, &self.machine)
+            || descriptor.executable().is_none()
+        {
+            return false;
+        }
+        if let Some(stateless) = self.stateless
+            && descriptor.is_stateless() != stateless
+        {
+            return false;
+        }
+        self.features
+            .iter()
+            .all(|(feature, &enabled)| descriptor.has_feature(feature) == enabled)
+    }
+}
+
+/// 按 QEMU 固件描述文件自动选择固件
+#[derive(Debug, Clone, Default)]
+pub struct FirmwareResolver {
+    /// 按文件名排序的描述文件
+    pub descriptors: Vec<(PathBuf, FirmwareDescriptor)>,
+}
+
+impl FirmwareResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_descriptor(mut self, path: &str, descriptor: FirmwareDescriptor) -> Self {
+        self.descriptors.push((PathBuf::from(path), descriptor));
+        self
+    }
+
+    /// 读取本机的 /etc/qemu/firmware 与 /usr/share/qemu/firmware
+    pub fn from_host() -> Result<Self, Vec<String>> {
+        Self::from_dirs(FIRMWARE_DIRS)
+    }
+
+    /// 读取单个目录中的 *.json，便于使用测试夹具
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, Vec<String>> {
+        Self::from_dirs(&[dir])
+    }
+
+    /// 读取多个目录，与 QEMU 规范一致：同名文件以靠前的目录为准，
+    /// 最终按文件名排序决定优先级；不存在的目录会被忽略。
+    pub fn from_dirs<P: AsRef<Path>>(dirs: &[P]) -> Result<Self, Vec<String>> {
+        let mut files: BTreeMap<String, PathBuf> = BTreeMap::new();
+        for dir in dirs {
+            let Ok(entries) = fs::read_dir(dir.as_ref()) else {
+                continue;
+            };
+            for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+                if path.extension().is_none_or(|ext| ext != "json") {
+                    continue;
+                }
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    files.entry(name.to_string()).or_insert(path);
+                }
+            }
+        }
+
+        let mut resolver = Self::new();
+        let mut errors = Vec::new();
+        for path in files.into_values() {
+            // 空文件用于屏蔽低优先级目录中的同名描述文件
+            match fs::read_to_string(&path) {
+                Ok(json) if json.trim().is_empty() => {}
+                Ok(json) => match FirmwareDescriptor::from_json(&json) {
+                    Ok(descriptor) => resolver.descriptors.push((path, descriptor)),
+                    Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+                },
+                Err(e) => errors.push(format!("Failed to read {}: {}", path.display(), e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(resolver)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 返回第一个满足条件的描述文件
+    pub fn select(&self, request: &FirmwareRequest) -> Option<&FirmwareDescriptor> {
+        self.descriptors
+            .iter()
+            .map(|(_, d)| d)
+            .find(|d| request.matches(d))
+    }
+
+    /// 为域选择固件并填写 `<loader>`/`<nvram>`
+    ///
+    /// 已指定 NVRAM 路径时只补充 template；固件需要 SMM 且未配置 `<smm>` 时一并开启。
+    pub fn resolve(&self, domain: &mut Domain) -> Result<FirmwareDescriptor, String> {
+        let request = FirmwareRequest::from_domain(domain)?;
+        let descriptor = self.select(&request).cloned().ok_or_else(|| {
+            format!(
+                "No {} firmware for {} machine '{}' matches features {:?}",
+                request.interface, request.arch, request.machine, request.features
+            )
+        })?;
+
+        let extension = match descriptor
+            .mapping
+            .nvram_template
+            .as_ref()
+            .and_then(|t| t.format.as_deref())
+        {
+            Some("qcow2") => "qcow2",
+            _ => "fd",
+        };
+        let nvram_path = format!("{}/{}_VARS.{}", NVRAM_DIR, domain.name, extension);
+        descriptor.apply(&mut domain.os, &nvram_path);
+
+        if descriptor.has_feature("requires-smm") {
+            let features = domain.features.get_or_insert_with(Features::new);
+            if features.smm.is_none() {
+                features.smm = Some(SmmConfig {
+                    state: "on".to_string(),
+                    tseg: None,
+                });
+            }
+        }
+        Ok(descriptor)
+    }
+}
+
+impl FirmwareDescriptor {
+    /// 按描述文件填写 `<loader>` 和 `<nvram>`
+    pub fn apply(&self, os: &mut Os, nvram_path: &str) {
+        let flash = self.mapping.device == "flash";
+        let loader = os.loader.get_or_insert_with(Loader::default);
+        loader.path = self.executable().map(str::to_string);
+        loader.loader_type = Some(if flash { "pflash" } else { "rom" }.to_string());
+        if flash {
+            loader.readonly = Some("yes".to_string());
+        }
+        loader.secure = Some(yes_no(self.has_feature("secure-boot")));
+        if self.is_stateless() {
+            loader.stateless = Some("yes".to_string());
+            os.nvram = None;
+            return;
+        }
+
+        let template = self
+            .mapping
+            .nvram_template
+            .as_ref()
+            .map(|t| t.filename.clone());
+        if !flash || template.is_none() {
+            return;
+        }
+        match &mut os.nvram {
+            Some(nvram) => nvram.template = template,
+            None => os.nvram = Some(Nvram::with_path(nvram_path, template)),
+        }
+    }
+}
+
+impl Os {
+    /// 是否使用 UEFI：`firmware='efi'` 或 pflash 加载器
+    pub fn uses_uefi(&self) -> bool {
+        self.firmware.as_deref() == Some("efi")
+            || self
+                .loader
+                .as_ref()
+                .is_some_and(|l| l.loader_type.as_deref() == Some("pflash"))
+    }
+
+    /// 验证 `<os firmware>`、`<loader>` 与 `<nvram>` 的组合
+    ///
+    /// x86 上 `secure='yes'` 需要 pflash 加载器和 `<smm state='on'/>`。
+    pub fn validate_firmware(&self, features: Option<&Features>) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if let Some(firmware) = &self.firmware
+            && firmware != "bios"
+            && firmware != "efi"
+        {
+            errors.push(format!(
+                "<os firmware> must be 'bios' or 'efi', got '{}'",
+                firmware
+            ));
+        }
+
+        let Some(loader) = &self.loader else {
+            return if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            };
+        };
+
+        for (name, value) in [
+            ("readonly", &loader.readonly),
+            ("secure", &loader.secure),
+            ("stateless", &loader.stateless),
+        ] {
+            if let Some(value) = value
+                && value != "yes"
+                && value != "no"
+            {
+                errors.push(format!(
+                    "<loader> {} must be 'yes' or 'no', got '{}'",
+                    name, value
+                ));
+            }
+        }
+        if let Some(loader_type) = &loader.loader_type
+            && loader_type != "rom"
+            && loader_type != "pflash"
+        {
+            errors.push(format!(
+                "<loader> type must be 'rom' or 'pflash', got '{}'",
+                loader_type
+            ));
+        }
+
+        if loader.stateless.as_deref() == Some("yes") && self.nvram.is_some() {
+            errors.push("<nvram> cannot be used with a stateless <loader>".to_string());
+        }
+
+        if loader.secure.as_deref() == Some("yes") {
+            if self.firmware.as_deref() == Some("bios") {
+                errors.push("Secure boot requires UEFI firmware, not 'bios'".to_string());
+            }
+            // 自动选择固件时类型由选择结果决定，手动指定路径时必须是 pflash
+            let pflash = match loader.loader_type.as_deref() {
+                Some(loader_type) => loader_type == "pflash",
+                None => loader.path.is_none(),
+            };
+            if !pflash {
+                errors.push("Secure boot requires a <loader type='pflash'>".to_string());
+            }
+
+            let x86 = self.os_type.arch.as_ref().is_none_or(OsArch::is_x86);
+            if x86 {
+                let smm_on = features
+                    .and_then(|f| f.smm.as_ref())
+                    .is_some_and(|smm| smm.state == "on");
+                if !smm_on {
+                    errors.push(
+                        "Secure boot requires <features><smm state='on'/></features>".to_string(),
+                    );
+                }
+                if let Some(machine) = &self.os_type.machine
+                    && !machine.is_q35()
+                {
+                    errors.push(format!(
+                        "Secure boot requires a q35 machine type, got '{}'",
+                        machine
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Domain {
+    /// 按 QEMU 固件描述文件选择固件，填写 `<loader>`/`<nvram>`
+    pub fn resolve_firmware(
+        &mut self,
+        resolver: &FirmwareResolver,
+    ) -> Result<FirmwareDescriptor, String> {
+        resolver.resolve(self)
+    }
+
+    /// 验证固件配置，secure boot 需要结合 `<features>` 检查
+    pub fn validate_firmware(&self) -> Result<(), Vec<String>> {
+        self.os.validate_firmware(self.features.as_ref())
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+// 机器类型别名按最新版本处理，以便匹配 "pc-q35-*" 这样的模式
+fn canonical_machine(machine: &str) -> String {
+    match machine {
+        "q35" => "pc-q35-latest".to_string(),
+        "pc" => "pc-i440fx-latest".to_string(),
+        _ => machine.to_string(),
+    }
+}
+
+// 支持 * 和 ? 的简单通配匹配
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
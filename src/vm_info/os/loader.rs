@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 //
 //
 // 当启用固件自动选择时，可以使用format属性告诉libvirt只考虑采用特定格式的固件构建。支持的值为raw和qcow2。自9.2.0起（仅限QEMU）
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Loader {
     // 属性
     #[serde(rename = "@readonly", skip_serializing_if = "Option::is_none")]
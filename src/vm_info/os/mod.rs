@@ -1,12 +1,23 @@
+mod acpi;
+mod container;
+mod firmware;
 mod loader;
 mod nvram;
 mod smbios;
+mod types;
 
 use serde::{Deserialize, Serialize};
 
-use loader::Loader;
-use nvram::Nvram;
-use smbios::Smbios;
+pub use acpi::{Acpi, AcpiTable};
+pub use container::InitEnv;
+pub use firmware::{
+    FirmwareDescriptor, FirmwareFeature, FirmwareFeatures, FirmwareFile, FirmwareMapping,
+    FirmwareRequest, FirmwareResolver, FirmwareTarget,
+};
+pub use loader::Loader;
+pub use nvram::Nvram;
+pub use smbios::{Smbios, SmbiosMode};
+pub use types::{MachineType, OsArch, OsKind};
 // Bios bootloader
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -19,13 +30,54 @@ pub struct Os {
     #[serde(rename = "type")]
     pub os_type: OsType, // hvm 裸机完全虚拟化；linux
 
+    // 固件自动选择时要求或排除的特性
+    #[serde(rename = "firmware", skip_serializing_if = "Option::is_none")]
+    pub firmware_features: Option<FirmwareFeatures>,
+
     #[serde(rename = "loader", skip_serializing_if = "Option::is_none")]
     pub loader: Option<Loader>,
 
     #[serde(rename = "nvram", skip_serializing_if = "Option::is_none")]
     pub nvram: Option<Nvram>,
 
-    #[serde(rename = "boot", skip_serializing_if = "Vec::is_empty")]
+    // 直接内核引导
+    #[serde(rename = "kernel", skip_serializing_if = "Option::is_none")]
+    pub kernel: Option<String>,
+
+    #[serde(rename = "initrd", skip_serializing_if = "Option::is_none")]
+    pub initrd: Option<String>,
+
+    #[serde(rename = "cmdline", skip_serializing_if = "Option::is_none")]
+    pub cmdline: Option<String>,
+
+    // 设备树，用于 ARM、RISC-V 等
+    #[serde(rename = "dtb", skip_serializing_if = "Option::is_none")]
+    pub dtb: Option<String>,
+
+    // UEFI 安全启动时先于内核加载的 shim
+    #[serde(rename = "shim", skip_serializing_if = "Option::is_none")]
+    pub shim: Option<String>,
+
+    // 容器引导：init 程序及其参数、环境、工作目录和身份
+    #[serde(rename = "init", skip_serializing_if = "Option::is_none")]
+    pub init: Option<String>,
+
+    #[serde(rename = "initarg", default, skip_serializing_if = "Vec::is_empty")]
+    pub init_args: Vec<String>,
+
+    #[serde(rename = "initenv", default, skip_serializing_if = "Vec::is_empty")]
+    pub init_envs: Vec<InitEnv>,
+
+    #[serde(rename = "initdir", skip_serializing_if = "Option::is_none")]
+    pub init_dir: Option<String>,
+
+    #[serde(rename = "inituser", skip_serializing_if = "Option::is_none")]
+    pub init_user: Option<String>,
+
+    #[serde(rename = "initgroup", skip_serializing_if = "Option::is_none")]
+    pub init_group: Option<String>,
+
+    #[serde(rename = "boot", default, skip_serializing_if = "Vec::is_empty")]
     pub boots: Vec<Boot>,
 
     #[serde(rename = "bootmenu", skip_serializing_if = "Option::is_none")]
@@ -36,6 +88,9 @@ pub struct Os {
 
     #[serde(rename = "bios", skip_serializing_if = "Option::is_none")]
     pub bios: Option<Bios>,
+
+    #[serde(rename = "acpi", skip_serializing_if = "Option::is_none")]
+    pub acpi: Option<Acpi>,
 }
 
 // 指定要在虚拟机中引导的操作系统类型：hvm 完全虚拟化; linux;
@@ -43,11 +98,211 @@ pub struct Os {
 pub struct OsType {
     // 属性
     #[serde(rename = "@arch", skip_serializing_if = "Option::is_none")]
-    pub arch: Option<String>,
+    pub arch: Option<OsArch>,
+
+    #[serde(rename = "@machine", skip_serializing_if = "Option::is_none")]
+    pub machine: Option<MachineType>, // e.g., "q35", "pc-i440fx-8.2", "virt"
 
     // 内容
     #[serde(rename = "$text")]
-    pub value: String,
+    pub value: OsKind,
+}
+
+impl Os {
+    pub fn new(os_type: OsType) -> Self {
+        Self {
+            firmware: None,
+            os_type,
+            firmware_features: None,
+            loader: None,
+            nvram: None,
+            kernel: None,
+            initrd: None,
+            cmdline: None,
+            dtb: None,
+            shim: None,
+            init: None,
+            init_args: Vec::new(),
+            init_envs: Vec::new(),
+            init_dir: None,
+            init_user: None,
+            init_group: None,
+            boots: Vec::new(),
+            bootmenu: None,
+            smbios: None,
+            bios: None,
+            acpi: None,
+        }
+    }
+
+    /// 直接内核引导
+    pub fn with_kernel(mut self, kernel: &str) -> Self {
+        self.kernel = Some(kernel.to_string());
+        self
+    }
+
+    pub fn with_initrd(mut self, initrd: &str) -> Self {
+        self.initrd = Some(initrd.to_string());
+        self
+    }
+
+    pub fn with_cmdline(mut self, cmdline: &str) -> Self {
+        self.cmdline = Some(cmdline.to_string());
+        self
+    }
+
+    /// 容器引导的 init 程序及参数
+    pub fn with_init(mut self, init: &str, args: &[&str]) -> Self {
+        self.init = Some(init.to_string());
+        self.init_args = args.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    pub fn with_init_env(mut self, name: &str, value: &str) -> Self {
+        self.init_envs.push(InitEnv::new(name, value));
+        self
+    }
+
+    pub fn with_boot(mut self, dev: &str) -> Self {
+        self.boots.push(Boot {
+            dev: dev.to_string(),
+        });
+        self
+    }
+
+    /// 验证引导方式之间的组合，例如 `<kernel>` 不能与 `<boot dev>` 同时使用
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let kind = self.os_type.value;
+        let arch = self.os_type.arch.as_ref();
+
+        for boot in &self.boots {
+            if !matches!(boot.dev.as_str(), "hd" | "cdrom" | "fd" | "network") {
+                errors.push(format!(
+                    "<boot dev> must be hd, cdrom, fd or network, got '{}'",
+                    boot.dev
+                ));
+            }
+        }
+
+        if self.kernel.is_some() {
+            if !self.boots.is_empty() {
+                errors.push("<kernel> direct boot cannot be combined with <boot dev>".to_string());
+            }
+        } else {
+            for (name, present) in [
+                ("initrd", self.initrd.is_some()),
+                ("cmdline", self.cmdline.is_some()),
+                ("dtb", self.dtb.is_some()),
+            ] {
+                if present {
+                    errors.push(format!("<{}> requires <kernel>", name));
+                }
+            }
+        }
+        if self.dtb.is_some() && arch.is_some_and(OsArch::is_x86) {
+            errors.push("<dtb> is not supported on x86".to_string());
+        }
+        if self.shim.is_some() && self.firmware.as_deref() != Some("efi") && self.loader.is_none() {
+            errors.push("<shim> requires UEFI firmware".to_string());
+        }
+
+        let init_elements = [
+            ("initarg", !self.init_args.is_empty()),
+            ("initenv", !self.init_envs.is_empty()),
+            ("initdir", self.init_dir.is_some()),
+            ("inituser", self.init_user.is_some()),
+            ("initgroup", self.init_group.is_some()),
+        ];
+        if kind == OsKind::Exe {
+            if self.init.is_none() {
+                errors.push("<os><type>exe</type> requires <init>".to_string());
+            }
+            for (name, present) in [
+                ("kernel", self.kernel.is_some()),
+                ("loader", self.loader.is_some()),
+                ("boot", !self.boots.is_empty()),
+                ("acpi", self.acpi.is_some()),
+            ] {
+                if present {
+                    errors.push(format!("<{}> cannot be used with <type>exe</type>", name));
+                }
+            }
+        } else {
+            if self.init.is_some() {
+                errors.push(format!(
+                    "<init> is only valid for <type>exe</type>, not '{}'",
+                    kind
+                ));
+            }
+            for (name, present) in init_elements {
+                if present {
+                    errors.push(format!("<{}> is only valid for <type>exe</type>", name));
+                }
+            }
+        }
+
+        if let Some(acpi) = &self.acpi {
+            if kind != OsKind::Hvm {
+                errors.push("<acpi><table> requires <type>hvm</type>".to_string());
+            }
+            for table in &acpi.tables {
+                if !matches!(
+                    table.table_type.as_str(),
+                    "raw" | "rawset" | "slic" | "msdm"
+                ) {
+                    errors.push(format!(
+                        "ACPI table type must be raw, rawset, slic or msdm, got '{}'",
+                        table.table_type
+                    ));
+                }
+                if table.path.is_empty() {
+                    errors.push("ACPI table path must not be empty".to_string());
+                }
+            }
+        }
+
+        if kind != OsKind::Hvm
+            && (self.firmware.is_some() || self.loader.is_some() || self.nvram.is_some())
+        {
+            errors.push(format!(
+                "Firmware configuration requires <type>hvm</type>, not '{}'",
+                kind
+            ));
+        }
+
+        if let Some(features) = &self.firmware_features
+            && let Err(mut e) = features.validate(self.firmware.as_deref(), self.loader.as_ref())
+        {
+            errors.append(&mut e);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl OsType {
+    pub fn new(value: OsKind) -> Self {
+        Self {
+            arch: None,
+            machine: None,
+            value,
+        }
+    }
+
+    pub fn with_arch(mut self, arch: &str) -> Self {
+        self.arch = Some(OsArch::from(arch));
+        self
+    }
+
+    pub fn with_machine(mut self, machine: &str) -> Self {
+        self.machine = Some(MachineType::from(machine));
+        self
+    }
 }
 
 // 引导选项
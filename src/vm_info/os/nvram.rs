@@ -16,7 +16,7 @@
 // 如果加载器被标记为无状态，则提供此元素无效。
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Nvram {
@@ -32,6 +32,22 @@ pub struct Nvram {
     pub content: NvramContent,
 }
 
+impl Nvram {
+    /// 以文本形式给出路径的 NVRAM 文件，即 `<nvram template='...'>path</nvram>`
+    pub fn with_path(path: &str, template: Option<String>) -> Self {
+        Self {
+            template,
+            nvram_type: None,
+            // 与反序列化结果一致，SimplePath 无法在 flatten 下序列化
+            content: NvramContent::Complex(Box::new(NvramComplex {
+                source: None,
+                extra: BTreeMap::new(),
+                text: Some(path.to_string()),
+            })),
+        }
+    }
+}
+
 // 处理 nvram 的不同内容类型
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -47,8 +63,8 @@ pub struct NvramComplex {
     pub source: Option<Source>,
 
     // 其他可能的子元素
-    #[serde(flatten, skip_serializing_if = "HashMap::is_empty")]
-    pub extra: HashMap<String, String>,
+    #[serde(flatten, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
 
     // 文本内容（如果有）
     #[serde(rename = "$text", skip_serializing_if = "Option::is_none")]
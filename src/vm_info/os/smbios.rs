@@ -1,7 +1,38 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Smbios {
     #[serde(rename = "@mode")]
     pub mode: String, // e.g., "sysinfo"
 }
+
+impl Smbios {
+    pub fn new(mode: SmbiosMode) -> Self {
+        Self {
+            mode: mode.to_string(),
+        }
+    }
+}
+
+/// `<os><smbios mode>` 的取值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmbiosMode {
+    /// 由 hypervisor 生成默认值
+    Emulate,
+    /// 复制宿主机的表 0 和表 1（uuid 除外）
+    Host,
+    /// 使用域中的 `<sysinfo type='smbios'>`
+    Sysinfo,
+}
+
+impl fmt::Display for SmbiosMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self {
+            SmbiosMode::Emulate => "emulate",
+            SmbiosMode::Host => "host",
+            SmbiosMode::Sysinfo => "sysinfo",
+        };
+        write!(f, "{}", mode)
+    }
+}
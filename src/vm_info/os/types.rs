@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// `<os><type>` 的内容，即客户机的引导方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OsKind {
+    /// 完全虚拟化
+    Hvm,
+    /// Xen 半虚拟化（旧名称）
+    Linux,
+    /// Xen 半虚拟化
+    Xen,
+    /// Xen PVH
+    Xenpvh,
+    /// 容器，运行 `<init>` 指定的程序
+    Exe,
+    /// User Mode Linux
+    Uml,
+}
+
+impl OsKind {
+    /// Xen 半虚拟化客户机，可以使用 `<bootloader>`
+    pub fn is_paravirt(&self) -> bool {
+        matches!(self, OsKind::Linux | OsKind::Xen | OsKind::Xenpvh)
+    }
+}
+
+impl fmt::Display for OsKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OsKind::Hvm => "hvm",
+            OsKind::Linux => "linux",
+            OsKind::Xen => "xen",
+            OsKind::Xenpvh => "xenpvh",
+            OsKind::Exe => "exe",
+            OsKind::Uml => "uml",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// `<os><type arch>`，未知的体系结构保留原样
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum OsArch {
+    X86_64,
+    I686,
+    Aarch64,
+    Armv7l,
+    Ppc64,
+    Ppc64le,
+    S390x,
+    Riscv64,
+    Loongarch64,
+    Other(String),
+}
+
+impl OsArch {
+    pub fn as_str(&self) -> &str {
+        match self {
+            OsArch::X86_64 => "x86_64",
+            OsArch::I686 => "i686",
+            OsArch::Aarch64 => "aarch64",
+            OsArch::Armv7l => "armv7l",
+            OsArch::Ppc64 => "ppc64",
+            OsArch::Ppc64le => "ppc64le",
+            OsArch::S390x => "s390x",
+            OsArch::Riscv64 => "riscv64",
+            OsArch::Loongarch64 => "loongarch64",
+            OsArch::Other(arch) => arch,
+        }
+    }
+
+    pub fn is_x86(&self) -> bool {
+        matches!(self, OsArch::X86_64 | OsArch::I686)
+    }
+}
+
+impl From<&str> for OsArch {
+    fn from(arch: &str) -> Self {
+        match arch {
+            "x86_64" => OsArch::X86_64,
+            "i686" => OsArch::I686,
+            "aarch64" => OsArch::Aarch64,
+            "armv7l" => OsArch::Armv7l,
+            "ppc64" => OsArch::Ppc64,
+            "ppc64le" => OsArch::Ppc64le,
+            "s390x" => OsArch::S390x,
+            "riscv64" => OsArch::Riscv64,
+            "loongarch64" => OsArch::Loongarch64,
+            other => OsArch::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for OsArch {
+    fn from(arch: String) -> Self {
+        OsArch::from(arch.as_str())
+    }
+}
+
+impl From<OsArch> for String {
+    fn from(arch: OsArch) -> Self {
+        arch.as_str().to_string()
+    }
+}
+
+impl fmt::Display for OsArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// `<os><type machine>`，按机器类型族解析，带版本号时保留版本
+///
+/// 例如 `pc-q35-8.2` 解析为 `Q35(Some("8.2"))`，`q35` 解析为 `Q35(None)`。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum MachineType {
+    /// `pc` 或 `pc-i440fx-<version>`
+    I440fx(Option<String>),
+    /// `q35` 或 `pc-q35-<version>`
+    Q35(Option<String>),
+    /// `virt` 或 `virt-<version>`（ARM、RISC-V）
+    Virt(Option<String>),
+    /// `pseries` 或 `pseries-<version>`
+    Pseries(Option<String>),
+    /// `s390-ccw-virtio` 或 `s390-ccw-virtio-<version>`
+    S390CcwVirtio(Option<String>),
+    Microvm,
+    Other(String),
+}
+
+impl MachineType {
+    pub fn is_q35(&self) -> bool {
+        matches!(self, MachineType::Q35(_))
+    }
+
+    pub fn is_i440fx(&self) -> bool {
+        matches!(self, MachineType::I440fx(_))
+    }
+
+    /// 是否提供 PCI 总线，未知的机器类型按提供处理
+    pub fn has_pci(&self) -> bool {
+        !matches!(self, MachineType::Microvm | MachineType::S390CcwVirtio(_))
+    }
+}
+
+impl From<&str> for MachineType {
+    fn from(machine: &str) -> Self {
+        let versioned = |prefix: &str| machine.strip_prefix(prefix).map(str::to_string);
+        match machine {
+            "pc" => MachineType::I440fx(None),
+            "q35" => MachineType::Q35(None),
+            "virt" => MachineType::Virt(None),
+            "pseries" => MachineType::Pseries(None),
+            "s390-ccw-virtio" => MachineType::S390CcwVirtio(None),
+            "microvm" => MachineType::Microvm,
+            _ => {
+                if let Some(version) = versioned("pc-i440fx-") {
+                    MachineType::I440fx(Some(version))
+                } else if let Some(version) = versioned("pc-q35-") {
+                    MachineType::Q35(Some(version))
+                } else if let Some(version) = versioned("virt-") {
+                    MachineType::Virt(Some(version))
+                } else if let Some(version) = versioned("pseries-") {
+                    MachineType::Pseries(Some(version))
+                } else if let Some(version) = versioned("s390-ccw-virtio-") {
+                    MachineType::S390CcwVirtio(Some(version))
+                } else {
+                    MachineType::Other(machine.to_string())
+                }
+            }
+        }
+    }
+}
+
+impl From<String> for MachineType {
+    fn from(machine: String) -> Self {
+        MachineType::from(machine.as_str())
+    }
+}
+
+impl From<MachineType> for String {
+    fn from(machine: MachineType) -> Self {
+        machine.to_string()
+    }
+}
+
+impl fmt::Display for MachineType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (alias, prefix, version) = match self {
+            MachineType::I440fx(version) => ("pc", "pc-i440fx-", version),
+            MachineType::Q35(version) => ("q35", "pc-q35-", version),
+            MachineType::Virt(version) => ("virt", "virt-", version),
+            MachineType::Pseries(version) => ("pseries", "pseries-", version),
+            MachineType::S390CcwVirtio(version) => ("s390-ccw-virtio", "s390-ccw-virtio-", version),
+            MachineType::Microvm => return write!(f, "microvm"),
+            MachineType::Other(machine) => return write!(f, "{}", machine),
+        };
+        match version {
+            Some(version) => write!(f, "{}{}", prefix, version),
+            None => write!(f, "{}", alias),
+        }
+    }
+}
@@ -1,8 +1,9 @@
 mod power_management_manager;
 mod sleep_state;
 
+pub use power_management_manager::PowerManagementManager;
 use serde::{Deserialize, Serialize};
-use sleep_state::{SleepState, SleepStateConfig};
+pub use sleep_state::{SleepState, SleepStateConfig};
 
 /// 电源管理配置
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
@@ -1,10 +1,11 @@
 use super::PowerManagement;
+use crate::vm_info::{CapabilityRegistry, Hypervisor};
 
 /// 电源管理配置管理器
 #[derive(Debug, Default)]
 pub struct PowerManagementManager {
-    /// 支持的驱动列表
-    supported_drivers: Vec<String>,
+    /// 域类型能力表
+    registry: CapabilityRegistry,
     /// 配置历史
     config_history: Vec<PowerManagement>,
 }
@@ -12,39 +13,47 @@ pub struct PowerManagementManager {
 impl PowerManagementManager {
     /// 创建新的电源管理管理器
     pub fn new() -> Self {
-        Self {
-            supported_drivers: vec!["qemu".to_string()],
-            config_history: Vec::new(),
-        }
+        Self::default()
+    }
+
+    /// 使用自定义的能力表
+    pub fn with_registry(mut self, registry: CapabilityRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// 检查域类型是否支持电源管理
+    pub fn is_driver_supported(&self, hypervisor: &Hypervisor) -> bool {
+        self.registry
+            .get(hypervisor)
+            .is_some_and(|caps| caps.power_management)
     }
 
-    /// 检查驱动是否支持电源管理
-    pub fn is_driver_supported(&self, driver: &str) -> bool {
-        self.supported_drivers
-            .iter()
-            .any(|supported| supported == driver)
+    /// 支持电源管理的域类型
+    fn supported_drivers(&self) -> Vec<&Hypervisor> {
+        self.registry
+            .hypervisors()
+            .into_iter()
+            .filter(|h| self.is_driver_supported(h))
+            .collect()
     }
 
     /// 验证配置（考虑驱动支持）
     pub fn validate_config(
         &self,
         config: &PowerManagement,
-        driver: &str,
+        hypervisor: &Hypervisor,
     ) -> Result<(), Vec<String>> {
         let mut errors = Vec::new();
 
         // 检查驱动支持
-        if !self.is_driver_supported(driver) {
-            errors.push(format!(
-                "Power management configuration is not supported by driver '{}'. Supported drivers: {}",
-                driver,
-                self.supported_drivers.join(", ")
-            ));
+        if let Err(mut e) = self.registry.validate_power_management(hypervisor, config) {
+            errors.append(&mut e);
             return Err(errors);
         }
 
         // 基础验证
-        match config.validate(Some(driver)) {
+        match config.validate(Some(hypervisor.driver())) {
             Ok(_) => {}
             Err(warnings) => {
                 // 这些是警告而不是错误
@@ -90,17 +99,17 @@ impl PowerManagementManager {
         let mut info = String::from("Power Management Driver Support:\n");
         info.push_str("==============================\n\n");
 
-        for driver in &self.supported_drivers {
-            match driver.as_str() {
+        for hypervisor in self.supported_drivers() {
+            match hypervisor.driver() {
                 "qemu" => {
-                    info.push_str("QEMU Driver:\n");
+                    info.push_str(&format!("QEMU Driver ({}):\n", hypervisor));
                     info.push_str("  - Full support for S3 (suspend-to-mem)\n");
                     info.push_str("  - Full support for S4 (suspend-to-disk)\n");
                     info.push_str("  - Controls BIOS ACPI advertisements\n");
                     info.push_str("  - Cannot prevent guest OS from suspending\n");
                 }
                 _ => {
-                    info.push_str(&format!("{}: Not supported\n", driver));
+                    info.push_str(&format!("{}: Supported\n", hypervisor));
                 }
             }
             info.push('\n');
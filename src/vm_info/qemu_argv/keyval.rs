@@ -0,0 +1,190 @@
+/// QEMU 风格的选项列表：`key=value` 以逗号分隔，`,,` 表示字面逗号。
+/// 不含 `=` 的项保存为值为 `None` 的键（例如 `-cpu` 的 `+vmx`）。
+#[derive(Debug, Clone, Default)]
+pub struct QemuOpts {
+    items: Vec<(String, Option<String>)>,
+}
+
+impl QemuOpts {
+    /// 解析选项字符串；若指定了 `implied_key`，第一项不含 `=` 时视为该键的值
+    pub fn parse(input: &str, implied_key: Option<&str>) -> Self {
+        let mut items = Vec::new();
+
+        for (index, item) in split_escaped(input).into_iter().enumerate() {
+            if item.is_empty() {
+                continue;
+            }
+
+            match item.split_once('=') {
+                Some((key, value)) => items.push((key.to_string(), Some(value.to_string()))),
+                None => match implied_key {
+                    Some(key) if index == 0 => items.push((key.to_string(), Some(item))),
+                    _ => items.push((item, None)),
+                },
+            }
+        }
+
+        Self { items }
+    }
+
+    /// 解析 `-device`、`-blockdev` 等参数：以 `{` 开头时按 JSON 解析，
+    /// 嵌套对象展开为点号分隔的键（与 QEMU keyval 的 `file.filename` 写法一致）
+    pub fn parse_any(input: &str, implied_key: Option<&str>) -> Result<Self, String> {
+        if !input.trim_start().starts_with('{') {
+            return Ok(Self::parse(input, implied_key));
+        }
+
+        let value: serde_json::Value = serde_json::from_str(input)
+            .map_err(|e| format!("Invalid JSON option '{}': {}", input, e))?;
+        let mut items = Vec::new();
+        flatten_json("", &value, &mut items);
+        Ok(Self { items })
+    }
+
+    /// 取出指定键的最后一个值
+    pub fn take(&mut self, key: &str) -> Option<String> {
+        let mut found = None;
+        self.items.retain(|(k, v)| {
+            if k == key {
+                found = Some(v.clone().unwrap_or_default());
+                false
+            } else {
+                true
+            }
+        });
+        found
+    }
+
+    /// 取出指定键的全部值（保持出现顺序）
+    pub fn take_all(&mut self, key: &str) -> Vec<String> {
+        let mut found = Vec::new();
+        self.items.retain(|(k, v)| {
+            if k == key {
+                found.push(v.clone().unwrap_or_default());
+                false
+            } else {
+                true
+            }
+        });
+        found
+    }
+
+    /// 取出开关型选项（on/yes/true 为真）
+    pub fn take_switch(&mut self, key: &str) -> Option<bool> {
+        self.take(key)
+            .map(|v| matches!(v.as_str(), "" | "on" | "yes" | "true"))
+    }
+
+    /// 取出全部剩余项
+    pub fn drain(&mut self) -> Vec<(String, Option<String>)> {
+        std::mem::take(&mut self.items)
+    }
+
+    /// 剩余未处理的键名
+    pub fn remaining_keys(&self) -> Vec<String> {
+        self.items.iter().map(|(k, _)| k.clone()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// 将 JSON 值展开为键值项，数组元素以下标作为键名
+fn flatten_json(
+    prefix: &str,
+    value: &serde_json::Value,
+    items: &mut Vec<(String, Option<String>)>,
+) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        }
+    };
+
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, value) in map {
+                flatten_json(&key(name), value, items);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                flatten_json(&key(&index.to_string()), value, items);
+            }
+        }
+        serde_json::Value::Null => {}
+        serde_json::Value::String(s) => items.push((prefix.to_string(), Some(s.clone()))),
+        other => items.push((prefix.to_string(), Some(other.to_string()))),
+    }
+}
+
+/// 按逗号切分，`,,` 还原为字面逗号
+fn split_escaped(input: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == ',' {
+            if chars.peek() == Some(&',') {
+                chars.next();
+                current.push(',');
+            } else {
+                parts.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    parts.push(current);
+
+    parts
+}
+
+/// 解析 QEMU 的容量参数（如 `2048`、`4G`、`512m`、`1.5G`），返回字节数。
+/// 后缀不区分大小写，小数部分按字节截断；无后缀时按 `default_multiplier` 计算。
+pub fn parse_size(value: &str, default_multiplier: u64) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split);
+    let invalid = || format!("Invalid size value: '{}'", value);
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() || (number.contains('.') && fraction.is_empty()) {
+        return Err(invalid());
+    }
+    let whole: u64 = whole.parse().map_err(|_| invalid())?;
+
+    let multiplier = match suffix.to_ascii_lowercase().as_str() {
+        "" => default_multiplier,
+        "b" => 1,
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        "t" => 1 << 40,
+        "p" => 1 << 50,
+        "e" => 1 << 60,
+        _ => return Err(format!("Invalid size suffix in '{}'", value)),
+    };
+
+    let too_large = || format!("Size value '{}' is too large", value);
+    let mut bytes = whole.checked_mul(multiplier).ok_or_else(too_large)?;
+    if !fraction.is_empty() {
+        // 与 QEMU 相同：字节单位不接受小数
+        if multiplier == 1 {
+            return Err(invalid());
+        }
+        let digits = &fraction[..fraction.len().min(18)];
+        let numerator: u128 = digits.parse().map_err(|_| invalid())?;
+        let denominator = 10u128.pow(digits.len() as u32);
+        let extra =
+            u64::try_from(numerator * multiplier as u128 / denominator).map_err(|_| too_large())?;
+        bytes = bytes.checked_add(extra).ok_or_else(too_large)?;
+    }
+    Ok(bytes)
+}
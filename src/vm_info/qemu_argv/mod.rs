@@ -0,0 +1,1173 @@
+mod keyval;
+
+use super::cpu::{CpuConfig, CpuFeature, CpuModel, CpuTopology, FeaturePolicy};
+use super::devices::{Disk, Driver, Interface, InterfaceSource, Source, Target};
+use super::memory::{CurrentMemory, MaxMemory};
+use super::memory_backing::MemoryAllocationMode;
+use super::os::{Boot, BootMenu, Loader, MachineType, Nvram, OsArch, Smbios};
+use super::sysinfo::{
+    BaseBoardInfo, BiosInfo, ChassisInfo, OemStringEntry, OemStringsInfo, SmbiosSysinfo, SystemInfo,
+};
+use super::{Domain, Hypervisor, LifecycleAction, Sysinfo, SysinfoEntry};
+use keyval::{QemuOpts, parse_size};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 不带参数的 QEMU 选项，包括已废弃但仍可能出现在旧命令行中的选项
+const FLAG_OPTIONS: &[&str] = &[
+    "enable-kvm",
+    "no-kvm",
+    "no-kvm-irqchip",
+    "no-kvm-pit",
+    "no-kvm-pit-reinjection",
+    "enable-nesting",
+    "nographic",
+    "no-hpet",
+    "no-acpi",
+    "no-reboot",
+    "no-shutdown",
+    "no-quit",
+    "S",
+    "s",
+    "daemonize",
+    "no-user-config",
+    "nodefaults",
+    "nodefconfig",
+    "snapshot",
+    "full-screen",
+    "no-frame",
+    "alt-grab",
+    "ctrl-grab",
+    "sdl",
+    "curses",
+    "portrait",
+    "no-fd-bootchk",
+    "win2k-hack",
+    "rtc-td-hack",
+    "localtime",
+    "tdf",
+    "old-param",
+    "semihosting",
+    "singlestep",
+    "enable-fips",
+    "enable-sync-profile",
+    "mem-prealloc",
+    "only-migratable",
+    "preconfig",
+    "xen-attach",
+    "xen-domid-restrict",
+    "usb",
+    "h",
+    "help",
+    "version",
+];
+
+/// 只影响 QEMU 进程管理、对域定义没有意义的选项，静默忽略
+const IGNORED_OPTIONS: &[&str] = &[
+    "pidfile",
+    "daemonize",
+    "S",
+    "no-user-config",
+    "nodefaults",
+    "nodefconfig",
+    "no-shutdown",
+    "no-quit",
+    "only-migratable",
+    "preconfig",
+    "h",
+    "help",
+    "version",
+    "msg",
+    "sandbox",
+    "runas",
+    "qmp",
+    "monitor",
+    "mon",
+    "D",
+];
+
+/// QEMU 命令行导入结果（对应 `virsh domxml-from-native qemu-argv`）
+#[derive(Debug)]
+pub struct QemuArgvImport {
+    /// 尽力转换得到的域配置
+    pub domain: Domain,
+    /// 无法转换或被忽略的选项
+    pub warnings: Vec<String>,
+}
+
+impl QemuArgvImport {
+    /// 从 argv 列表导入，第一个元素可以是 QEMU 可执行文件路径
+    pub fn from_argv<S: AsRef<str>>(argv: &[S]) -> Result<Self, Vec<String>> {
+        let args: Vec<&str> = argv.iter().map(|a| a.as_ref()).collect();
+        if args.is_empty() {
+            return Err(vec!["QEMU command line is empty".to_string()]);
+        }
+
+        let mut importer = ArgvImporter::new();
+        importer.run(&args)?;
+        Ok(importer.finish())
+    }
+
+    /// 从 `/proc/<pid>/cmdline` 格式（以 NUL 分隔）的内容导入
+    pub fn from_cmdline(cmdline: &[u8]) -> Result<Self, Vec<String>> {
+        let args: Vec<String> = cmdline
+            .split(|b| *b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+        Self::from_argv(&args)
+    }
+
+    /// 读取 `/proc/<pid>/cmdline` 文件（或其转储）并导入
+    pub fn from_proc_cmdline<P: AsRef<Path>>(path: P) -> Result<Self, Vec<String>> {
+        let path = path.as_ref();
+        let content = fs::read(path)
+            .map_err(|e| vec![format!("Failed to read {}: {}", path.display(), e)])?;
+        Self::from_cmdline(&content)
+    }
+}
+
+/// 等待 `-device` 引用的 `-drive if=none`
+struct PendingDrive {
+    id: String,
+    opts: QemuOpts,
+}
+
+/// `-blockdev` 定义的块节点，按 node-name 查找
+struct PendingBlockdev {
+    driver: String,
+    opts: QemuOpts,
+}
+
+/// 等待 `-device` 引用的 `-netdev`
+struct PendingNetdev {
+    backend: String,
+    opts: QemuOpts,
+}
+
+struct ArgvImporter {
+    domain: Domain,
+    warnings: Vec<String>,
+    accel_kvm: bool,
+    drives: Vec<PendingDrive>,
+    blockdevs: HashMap<String, PendingBlockdev>,
+    netdevs: HashMap<String, PendingNetdev>,
+    legacy_nics: Vec<QemuOpts>,
+    legacy_backends: Vec<QemuOpts>,
+    disk_names: HashMap<&'static str, u32>,
+    pflash_count: u32,
+    smbios: SmbiosSysinfo,
+    fwcfg_entries: Vec<SysinfoEntry>,
+}
+
+impl ArgvImporter {
+    fn new() -> Self {
+        Self {
+            domain: Domain::new("qemu", "unnamed"),
+            warnings: Vec::new(),
+            accel_kvm: false,
+            drives: Vec::new(),
+            blockdevs: HashMap::new(),
+            netdevs: HashMap::new(),
+            legacy_nics: Vec::new(),
+            legacy_backends: Vec::new(),
+            disk_names: HashMap::new(),
+            pflash_count: 0,
+            smbios: SmbiosSysinfo::new(),
+            fwcfg_entries: Vec::new(),
+        }
+    }
+
+    fn run(&mut self, args: &[&str]) -> Result<(), Vec<String>> {
+        let mut index = 0;
+
+        // 第一个参数不是选项时视为可执行文件
+        if !args[0].starts_with('-') {
+            self.parse_binary(args[0]);
+            index = 1;
+        }
+
+        let mut errors = Vec::new();
+        while index < args.len() {
+            let arg = args[index];
+            index += 1;
+
+            let Some(option) = arg.strip_prefix("--").or_else(|| arg.strip_prefix('-')) else {
+                self.warnings
+                    .push(format!("Ignoring stray argument '{}'", arg));
+                continue;
+            };
+
+            if FLAG_OPTIONS.contains(&option) {
+                self.handle_flag(option);
+                continue;
+            }
+
+            let Some(value) = args.get(index) else {
+                errors.push(format!("Option -{} requires an argument", option));
+                break;
+            };
+            // 以 `-` 开头的参数不会是选项值，按下一个选项处理
+            if value.starts_with('-') {
+                self.warnings.push(format!(
+                    "Option -{} is missing its argument, '{}' is treated as the next option",
+                    option, value
+                ));
+                continue;
+            }
+            index += 1;
+
+            if let Err(e) = self.handle_option(option, value) {
+                errors.push(e);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn parse_binary(&mut self, binary: &str) {
+        let name = binary.rsplit('/').next().unwrap_or(binary);
+
+        if let Some(arch) = name.strip_prefix("qemu-system-") {
+            self.domain.os.os_type.arch = Some(OsArch::from(arch));
+        } else if name == "qemu-kvm" {
+            self.domain.os.os_type.arch = Some(OsArch::X86_64);
+            self.accel_kvm = true;
+        } else {
+            self.warnings.push(format!(
+                "Unrecognized emulator binary '{}', architecture not set",
+                binary
+            ));
+        }
+    }
+
+    fn handle_flag(&mut self, flag: &str) {
+        match flag {
+            "enable-kvm" => self.accel_kvm = true,
+            "no-kvm" => self.accel_kvm = false,
+            "no-reboot" => self.domain.on_reboot = Some(LifecycleAction::Destroy),
+            "mem-prealloc" => {
+                let backing = self
+                    .domain
+                    .memory_backing
+                    .take()
+                    .unwrap_or_default()
+                    .with_allocation(MemoryAllocationMode::Immediate, None);
+                self.domain.memory_backing = Some(backing);
+            }
+            _ if IGNORED_OPTIONS.contains(&flag) => {}
+            _ => self
+                .warnings
+                .push(format!("Option -{} is not translated", flag)),
+        }
+    }
+
+    fn handle_option(&mut self, option: &str, value: &str) -> Result<(), String> {
+        match option {
+            "name" => self.parse_name(value),
+            "uuid" => self.domain.uuid = Some(value.to_string()),
+            "m" => self.parse_memory(value)?,
+            "smp" => self.parse_smp(value)?,
+            "cpu" => self.parse_cpu(value),
+            "machine" | "M" => self.parse_machine(value),
+            "accel" => {
+                let mut opts = QemuOpts::parse(value, Some("accel"));
+                if opts.take("accel").as_deref() == Some("kvm") {
+                    self.accel_kvm = true;
+                }
+                self.warn_leftover(&format!("-accel {}", value), &opts);
+            }
+            "drive" => self.parse_drive(value),
+            "blockdev" => self.parse_blockdev(value),
+            "hda" | "hdb" | "hdc" | "hdd" => {
+                let index = (option.as_bytes()[2] - b'a') as u32;
+                self.add_disk(
+                    "disk",
+                    "ide",
+                    Some(index),
+                    value,
+                    None,
+                    &format!("-{}", option),
+                );
+            }
+            "cdrom" => self.add_disk("cdrom", "ide", Some(2), value, Some("raw"), "-cdrom"),
+            "device" => self.parse_device(value),
+            "netdev" => self.parse_netdev(value),
+            "nic" => self.parse_nic(value),
+            "net" => {
+                let opts = QemuOpts::parse(value, Some("type"));
+                if value.starts_with("nic") {
+                    self.legacy_nics.push(opts);
+                } else {
+                    self.legacy_backends.push(opts);
+                }
+            }
+            "smbios" => self.parse_smbios(value),
+            "fw_cfg" => self.parse_fw_cfg(value),
+            "boot" => self.parse_boot(value),
+            "bios" => {
+                self.domain.os.loader = Some(Loader {
+                    readonly: None,
+                    secure: None,
+                    stateless: None,
+                    loader_type: Some("rom".to_string()),
+                    path: Some(value.to_string()),
+                });
+            }
+            "kernel" => self.domain.os.kernel = Some(value.to_string()),
+            "initrd" => self.domain.os.initrd = Some(value.to_string()),
+            "append" => self.domain.os.cmdline = Some(value.to_string()),
+            "dtb" => self.domain.os.dtb = Some(value.to_string()),
+            _ if IGNORED_OPTIONS.contains(&option) => {}
+            _ => self
+                .warnings
+                .push(format!("Option -{} {} is not translated", option, value)),
+        }
+
+        Ok(())
+    }
+
+    fn parse_name(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, Some("guest"));
+        if let Some(name) = opts.take("guest") {
+            self.domain.name = name;
+        }
+        opts.take("process");
+        opts.take("debug-threads");
+        self.warn_leftover(&format!("-name {}", value), &opts);
+    }
+
+    fn parse_memory(&mut self, value: &str) -> Result<(), String> {
+        let mut opts = QemuOpts::parse(value, Some("size"));
+
+        if let Some(size) = opts.take("size") {
+            let kib = parse_size(&size, 1024 * 1024)? / 1024;
+            self.domain.memory.value = kib;
+            self.domain.memory.unit = "KiB".to_string();
+            self.domain.current_memory = Some(CurrentMemory {
+                unit: "KiB".to_string(),
+                value: kib,
+            });
+        }
+
+        if let Some(maxmem) = opts.take("maxmem") {
+            let slots = match opts.take("slots") {
+                Some(slots) => slots
+                    .parse()
+                    .map_err(|_| format!("Invalid memory slots value: '{}'", slots))?,
+                None => 0,
+            };
+            self.domain.max_memory = Some(MaxMemory {
+                slots,
+                unit: "KiB".to_string(),
+                value: parse_size(&maxmem, 1)? / 1024,
+            });
+        }
+
+        self.warn_leftover(&format!("-m {}", value), &opts);
+        Ok(())
+    }
+
+    fn parse_smp(&mut self, value: &str) -> Result<(), String> {
+        let mut opts = QemuOpts::parse(value, Some("cpus"));
+
+        // 拓扑参数为 0 会导致除零，统一拒绝
+        let mut take_u32 = |key: &str| -> Result<Option<u32>, String> {
+            opts.take(key)
+                .map(|v| match v.parse::<u32>() {
+                    Ok(0) => Err(format!("-smp {} must be greater than 0", key)),
+                    Ok(n) => Ok(n),
+                    Err(_) => Err(format!("Invalid -smp {} value: '{}'", key, v)),
+                })
+                .transpose()
+        };
+
+        let cpus = take_u32("cpus")?;
+        let maxcpus = take_u32("maxcpus")?;
+        let sockets = take_u32("sockets")?;
+        let dies = take_u32("dies")?;
+        let clusters = take_u32("clusters")?;
+        let cores = take_u32("cores")?;
+        let threads = take_u32("threads")?;
+
+        let has_topology = sockets.is_some() || cores.is_some() || threads.is_some();
+        let dies_v = dies.unwrap_or(1);
+        let clusters_v = clusters.unwrap_or(1);
+        let threads_v = threads.unwrap_or(1);
+
+        let product = |values: &[u32]| {
+            values
+                .iter()
+                .try_fold(1u32, |acc, v| acc.checked_mul(*v))
+                .ok_or_else(|| format!("-smp topology in '{}' overflows", value))
+        };
+
+        // 与 QEMU 相同：未给出 cpus/maxcpus 时由拓扑乘积推导，缺省的层级优先补到 cores
+        let explicit = product(&[
+            sockets.unwrap_or(1),
+            dies_v,
+            clusters_v,
+            cores.unwrap_or(1),
+            threads_v,
+        ])?;
+        let max = maxcpus.or(cpus).unwrap_or(explicit);
+        let (sockets_v, cores_v) = match (sockets, cores) {
+            (Some(s), Some(c)) => (s, c),
+            (Some(s), None) => (
+                s,
+                (max / product(&[s, dies_v, clusters_v, threads_v])?).max(1),
+            ),
+            (None, Some(c)) => (
+                (max / product(&[c, dies_v, clusters_v, threads_v])?).max(1),
+                c,
+            ),
+            (None, None) => (1, (max / product(&[dies_v, clusters_v, threads_v])?).max(1)),
+        };
+
+        let current = cpus.unwrap_or(max);
+        self.domain.vcpu.vcpu_count = max;
+        self.domain.vcpu.current = (current < max).then_some(current);
+
+        if has_topology {
+            let mut topology = CpuTopology::new(sockets_v, cores_v, threads_v);
+            if let Some(dies) = dies {
+                topology = topology.with_dies(dies);
+            }
+            if let Some(clusters) = clusters {
+                topology = topology.with_clusters(clusters);
+            }
+
+            if topology.total_vcpus() != max {
+                self.warnings.push(format!(
+                    "-smp topology provides {} vCPUs but maxcpus is {}",
+                    topology.total_vcpus(),
+                    max
+                ));
+            }
+
+            let cpu = self.domain.cpu.take().unwrap_or_default();
+            self.domain.cpu = Some(cpu.with_topology(topology));
+        }
+
+        Ok(())
+    }
+
+    fn parse_cpu(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, Some("model"));
+        let model = opts.take("model").unwrap_or_default();
+
+        let mut cpu = match model.as_str() {
+            "host" => CpuConfig::host_passthrough(),
+            "max" => CpuConfig::maximum(),
+            _ => CpuConfig::custom()
+                .with_match_mode(super::cpu::CpuMatch::Exact)
+                .with_model(CpuModel::new(&model)),
+        };
+
+        // 保留之前由 -smp 设置的拓扑
+        if let Some(topology) = self.domain.cpu.take().and_then(|c| c.topology) {
+            cpu = cpu.with_topology(topology);
+        }
+
+        if let Some(migratable) = opts.take_switch("migratable") {
+            cpu = cpu.with_migratable(migratable);
+        }
+
+        for (key, value) in opts.drain() {
+            let (name, policy) = match (key.as_str(), value.as_deref()) {
+                (k, None) if k.starts_with('+') => (&k[1..], FeaturePolicy::Require),
+                (k, None) if k.starts_with('-') => (&k[1..], FeaturePolicy::Disable),
+                (k, None) => (k, FeaturePolicy::Require),
+                (k, Some("on" | "true")) => (k, FeaturePolicy::Require),
+                (k, Some("off" | "false")) => (k, FeaturePolicy::Disable),
+                _ => {
+                    self.warnings.push(format!(
+                        "CPU property '{}' is not translated",
+                        value.map_or(key.clone(), |v| format!("{}={}", key, v))
+                    ));
+                    continue;
+                }
+            };
+
+            if name.starts_with("hv_") || name.starts_with("hv-") || name == "kvm" {
+                self.warnings.push(format!(
+                    "CPU flag '{}' maps to <features> and is not translated",
+                    name
+                ));
+                continue;
+            }
+
+            cpu = cpu.add_feature(CpuFeature::new(name, policy));
+        }
+
+        self.domain.cpu = Some(cpu);
+    }
+
+    fn parse_machine(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, Some("type"));
+
+        if let Some(machine) = opts.take("type") {
+            self.domain.os.os_type.machine = Some(MachineType::from(machine));
+        }
+
+        if let Some(accel) = opts.take("accel") {
+            self.accel_kvm = accel.split(':').next() == Some("kvm");
+        }
+
+        self.warn_leftover(&format!("-machine {}", value), &opts);
+    }
+
+    fn parse_drive(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, None);
+        let interface = opts.take("if").unwrap_or_else(|| "ide".to_string());
+
+        match interface.as_str() {
+            "none" => match opts.take("id") {
+                Some(id) => self.drives.push(PendingDrive { id, opts }),
+                None => self
+                    .warnings
+                    .push(format!("-drive {} has if=none but no id", value)),
+            },
+            "pflash" => self.add_pflash(opts, value),
+            "ide" | "virtio" | "scsi" | "floppy" | "sd" => {
+                let bus = match interface.as_str() {
+                    "floppy" => "fdc",
+                    other => other,
+                };
+                let index = opts.take("index").and_then(|i| i.parse().ok());
+                self.add_drive(opts, bus, None, index, &format!("-drive {}", value));
+            }
+            other => self.warnings.push(format!(
+                "-drive {}: interface '{}' is not translated",
+                value, other
+            )),
+        }
+    }
+
+    fn add_pflash(&mut self, mut opts: QemuOpts, context: &str) {
+        let Some(file) = opts.take("file") else {
+            self.warnings
+                .push(format!("-drive {}: pflash without file", context));
+            return;
+        };
+        let readonly = opts.take_switch("readonly").unwrap_or(false);
+        opts.take("format");
+        opts.take("unit");
+
+        if self.pflash_count == 0 {
+            self.domain.os.loader = Some(Loader {
+                readonly: Some(if readonly { "yes" } else { "no" }.to_string()),
+                secure: None,
+                stateless: None,
+                loader_type: Some("pflash".to_string()),
+                path: Some(file),
+            });
+        } else {
+            self.domain.os.nvram = Some(Nvram::with_path(&file, None));
+        }
+        self.pflash_count += 1;
+
+        self.warn_leftover(&format!("-drive {}", context), &opts);
+    }
+
+    /// 从 -drive 选项创建磁盘；`device_override` 来自 -device（如 ide-cd）
+    fn add_drive(
+        &mut self,
+        mut opts: QemuOpts,
+        bus: &str,
+        device_override: Option<&str>,
+        index: Option<u32>,
+        context: &str,
+    ) {
+        let device = device_override
+            .map(str::to_string)
+            .or_else(|| opts.take("media"))
+            .unwrap_or_else(|| {
+                if bus == "fdc" {
+                    "floppy".to_string()
+                } else {
+                    "disk".to_string()
+                }
+            });
+        let format = opts.take("format");
+
+        let Some(file) = opts.take("file") else {
+            self.warnings
+                .push(format!("{}: empty drive is not translated", context));
+            return;
+        };
+
+        // 这些属性由 QEMU 使用，目前的磁盘模型无法表达
+        opts.take("id");
+        opts.take("bus");
+        opts.take("unit");
+        self.warn_leftover(context, &opts);
+
+        self.add_disk(&device, bus, index, &file, format.as_deref(), context);
+    }
+
+    fn add_disk(
+        &mut self,
+        device: &str,
+        bus: &str,
+        index: Option<u32>,
+        file: &str,
+        format: Option<&str>,
+        context: &str,
+    ) {
+        let prefix = match bus {
+            "virtio" => "vd",
+            "ide" => "hd",
+            "fdc" => "fd",
+            _ => "sd",
+        };
+
+        let dev = match index {
+            Some(index) => disk_name(prefix, index),
+            None => {
+                let mut next = self.disk_names.get(prefix).copied().unwrap_or(0);
+                let mut candidate = disk_name(prefix, next);
+                while self.has_disk_target(&candidate) {
+                    next += 1;
+                    candidate = disk_name(prefix, next);
+                }
+                self.disk_names.insert(prefix, next + 1);
+                candidate
+            }
+        };
+
+        if self.has_disk_target(&dev) {
+            self.warnings
+                .push(format!("{}: duplicate disk target '{}'", context, dev));
+            return;
+        }
+
+        let disk_type = if file.starts_with("/dev/") {
+            "block"
+        } else {
+            "file"
+        };
+
+        self.domain
+            .devices
+            .disk
+            .get_or_insert_with(Vec::new)
+            .push(Disk {
+                disk_type: disk_type.to_string(),
+                device: device.to_string(),
+                driver: Driver::new("qemu", format.unwrap_or("raw")),
+                source: Source::from_path(file),
+                target: Target {
+                    dev,
+                    bus: bus.to_string(),
+                },
+                boot: None,
+                alias: None,
+                address: None,
+            });
+    }
+
+    fn has_disk_target(&self, dev: &str) -> bool {
+        self.domain
+            .devices
+            .disk
+            .as_ref()
+            .is_some_and(|disks| disks.iter().any(|d| d.target.dev == dev))
+    }
+
+    fn parse_device(&mut self, value: &str) {
+        let mut opts = match QemuOpts::parse_any(value, Some("driver")) {
+            Ok(opts) => opts,
+            Err(e) => {
+                self.warnings.push(format!("-device: {}", e));
+                return;
+            }
+        };
+        let driver = opts.take("driver").unwrap_or_default();
+
+        let disk_bus = match driver.as_str() {
+            "virtio-blk-pci" | "virtio-blk" | "virtio-blk-device" | "virtio-blk-ccw" => {
+                Some(("virtio", None))
+            }
+            "scsi-hd" | "scsi-disk" | "scsi-block" => Some(("scsi", None)),
+            "scsi-cd" => Some(("scsi", Some("cdrom"))),
+            "ide-hd" | "ide-drive" => Some(("ide", None)),
+            "ide-cd" => Some(("ide", Some("cdrom"))),
+            "usb-storage" => Some(("usb", None)),
+            _ => None,
+        };
+
+        if let Some((bus, device)) = disk_bus {
+            let Some(drive_id) = opts.take("drive") else {
+                self.warnings
+                    .push(format!("-device {}: no drive property", value));
+                return;
+            };
+            if let Some(position) = self.drives.iter().position(|d| d.id == drive_id) {
+                let drive = self.drives.remove(position);
+                let context = format!("-drive id={}", drive.id);
+                self.add_drive(drive.opts, bus, device, None, &context);
+            } else if self.blockdevs.contains_key(&drive_id) {
+                let context = format!("-blockdev node-name={}", drive_id);
+                if let Some(opts) = self.resolve_blockdev(&drive_id, &context) {
+                    self.add_drive(opts, bus, device, None, &context);
+                }
+            } else {
+                self.warnings.push(format!(
+                    "-device {}: drive '{}' is not defined",
+                    value, drive_id
+                ));
+                return;
+            }
+
+            self.warn_leftover_device(&driver, &mut opts);
+            return;
+        }
+
+        if let Some(model) = nic_model(&driver) {
+            let Some(netdev_id) = opts.take("netdev") else {
+                self.warnings
+                    .push(format!("-device {}: no netdev property", value));
+                return;
+            };
+            let Some(netdev) = self.netdevs.remove(&netdev_id) else {
+                self.warnings.push(format!(
+                    "-device {}: netdev '{}' is not defined",
+                    value, netdev_id
+                ));
+                return;
+            };
+
+            let mac = opts.take("mac");
+            self.add_interface(netdev.backend, netdev.opts, model, mac.as_deref(), value);
+            self.warn_leftover_device(&driver, &mut opts);
+            return;
+        }
+
+        self.warnings
+            .push(format!("Device -device {} is not translated", value));
+    }
+
+    fn parse_blockdev(&mut self, value: &str) {
+        let mut opts = match QemuOpts::parse_any(value, None) {
+            Ok(opts) => opts,
+            Err(e) => {
+                self.warnings.push(format!("-blockdev: {}", e));
+                return;
+            }
+        };
+
+        let (Some(node_name), Some(driver)) = (opts.take("node-name"), opts.take("driver")) else {
+            self.warnings
+                .push(format!("-blockdev {} needs node-name and driver", value));
+            return;
+        };
+        self.blockdevs
+            .insert(node_name, PendingBlockdev { driver, opts });
+    }
+
+    /// 沿 format 节点到 protocol 节点的链解析出镜像路径与格式，
+    /// 返回可交给 [`Self::add_drive`] 的 `file=`/`format=` 选项
+    fn resolve_blockdev(&mut self, node_name: &str, context: &str) -> Option<QemuOpts> {
+        let PendingBlockdev { driver, mut opts } = self.blockdevs.remove(node_name)?;
+
+        // 缓存、锁、只读等属性由 libvirt 根据 <driver> 重新生成
+        for key in [
+            "auto-read-only",
+            "read-only",
+            "discard",
+            "detect-zeroes",
+            "locking",
+            "cache.direct",
+            "cache.no-flush",
+        ] {
+            opts.take(key);
+        }
+
+        let (file, format) = match driver.as_str() {
+            "file" | "host_device" | "host_cdrom" => {
+                let Some(filename) = opts.take("filename") else {
+                    self.warnings
+                        .push(format!("{}: protocol node without filename", context));
+                    return None;
+                };
+                (filename, None)
+            }
+            "raw" | "qcow2" | "qed" | "vmdk" | "vdi" | "vpc" | "vhdx" => {
+                let file = match opts.take("file") {
+                    // 引用另一个 -blockdev 节点
+                    Some(child) => {
+                        let context = format!("-blockdev node-name={}", child);
+                        let mut child_opts = self.resolve_blockdev(&child, &context)?;
+                        child_opts.take("file")
+                    }
+                    // 内联的 file.driver=file,file.filename=...
+                    None => {
+                        opts.take("file.driver");
+                        opts.take("file.filename")
+                    }
+                };
+                let Some(file) = file else {
+                    self.warnings
+                        .push(format!("{}: format node without a file", context));
+                    return None;
+                };
+                (file, Some(driver))
+            }
+            other => {
+                self.warnings.push(format!(
+                    "{}: block driver '{}' is not translated",
+                    context, other
+                ));
+                return None;
+            }
+        };
+
+        self.warn_leftover(context, &opts);
+
+        let mut escaped = format!("file={}", file.replace(',', ",,"));
+        if let Some(format) = format {
+            escaped.push_str(&format!(",format={}", format));
+        }
+        Some(QemuOpts::parse(&escaped, None))
+    }
+
+    fn warn_leftover_device(&mut self, driver: &str, opts: &mut QemuOpts) {
+        // 总线地址由 libvirt 自动分配
+        opts.take("id");
+        opts.take("bus");
+        opts.take("addr");
+        self.warn_leftover(&format!("-device {}", driver), opts);
+    }
+
+    fn parse_netdev(&mut self, value: &str) {
+        let mut opts = match QemuOpts::parse_any(value, Some("type")) {
+            Ok(opts) => opts,
+            Err(e) => {
+                self.warnings.push(format!("-netdev: {}", e));
+                return;
+            }
+        };
+        let backend = opts.take("type").unwrap_or_default();
+
+        match opts.take("id") {
+            Some(id) => {
+                self.netdevs.insert(id, PendingNetdev { backend, opts });
+            }
+            None => self.warnings.push(format!("-netdev {} has no id", value)),
+        }
+    }
+
+    fn parse_nic(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, Some("type"));
+        let backend = opts.take("type").unwrap_or_else(|| "user".to_string());
+        if backend == "none" {
+            return;
+        }
+
+        let driver = opts
+            .take("model")
+            .unwrap_or_else(|| "virtio-net-pci".to_string());
+        let model = nic_model(&driver).unwrap_or(driver.as_str()).to_string();
+        let mac = opts.take("mac");
+        opts.take("id");
+
+        self.add_interface(backend, opts, &model, mac.as_deref(), value);
+    }
+
+    fn add_interface(
+        &mut self,
+        backend: String,
+        mut opts: QemuOpts,
+        model: &str,
+        mac: Option<&str>,
+        context: &str,
+    ) {
+        let mut interface = match backend.as_str() {
+            "user" => Interface::new("user"),
+            "bridge" => Interface::new("bridge").with_source(InterfaceSource {
+                bridge: Some(opts.take("br").unwrap_or_else(|| "br0".to_string())),
+                ..Default::default()
+            }),
+            "tap" => {
+                let mut interface = Interface::new("ethernet");
+                if let Some(ifname) = opts.take("ifname") {
+                    interface = interface.with_target(&ifname);
+                }
+                // 由 libvirt 负责创建 tap 设备
+                opts.take("script");
+                opts.take("downscript");
+                opts.take("vhost");
+                opts.take("fd");
+                opts.take("vhostfd");
+                interface
+            }
+            other => {
+                self.warnings.push(format!(
+                    "Network backend '{}' ({}) is not translated",
+                    other, context
+                ));
+                return;
+            }
+        };
+
+        interface = interface.with_model(model);
+        if let Some(mac) = mac {
+            interface = interface.with_mac(mac);
+        }
+        self.warn_leftover(&format!("network backend {}", backend), &opts);
+
+        self.domain
+            .devices
+            .interfaces
+            .get_or_insert_with(Vec::new)
+            .push(interface);
+    }
+
+    fn parse_smbios(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, None);
+
+        let entries = |opts: &mut QemuOpts, keys: &[&str]| -> Vec<SysinfoEntry> {
+            keys.iter()
+                .filter_map(|key| {
+                    opts.take(key).map(|value| SysinfoEntry {
+                        name: key.to_string(),
+                        file: None,
+                        value: Some(value),
+                    })
+                })
+                .collect()
+        };
+
+        match opts.take("type").as_deref() {
+            Some("0") => {
+                let entries = entries(&mut opts, &["vendor", "version", "date", "release"]);
+                self.smbios.bios = Some(BiosInfo { entries });
+            }
+            Some("1") => {
+                let entries = entries(
+                    &mut opts,
+                    &[
+                        "manufacturer",
+                        "product",
+                        "version",
+                        "serial",
+                        "uuid",
+                        "sku",
+                        "family",
+                    ],
+                );
+                self.smbios.system = Some(SystemInfo { entries });
+            }
+            Some("2") => {
+                let entries = entries(
+                    &mut opts,
+                    &[
+                        "manufacturer",
+                        "product",
+                        "version",
+                        "serial",
+                        "asset",
+                        "location",
+                    ],
+                );
+                self.smbios.base_boards.push(BaseBoardInfo { entries });
+            }
+            Some("3") => {
+                let entries = entries(
+                    &mut opts,
+                    &["manufacturer", "version", "serial", "asset", "sku"],
+                );
+                self.smbios.chassis = Some(ChassisInfo { entries });
+            }
+            Some("11") => {
+                let oem = self
+                    .smbios
+                    .oem_strings
+                    .get_or_insert_with(OemStringsInfo::default);
+                oem.entries.extend(
+                    opts.take_all("value")
+                        .into_iter()
+                        .map(|value| OemStringEntry { value }),
+                );
+            }
+            _ => {
+                self.warnings
+                    .push(format!("-smbios {} is not translated", value));
+                return;
+            }
+        }
+
+        self.warn_leftover(&format!("-smbios {}", value), &opts);
+    }
+
+    fn parse_fw_cfg(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, Some("name"));
+        let Some(name) = opts.take("name") else {
+            self.warnings.push(format!("-fw_cfg {} has no name", value));
+            return;
+        };
+
+        let entry = match (opts.take("file"), opts.take("string")) {
+            (Some(file), None) => SysinfoEntry {
+                name,
+                file: Some(file),
+                value: None,
+            },
+            (None, Some(string)) => SysinfoEntry {
+                name,
+                file: None,
+                value: Some(string),
+            },
+            _ => {
+                self.warnings.push(format!(
+                    "-fw_cfg {} must have exactly one of file or string",
+                    value
+                ));
+                return;
+            }
+        };
+
+        self.fwcfg_entries.push(entry);
+        self.warn_leftover(&format!("-fw_cfg {}", value), &opts);
+    }
+
+    fn parse_boot(&mut self, value: &str) {
+        let mut opts = QemuOpts::parse(value, Some("order"));
+
+        if let Some(order) = opts.take("order") {
+            for letter in order.chars() {
+                let dev = match letter {
+                    'a' | 'b' => "fd",
+                    'c' => "hd",
+                    'd' => "cdrom",
+                    'n' => "network",
+                    other => {
+                        self.warnings
+                            .push(format!("Unknown boot device '{}' in -boot", other));
+                        continue;
+                    }
+                };
+                self.domain.os.boots.push(Boot {
+                    dev: dev.to_string(),
+                });
+            }
+        }
+
+        if let Some(menu) = opts.take_switch("menu") {
+            self.domain.os.bootmenu = Some(BootMenu {
+                enable: if menu { "yes" } else { "no" }.to_string(),
+                timeout: opts.take("splash-time"),
+            });
+        }
+
+        self.warn_leftover(&format!("-boot {}", value), &opts);
+    }
+
+    fn warn_leftover(&mut self, context: &str, opts: &QemuOpts) {
+        if !opts.is_empty() {
+            self.warnings.push(format!(
+                "{}: ignored properties {}",
+                context,
+                opts.remaining_keys().join(", ")
+            ));
+        }
+    }
+
+    fn finish(mut self) -> QemuArgvImport {
+        // 旧式 -net nic 与后端按出现顺序配对
+        let backends = std::mem::take(&mut self.legacy_backends);
+        let mut backends = backends.into_iter();
+        for mut nic in std::mem::take(&mut self.legacy_nics) {
+            nic.take("type");
+            let driver = nic.take("model").unwrap_or_else(|| "e1000".to_string());
+            let model = nic_model(&driver).unwrap_or(driver.as_str()).to_string();
+            let mac = nic.take("macaddr");
+            let (backend, opts) = match backends.next() {
+                Some(mut opts) => (opts.take("type").unwrap_or_default(), opts),
+                None => ("user".to_string(), QemuOpts::default()),
+            };
+            self.add_interface(backend, opts, &model, mac.as_deref(), "-net nic");
+        }
+
+        for drive in std::mem::take(&mut self.drives) {
+            self.warnings.push(format!(
+                "-drive id={} is not attached to any device",
+                drive.id
+            ));
+        }
+
+        let mut unused: Vec<String> = self.blockdevs.keys().cloned().collect();
+        unused.sort();
+        for node_name in unused {
+            self.warnings.push(format!(
+                "-blockdev node-name={} is not attached to any device",
+                node_name
+            ));
+        }
+
+        let mut unused: Vec<String> = self.netdevs.keys().cloned().collect();
+        unused.sort();
+        for id in unused {
+            self.warnings
+                .push(format!("-netdev id={} is not attached to any device", id));
+        }
+
+        let smbios = std::mem::take(&mut self.smbios);
+
+        let mut sysinfo = Vec::new();
+        if !smbios.is_empty() {
+            sysinfo.push(Sysinfo::Smbios(smbios));
+            self.domain.os.smbios = Some(Smbios {
+                mode: "sysinfo".to_string(),
+            });
+        }
+        if !self.fwcfg_entries.is_empty() {
+            sysinfo.push(Sysinfo::fwcfg(std::mem::take(&mut self.fwcfg_entries)));
+        }
+        if !sysinfo.is_empty() {
+            self.domain.sysinfo = Some(sysinfo);
+        }
+
+        if self.accel_kvm {
+            self.domain.domain_type = Hypervisor::Kvm;
+        }
+
+        QemuArgvImport {
+            domain: self.domain,
+            warnings: self.warnings,
+        }
+    }
+}
+
+/// 将 QEMU 网卡设备名映射为 libvirt 的 model type
+fn nic_model(driver: &str) -> Option<&str> {
+    match driver {
+        "virtio-net-pci" | "virtio-net" | "virtio-net-device" | "virtio-net-ccw" | "virtio" => {
+            Some("virtio")
+        }
+        "e1000" | "e1000e" | "rtl8139" | "vmxnet3" | "ne2k_pci" | "pcnet" | "i82559er" | "igb" => {
+            Some(driver)
+        }
+        _ => None,
+    }
+}
+
+/// 生成磁盘目标名：0 -> "a"，25 -> "z"，26 -> "aa"
+fn disk_name(prefix: &str, index: u32) -> String {
+    let mut suffix = Vec::new();
+    let mut n = index as i64;
+    loop {
+        suffix.push((b'a' + (n % 26) as u8) as char);
+        n = n / 26 - 1;
+        if n < 0 {
+            break;
+        }
+    }
+    format!("{}{}", prefix, suffix.iter().rev().collect::<String>())
+}
@@ -6,6 +6,7 @@ mod resource_manager;
 
 use fibrechannel::FibreChannelConfig;
 use partition::PartitionConfig;
+pub use resource_manager::ResourceManager;
 
 /// 资源分区配置
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
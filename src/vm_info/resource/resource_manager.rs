@@ -1,6 +1,7 @@
 use crate::vm_info::resource::ResourceConfig;
 use crate::vm_info::resource::fibrechannel::FibreChannelConfig;
 use crate::vm_info::resource::partition::PartitionPath;
+use crate::vm_info::{CapabilityRegistry, Hypervisor};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -11,8 +12,8 @@ pub struct ResourceManager {
     known_partitions: Vec<PartitionPath>,
     /// 默认分区
     default_partition: PartitionPath,
-    /// 支持的驱动
-    supported_drivers: Vec<String>,
+    /// 域类型能力表
+    registry: CapabilityRegistry,
 }
 
 impl ResourceManager {
@@ -21,10 +22,26 @@ impl ResourceManager {
         Self {
             known_partitions: vec![PartitionPath::new("/")],
             default_partition: PartitionPath::new("/"),
-            supported_drivers: vec!["qemu".to_string(), "lxc".to_string()],
+            registry: CapabilityRegistry::default(),
         }
     }
 
+    /// 使用自定义的能力表
+    pub fn with_registry(mut self, registry: CapabilityRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    // 支持资源分区的域类型
+    fn supported_drivers(&self) -> Vec<&str> {
+        self.registry
+            .hypervisors()
+            .into_iter()
+            .filter(|h| self.registry.get(h).is_some_and(|c| c.resource_partition))
+            .map(Hypervisor::as_str)
+            .collect()
+    }
+
     /// 设置默认分区
     pub fn with_default_partition<P: AsRef<Path>>(mut self, path: P) -> Self {
         let partition = PartitionPath::new(path).normalize();
@@ -66,17 +83,13 @@ impl ResourceManager {
     pub fn validate_domain_config(
         &self,
         resource: &ResourceConfig,
-        driver: &str,
+        hypervisor: &Hypervisor,
     ) -> Result<(), Vec<String>> {
         let mut errors = Vec::new();
 
         // 检查驱动支持
-        if !self.supported_drivers.iter().any(|d| d == driver) {
-            errors.push(format!(
-                "Driver '{}' does not support resource partitioning. Supported drivers: {}",
-                driver,
-                self.supported_drivers.join(", ")
-            ));
+        if let Err(mut e) = self.registry.validate_resource(hypervisor, resource) {
+            errors.append(&mut e);
         }
 
         // 验证分区配置
@@ -200,7 +213,7 @@ impl ResourceManager {
         ));
         summary.push_str(&format!(
             "  Supported drivers: {}\n",
-            self.supported_drivers.join(", ")
+            self.supported_drivers().join(", ")
         ));
 
         if !self.known_partitions.is_empty() {
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+// s390 加密密钥包装 <keywrap><cipher name='aes' state='off'/></keywrap>
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyWrap {
+    #[serde(rename = "cipher", default)]
+    pub ciphers: Vec<KeyWrapCipher>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyWrapCipher {
+    /// aes 或 dea
+    #[serde(rename = "@name")]
+    pub name: String,
+    /// on 或 off
+    #[serde(rename = "@state")]
+    pub state: String,
+}
+
+impl KeyWrap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cipher(mut self, name: &str, on: bool) -> Self {
+        self.ciphers.retain(|c| c.name != name);
+        self.ciphers.push(KeyWrapCipher {
+            name: name.to_string(),
+            state: if on { "on" } else { "off" }.to_string(),
+        });
+        self
+    }
+
+    /// `arch` 为 `<os><type arch>`，未知时不检查体系结构
+    pub fn validate(&self, arch: Option<&str>) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if let Some(arch) = arch
+            && arch != "s390x"
+        {
+            errors.push(format!(
+                "<keywrap> is only supported on s390x, not '{}'",
+                arch
+            ));
+        }
+        if self.ciphers.is_empty() {
+            errors.push("<keywrap> must contain at least one <cipher>".to_string());
+        }
+
+        let mut seen = BTreeSet::new();
+        for cipher in &self.ciphers {
+            if cipher.name != "aes" && cipher.name != "dea" {
+                errors.push(format!(
+                    "Unknown keywrap cipher '{}', expected 'aes' or 'dea'",
+                    cipher.name
+                ));
+            }
+            if cipher.state != "on" && cipher.state != "off" {
+                errors.push(format!(
+                    "Keywrap cipher '{}' state must be 'on' or 'off', got '{}'",
+                    cipher.name, cipher.state
+                ));
+            }
+            if !seen.insert(&cipher.name) {
+                errors.push(format!("Duplicate keywrap cipher '{}'", cipher.name));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
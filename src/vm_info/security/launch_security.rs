@@ -0,0 +1,440 @@
+use serde::{Deserialize, Serialize};
+
+/// SEV-SNP policy 的保留位 17，必须为 1
+const SNP_POLICY_RESERVED: u64 = 1 << 17;
+
+/// `<launchSecurity>`，按 `@type` 区分布局
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "RawLaunchSecurity", into = "RawLaunchSecurity")]
+pub enum LaunchSecurity {
+    /// AMD SEV / SEV-ES
+    Sev(SevLaunchSecurity),
+    /// AMD SEV-SNP
+    SevSnp(SevSnpLaunchSecurity),
+    /// Intel TDX
+    Tdx(TdxLaunchSecurity),
+    /// IBM Secure Execution（s390 Protected Virtualization）
+    S390Pv,
+}
+
+// AMD SEV 启动参数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SevLaunchSecurity {
+    /// 将 kernel/initrd/cmdline 的哈希注入度量，需要直接内核引导
+    pub kernel_hashes: Option<String>,
+    /// C-bit 在页表项中的位置
+    pub cbitpos: Option<u32>,
+    /// 启用加密后损失的物理地址位数
+    pub reduced_phys_bits: Option<u32>,
+    /// 十六进制 guest policy，如 0x0033
+    pub policy: Option<String>,
+    /// 客户机所有者的 Diffie-Hellman 证书（base64）
+    pub dh_cert: Option<String>,
+    /// 会话参数（base64）
+    pub session: Option<String>,
+}
+
+// AMD SEV-SNP 启动参数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SevSnpLaunchSecurity {
+    pub kernel_hashes: Option<String>,
+    /// id_auth 中是否包含作者密钥
+    pub author_key: Option<String>,
+    /// 是否使用 VCEK 签名证明报告
+    pub vcek: Option<String>,
+    pub cbitpos: Option<u32>,
+    pub reduced_phys_bits: Option<u32>,
+    /// 十六进制 64 位 guest policy，位 17 必须为 1
+    pub policy: Option<String>,
+    pub guest_visible_workarounds: Option<String>,
+    /// ID Block 结构（base64）
+    pub id_block: Option<String>,
+    /// ID Authentication Information 结构（base64）
+    pub id_auth: Option<String>,
+    /// 32 字节的宿主机数据（base64）
+    pub host_data: Option<String>,
+}
+
+// Intel TDX 启动参数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TdxLaunchSecurity {
+    /// 十六进制 TD 属性
+    pub policy: Option<String>,
+    /// 以下均为 48 字节 SHA-384 摘要（base64）
+    pub mr_config_id: Option<String>,
+    pub mr_owner: Option<String>,
+    pub mr_owner_config: Option<String>,
+}
+
+impl SevLaunchSecurity {
+    pub fn new(policy: u32) -> Self {
+        Self {
+            policy: Some(format!("{:#06x}", policy)),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_cbitpos(mut self, cbitpos: u32, reduced_phys_bits: u32) -> Self {
+        self.cbitpos = Some(cbitpos);
+        self.reduced_phys_bits = Some(reduced_phys_bits);
+        self
+    }
+
+    pub fn with_kernel_hashes(mut self) -> Self {
+        self.kernel_hashes = Some("yes".to_string());
+        self
+    }
+}
+
+impl SevSnpLaunchSecurity {
+    pub fn new(policy: u64) -> Self {
+        Self {
+            policy: Some(format!("{:#x}", policy)),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_cbitpos(mut self, cbitpos: u32, reduced_phys_bits: u32) -> Self {
+        self.cbitpos = Some(cbitpos);
+        self.reduced_phys_bits = Some(reduced_phys_bits);
+        self
+    }
+
+    pub fn with_kernel_hashes(mut self) -> Self {
+        self.kernel_hashes = Some("yes".to_string());
+        self
+    }
+}
+
+impl TdxLaunchSecurity {
+    pub fn new(policy: u64) -> Self {
+        Self {
+            policy: Some(format!("{:#x}", policy)),
+            ..Self::default()
+        }
+    }
+}
+
+impl LaunchSecurity {
+    pub fn security_type(&self) -> &'static str {
+        match self {
+            LaunchSecurity::Sev(_) => "sev",
+            LaunchSecurity::SevSnp(_) => "sev-snp",
+            LaunchSecurity::Tdx(_) => "tdx",
+            LaunchSecurity::S390Pv => "s390-pv",
+        }
+    }
+
+    /// 运行在 x86 上，需要 q35 与 UEFI
+    pub fn is_x86(&self) -> bool {
+        !matches!(self, LaunchSecurity::S390Pv)
+    }
+
+    /// 客户机内存加密后设备只能通过 IOMMU 访问，virtio 设备需要 `iommu='on'`
+    pub fn requires_virtio_iommu(&self) -> bool {
+        !matches!(self, LaunchSecurity::Tdx(_))
+    }
+
+    pub fn kernel_hashes(&self) -> bool {
+        let value = match self {
+            LaunchSecurity::Sev(sev) => sev.kernel_hashes.as_deref(),
+            LaunchSecurity::SevSnp(snp) => snp.kernel_hashes.as_deref(),
+            _ => None,
+        };
+        value == Some("yes")
+    }
+
+    /// 只检查自身字段，与域其他部分的组合见 `Domain::validate_security`
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let what = self.security_type();
+
+        match self {
+            LaunchSecurity::Sev(sev) => {
+                check_yes_no(what, "kernelHashes", &sev.kernel_hashes, &mut errors);
+                check_cbitpos(what, sev.cbitpos, sev.reduced_phys_bits, &mut errors);
+                if let Some(policy) = &sev.policy {
+                    match parse_hex(policy) {
+                        Some(value) if value > u32::MAX as u64 => {
+                            errors.push(format!("sev policy '{}' does not fit in 32 bits", policy))
+                        }
+                        Some(_) => {}
+                        None => errors.push(format!("Invalid sev policy '{}'", policy)),
+                    }
+                }
+                check_base64(what, "dhCert", &sev.dh_cert, None, &mut errors);
+                check_base64(what, "session", &sev.session, None, &mut errors);
+            }
+            LaunchSecurity::SevSnp(snp) => {
+                check_yes_no(what, "kernelHashes", &snp.kernel_hashes, &mut errors);
+                check_yes_no(what, "authorKey", &snp.author_key, &mut errors);
+                check_yes_no(what, "vcek", &snp.vcek, &mut errors);
+                check_cbitpos(what, snp.cbitpos, snp.reduced_phys_bits, &mut errors);
+                if let Some(policy) = &snp.policy {
+                    match parse_hex(policy) {
+                        Some(value) if value & SNP_POLICY_RESERVED == 0 => errors.push(format!(
+                            "sev-snp policy '{}' must set reserved bit 17",
+                            policy
+                        )),
+                        Some(_) => {}
+                        None => errors.push(format!("Invalid sev-snp policy '{}'", policy)),
+                    }
+                }
+                check_base64(what, "idBlock", &snp.id_block, None, &mut errors);
+                check_base64(what, "idAuth", &snp.id_auth, None, &mut errors);
+                check_base64(what, "hostData", &snp.host_data, Some(32), &mut errors);
+                if snp.id_auth.is_some() && snp.id_block.is_none() {
+                    errors.push("sev-snp <idAuth> requires <idBlock>".to_string());
+                }
+                if snp.author_key.as_deref() == Some("yes") && snp.id_auth.is_none() {
+                    errors.push("sev-snp authorKey='yes' requires <idAuth>".to_string());
+                }
+            }
+            LaunchSecurity::Tdx(tdx) => {
+                if let Some(policy) = &tdx.policy
+                    && parse_hex(policy).is_none()
+                {
+                    errors.push(format!("Invalid tdx policy '{}'", policy));
+                }
+                check_base64(what, "mrConfigId", &tdx.mr_config_id, Some(48), &mut errors);
+                check_base64(what, "mrOwner", &tdx.mr_owner, Some(48), &mut errors);
+                check_base64(
+                    what,
+                    "mrOwnerConfig",
+                    &tdx.mr_owner_config,
+                    Some(48),
+                    &mut errors,
+                );
+            }
+            LaunchSecurity::S390Pv => {}
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl LaunchSecurity {
+    /// QEMU 固件描述文件中对应的特性名，用于自动选择固件
+    ///
+    /// SEV 的 policy 设置了 ES 位（0x4）时还需要 amd-sev-es。
+    pub fn firmware_features(&self) -> Vec<&'static str> {
+        match self {
+            LaunchSecurity::Sev(sev) => {
+                let es = sev
+                    .policy
+                    .as_deref()
+                    .and_then(parse_hex)
+                    .is_some_and(|policy| policy & 0x4 != 0);
+                if es {
+                    vec!["amd-sev", "amd-sev-es"]
+                } else {
+                    vec!["amd-sev"]
+                }
+            }
+            LaunchSecurity::SevSnp(_) => vec!["amd-sev-snp"],
+            LaunchSecurity::Tdx(_) => vec!["intel-tdx"],
+            LaunchSecurity::S390Pv => Vec::new(),
+        }
+    }
+}
+
+fn parse_hex(value: &str) -> Option<u64> {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    u64::from_str_radix(digits, 16).ok()
+}
+
+fn check_yes_no(what: &str, name: &str, value: &Option<String>, errors: &mut Vec<String>) {
+    if let Some(value) = value
+        && value != "yes"
+        && value != "no"
+    {
+        errors.push(format!(
+            "{} {} must be 'yes' or 'no', got '{}'",
+            what, name, value
+        ));
+    }
+}
+
+fn check_cbitpos(
+    what: &str,
+    cbitpos: Option<u32>,
+    reduced_phys_bits: Option<u32>,
+    errors: &mut Vec<String>,
+) {
+    if let Some(cbitpos) = cbitpos
+        && !(32..=63).contains(&cbitpos)
+    {
+        errors.push(format!(
+            "{} cbitpos {} is out of range 32..63",
+            what, cbitpos
+        ));
+    }
+    if let Some(bits) = reduced_phys_bits
+        && bits > 63
+    {
+        errors.push(format!(
+            "{} reducedPhysBits {} is out of range 0..63",
+            what, bits
+        ));
+    }
+}
+
+// 检查 base64 编码，`len` 为解码后要求的字节数
+fn check_base64(
+    what: &str,
+    name: &str,
+    value: &Option<String>,
+    len: Option<usize>,
+    errors: &mut Vec<String>,
+) {
+    let Some(value) = value else {
+        return;
+    };
+    let value = value.trim();
+    let padding = value.chars().rev().take_while(|&c| c == '=').count();
+    let body = &value[..value.len() - padding];
+    let valid = value.len() % 4 == 0
+        && padding <= 2
+        && body
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/');
+    if !valid {
+        errors.push(format!("{} <{}> is not valid base64", what, name));
+        return;
+    }
+    let decoded = value.len() / 4 * 3 - padding;
+    if let Some(len) = len
+        && decoded != len
+    {
+        errors.push(format!(
+            "{} <{}> must decode to {} bytes, got {}",
+            what, name, len, decoded
+        ));
+    }
+}
+
+// XML 中 <launchSecurity> 的实际布局，用于在 LaunchSecurity 枚举之间转换
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct RawLaunchSecurity {
+    #[serde(rename = "@type")]
+    security_type: String,
+    #[serde(rename = "@kernelHashes", skip_serializing_if = "Option::is_none")]
+    kernel_hashes: Option<String>,
+    #[serde(rename = "@authorKey", skip_serializing_if = "Option::is_none")]
+    author_key: Option<String>,
+    #[serde(rename = "@vcek", skip_serializing_if = "Option::is_none")]
+    vcek: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cbitpos: Option<u32>,
+    #[serde(rename = "reducedPhysBits", skip_serializing_if = "Option::is_none")]
+    reduced_phys_bits: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    policy: Option<String>,
+    #[serde(rename = "dhCert", skip_serializing_if = "Option::is_none")]
+    dh_cert: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<String>,
+    #[serde(
+        rename = "guestVisibleWorkarounds",
+        skip_serializing_if = "Option::is_none"
+    )]
+    guest_visible_workarounds: Option<String>,
+    #[serde(rename = "idBlock", skip_serializing_if = "Option::is_none")]
+    id_block: Option<String>,
+    #[serde(rename = "idAuth", skip_serializing_if = "Option::is_none")]
+    id_auth: Option<String>,
+    #[serde(rename = "hostData", skip_serializing_if = "Option::is_none")]
+    host_data: Option<String>,
+    #[serde(rename = "mrConfigId", skip_serializing_if = "Option::is_none")]
+    mr_config_id: Option<String>,
+    #[serde(rename = "mrOwner", skip_serializing_if = "Option::is_none")]
+    mr_owner: Option<String>,
+    #[serde(rename = "mrOwnerConfig", skip_serializing_if = "Option::is_none")]
+    mr_owner_config: Option<String>,
+}
+
+impl TryFrom<RawLaunchSecurity> for LaunchSecurity {
+    type Error = String;
+
+    fn try_from(raw: RawLaunchSecurity) -> Result<Self, Self::Error> {
+        match raw.security_type.as_str() {
+            "sev" => Ok(LaunchSecurity::Sev(SevLaunchSecurity {
+                kernel_hashes: raw.kernel_hashes,
+                cbitpos: raw.cbitpos,
+                reduced_phys_bits: raw.reduced_phys_bits,
+                policy: raw.policy,
+                dh_cert: raw.dh_cert,
+                session: raw.session,
+            })),
+            "sev-snp" => Ok(LaunchSecurity::SevSnp(SevSnpLaunchSecurity {
+                kernel_hashes: raw.kernel_hashes,
+                author_key: raw.author_key,
+                vcek: raw.vcek,
+                cbitpos: raw.cbitpos,
+                reduced_phys_bits: raw.reduced_phys_bits,
+                policy: raw.policy,
+                guest_visible_workarounds: raw.guest_visible_workarounds,
+                id_block: raw.id_block,
+                id_auth: raw.id_auth,
+                host_data: raw.host_data,
+            })),
+            "tdx" => Ok(LaunchSecurity::Tdx(TdxLaunchSecurity {
+                policy: raw.policy,
+                mr_config_id: raw.mr_config_id,
+                mr_owner: raw.mr_owner,
+                mr_owner_config: raw.mr_owner_config,
+            })),
+            "s390-pv" => Ok(LaunchSecurity::S390Pv),
+            other => Err(format!(
+                "Unknown launchSecurity type '{}', expected 'sev', 'sev-snp', 'tdx' or 's390-pv'",
+                other
+            )),
+        }
+    }
+}
+
+impl From<LaunchSecurity> for RawLaunchSecurity {
+    fn from(security: LaunchSecurity) -> Self {
+        let mut raw = RawLaunchSecurity {
+            security_type: security.security_type().to_string(),
+            ..Self::default()
+        };
+        match security {
+            LaunchSecurity::Sev(sev) => {
+                raw.kernel_hashes = sev.kernel_hashes;
+                raw.cbitpos = sev.cbitpos;
+                raw.reduced_phys_bits = sev.reduced_phys_bits;
+                raw.policy = sev.policy;
+                raw.dh_cert = sev.dh_cert;
+                raw.session = sev.session;
+            }
+            LaunchSecurity::SevSnp(snp) => {
+                raw.kernel_hashes = snp.kernel_hashes;
+                raw.author_key = snp.author_key;
+                raw.vcek = snp.vcek;
+                raw.cbitpos = snp.cbitpos;
+                raw.reduced_phys_bits = snp.reduced_phys_bits;
+                raw.policy = snp.policy;
+                raw.guest_visible_workarounds = snp.guest_visible_workarounds;
+                raw.id_block = snp.id_block;
+                raw.id_auth = snp.id_auth;
+                raw.host_data = snp.host_data;
+            }
+            LaunchSecurity::Tdx(tdx) => {
+                raw.policy = tdx.policy;
+                raw.mr_config_id = tdx.mr_config_id;
+                raw.mr_owner = tdx.mr_owner;
+                raw.mr_owner_config = tdx.mr_owner_config;
+            }
+            LaunchSecurity::S390Pv => {}
+        }
+        raw
+    }
+}
@@ -0,0 +1,126 @@
+mod keywrap;
+mod launch_security;
+mod seclabel;
+
+use super::{Domain, Hypervisor, OsArch};
+pub use keywrap::{KeyWrap, KeyWrapCipher};
+pub use launch_security::{
+    LaunchSecurity, SevLaunchSecurity, SevSnpLaunchSecurity, TdxLaunchSecurity,
+};
+pub use seclabel::{Seclabel, SeclabelType};
+use std::collections::BTreeSet;
+
+impl Domain {
+    /// 验证 `<seclabel>`、`<keywrap>` 与 `<launchSecurity>`
+    ///
+    /// SEV/SEV-SNP/TDX 需要 kvm、q35 与 UEFI；SEV 与 s390-pv 的 virtio 设备需要 `iommu='on'`。
+    pub fn validate_security(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let arch = self.os.os_type.arch.as_ref().map(OsArch::as_str);
+
+        let seclabels = self.seclabels.as_deref().unwrap_or_default();
+        let mut models = BTreeSet::new();
+        for seclabel in seclabels {
+            if let Err(mut e) = seclabel.validate() {
+                errors.append(&mut e);
+            }
+            match &seclabel.model {
+                Some(model) if !models.insert(model) => {
+                    errors.push(format!("Duplicate <seclabel> for model '{}'", model));
+                }
+                Some(_) => {}
+                None if seclabels.len() > 1 => errors.push(
+                    "Every <seclabel> must specify a model when more than one is present"
+                        .to_string(),
+                ),
+                None => {}
+            }
+        }
+
+        if let Some(keywrap) = &self.keywrap
+            && let Err(mut e) = keywrap.validate(arch)
+        {
+            errors.append(&mut e);
+        }
+
+        if let Some(security) = &self.launch_security {
+            let what = security.security_type();
+            if let Err(mut e) = security.validate() {
+                errors.append(&mut e);
+            }
+
+            if security.is_x86() {
+                if self.domain_type != Hypervisor::Kvm {
+                    errors.push(format!(
+                        "launchSecurity '{}' requires domain type 'kvm', got '{}'",
+                        what, self.domain_type
+                    ));
+                }
+                if let Some(arch) = arch
+                    && arch != "x86_64"
+                {
+                    errors.push(format!(
+                        "launchSecurity '{}' requires arch 'x86_64', got '{}'",
+                        what, arch
+                    ));
+                }
+                if !self.os.os_type.machine.as_ref().is_some_and(|m| m.is_q35()) {
+                    errors.push(format!(
+                        "launchSecurity '{}' requires a q35 machine type",
+                        what
+                    ));
+                }
+                if !self.os.uses_uefi() {
+                    errors.push(format!("launchSecurity '{}' requires UEFI firmware", what));
+                }
+            } else if let Some(arch) = arch
+                && arch != "s390x"
+            {
+                errors.push(format!(
+                    "launchSecurity '{}' requires arch 's390x', got '{}'",
+                    what, arch
+                ));
+            }
+
+            if security.kernel_hashes() && self.os.kernel.is_none() {
+                errors.push(format!(
+                    "launchSecurity '{}' kernelHashes='yes' requires direct kernel boot",
+                    what
+                ));
+            }
+
+            if security.requires_virtio_iommu() {
+                for device in self.devices.virtio_without_iommu() {
+                    errors.push(format!(
+                        "launchSecurity '{}' requires iommu='on' on virtio {}",
+                        what, device
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 不影响启动但推荐的安全配置
+    pub fn security_recommendations(&self) -> Vec<String> {
+        let mut recommendations = Vec::new();
+        if let Some(security) = &self.launch_security {
+            let locked = self
+                .memory_backing
+                .as_ref()
+                .is_some_and(|m| m.locked.is_some());
+            if !locked {
+                recommendations.push(format!(
+                    "launchSecurity '{}' should use <memoryBacking><locked/> so encrypted guest memory is not swapped",
+                    security.security_type()
+                ));
+            }
+        }
+        recommendations
+    }
+}
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 安全标签的分配方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeclabelType {
+    /// 启动时由 libvirt 生成唯一标签
+    Dynamic,
+    /// 使用配置中的标签
+    Static,
+    /// 不做安全隔离
+    None,
+}
+
+impl fmt::Display for SeclabelType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SeclabelType::Dynamic => "dynamic",
+            SeclabelType::Static => "static",
+            SeclabelType::None => "none",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// 安全标签 <seclabel type='dynamic' model='selinux' relabel='yes'>
+// 运行中的域会带有生成的 <label> 和 <imagelabel>，其中 imagelabel 只出现在输出中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Seclabel {
+    #[serde(rename = "@type")]
+    pub label_type: SeclabelType,
+    /// selinux、apparmor、dac 等
+    #[serde(rename = "@model", skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(rename = "@relabel", skip_serializing_if = "Option::is_none")]
+    pub relabel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(rename = "imagelabel", skip_serializing_if = "Option::is_none")]
+    pub image_label: Option<String>,
+    /// dynamic 类型生成标签时使用的基础标签
+    #[serde(rename = "baselabel", skip_serializing_if = "Option::is_none")]
+    pub base_label: Option<String>,
+}
+
+impl Seclabel {
+    pub fn new(label_type: SeclabelType) -> Self {
+        Self {
+            label_type,
+            model: None,
+            relabel: None,
+            label: None,
+            image_label: None,
+            base_label: None,
+        }
+    }
+
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = Some(model.to_string());
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn with_base_label(mut self, label: &str) -> Self {
+        self.base_label = Some(label.to_string());
+        self
+    }
+
+    /// 运行时生成的字段名称
+    pub fn runtime_fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.label_type == SeclabelType::Dynamic && self.label.is_some() {
+            fields.push("label");
+        }
+        if self.image_label.is_some() {
+            fields.push("imagelabel");
+        }
+        fields
+    }
+
+    /// 去掉运行时生成的标签
+    pub fn strip_runtime(&mut self) {
+        if self.label_type == SeclabelType::Dynamic {
+            self.label = None;
+        }
+        self.image_label = None;
+    }
+
+    pub fn with_relabel(mut self, relabel: bool) -> Self {
+        self.relabel = Some(if relabel { "yes" } else { "no" }.to_string());
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let what = match &self.model {
+            Some(model) => format!("<seclabel model='{}'>", model),
+            None => "<seclabel>".to_string(),
+        };
+
+        if let Some(relabel) = &self.relabel
+            && relabel != "yes"
+            && relabel != "no"
+        {
+            errors.push(format!(
+                "{} relabel must be 'yes' or 'no', got '{}'",
+                what, relabel
+            ));
+        }
+
+        match self.label_type {
+            SeclabelType::Static => {
+                if self.label.is_none() {
+                    errors.push(format!("{} type 'static' requires a <label>", what));
+                }
+                if self.base_label.is_some() {
+                    errors.push(format!(
+                        "{} <baselabel> is only valid for type 'dynamic'",
+                        what
+                    ));
+                }
+            }
+            SeclabelType::Dynamic => {
+                if self.relabel.as_deref() == Some("no") {
+                    errors.push(format!("{} type 'dynamic' requires relabel='yes'", what));
+                }
+            }
+            SeclabelType::None => {
+                if self.label.is_some() || self.image_label.is_some() || self.base_label.is_some() {
+                    errors.push(format!("{} type 'none' cannot contain labels", what));
+                }
+                if self.relabel.as_deref() == Some("yes") {
+                    errors.push(format!("{} type 'none' cannot use relabel='yes'", what));
+                }
+            }
+        }
+
+        // DAC 标签格式为 user:group，如 +107:+107
+        if self.model.as_deref() == Some("dac") {
+            for label in [&self.label, &self.base_label].into_iter().flatten() {
+                if label.split(':').count() != 2 {
+                    errors.push(format!(
+                        "{} label '{}' must be in the form 'user:group'",
+                        what, label
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -1,26 +1,85 @@
 use super::SysinfoEntry;
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 // FWCFG 类型的 sysinfo
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-#[serde(rename = "sysinfo")]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct FwcfgSysinfo {
-    #[serde(rename = "@type")]
-    pub sysinfo_type: String, //"fwcfg"
-    #[serde(rename = "entry", default)]
     pub entries: Vec<SysinfoEntry>,
 }
 
-// FWCFG 条目
-#[allow(unused)]
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
-pub struct FwcfgEntry {
-    #[serde(rename = "@name")]
-    pub name: String,
+impl FwcfgSysinfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    #[serde(rename = "@file", skip_serializing_if = "Option::is_none")]
-    pub file: Option<String>,
+    /// 直接给出内容的条目
+    pub fn with_value(mut self, name: &str, value: &str) -> Self {
+        self.entries.push(SysinfoEntry::new(name, value));
+        self
+    }
 
-    #[serde(rename = "$text", skip_serializing_if = "Option::is_none")]
-    pub value: Option<String>,
+    /// 内容来自宿主机文件的条目
+    pub fn with_file(mut self, name: &str, file: &str) -> Self {
+        self.entries.push(SysinfoEntry::from_file(name, file));
+        self
+    }
+}
+
+/// fw_cfg 文件名的最大长度（QEMU 限制为 56 字节，含结尾的 NUL）
+pub const FWCFG_NAME_MAX: usize = 55;
+
+impl FwcfgSysinfo {
+    /// 设置条目，同名条目只保留一个
+    pub fn set(&mut self, entry: SysinfoEntry) {
+        self.entries.retain(|e| e.name != entry.name);
+        self.entries.push(entry);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SysinfoEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    /// 验证条目名称与内容
+    ///
+    /// 名称必须以 `opt/` 开头且不超过 55 个字符，不能重复；
+    /// 每个条目必须且只能给出内容或 `file` 之一。
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut seen = BTreeSet::new();
+
+        for entry in &self.entries {
+            let name = &entry.name;
+            if !name.starts_with("opt/") {
+                errors.push(format!("fw_cfg entry '{}' must start with 'opt/'", name));
+            }
+            if name.len() > FWCFG_NAME_MAX {
+                errors.push(format!(
+                    "fw_cfg entry '{}' is {} characters long, at most {} are allowed",
+                    name,
+                    name.len(),
+                    FWCFG_NAME_MAX
+                ));
+            }
+            if !seen.insert(name.as_str()) {
+                errors.push(format!("Duplicate fw_cfg entry '{}'", name));
+            }
+            match (&entry.value, &entry.file) {
+                (Some(_), Some(_)) => errors.push(format!(
+                    "fw_cfg entry '{}' cannot have both a value and a file",
+                    name
+                )),
+                (None, None) => errors.push(format!(
+                    "fw_cfg entry '{}' requires either a value or a file",
+                    name
+                )),
+                _ => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
@@ -0,0 +1,278 @@
+use super::smbios::normalize_uuid;
+use super::{
+    BaseBoardEntry, BaseBoardInfo, BiosEntry, BiosInfo, ChassisEntry, ChassisInfo, SmbiosSysinfo,
+    Sysinfo, SystemEntry, SystemInfo,
+};
+use crate::vm_info::Domain;
+use crate::vm_info::os::{Smbios, SmbiosMode};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 宿主机 DMI 信息所在目录
+pub const DMI_DIR: &str = "/sys/class/dmi/id";
+
+// 固件常见的占位值，复制给客户机没有意义
+const PLACEHOLDERS: &[&str] = &[
+    "To Be Filled By O.E.M.",
+    "To be filled by O.E.M.",
+    "Default string",
+    "Not Specified",
+    "Not Applicable",
+    "None",
+    "System Product Name",
+    "System Serial Number",
+    "System Version",
+    "Chassis Serial Number",
+    "Base Board Serial Number",
+];
+
+// DMI 文件对应的 SMBIOS 表与条目
+enum DmiField {
+    Bios(BiosEntry),
+    System(SystemEntry),
+    BaseBoard(BaseBoardEntry),
+    Chassis(ChassisEntry),
+}
+
+const DMI_FIELDS: &[(&str, DmiField)] = &[
+    ("bios_vendor", DmiField::Bios(BiosEntry::Vendor)),
+    ("bios_version", DmiField::Bios(BiosEntry::Version)),
+    ("bios_date", DmiField::Bios(BiosEntry::Date)),
+    ("bios_release", DmiField::Bios(BiosEntry::Release)),
+    ("sys_vendor", DmiField::System(SystemEntry::Manufacturer)),
+    ("product_name", DmiField::System(SystemEntry::Product)),
+    ("product_version", DmiField::System(SystemEntry::Version)),
+    ("product_serial", DmiField::System(SystemEntry::Serial)),
+    ("product_uuid", DmiField::System(SystemEntry::Uuid)),
+    ("product_sku", DmiField::System(SystemEntry::Sku)),
+    ("product_family", DmiField::System(SystemEntry::Family)),
+    (
+        "board_vendor",
+        DmiField::BaseBoard(BaseBoardEntry::Manufacturer),
+    ),
+    ("board_name", DmiField::BaseBoard(BaseBoardEntry::Product)),
+    (
+        "board_version",
+        DmiField::BaseBoard(BaseBoardEntry::Version),
+    ),
+    ("board_serial", DmiField::BaseBoard(BaseBoardEntry::Serial)),
+    (
+        "board_asset_tag",
+        DmiField::BaseBoard(BaseBoardEntry::Asset),
+    ),
+    (
+        "chassis_vendor",
+        DmiField::Chassis(ChassisEntry::Manufacturer),
+    ),
+    ("chassis_version", DmiField::Chassis(ChassisEntry::Version)),
+    ("chassis_serial", DmiField::Chassis(ChassisEntry::Serial)),
+    ("chassis_asset_tag", DmiField::Chassis(ChassisEntry::Asset)),
+];
+
+/// 复制宿主机 SMBIOS 信息时需要隐藏的字段
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DmiRedaction {
+    /// 各表的 serial
+    pub serials: bool,
+    /// system uuid
+    pub uuid: bool,
+    /// baseBoard/chassis 的 asset
+    pub asset_tags: bool,
+    /// 被隐藏字段的替代值，None 表示直接省略
+    pub replacement: Option<String>,
+    /// 保留 "To Be Filled By O.E.M." 之类的占位值
+    pub keep_placeholders: bool,
+}
+
+impl DmiRedaction {
+    /// 不隐藏任何字段
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 隐藏 serial、uuid 和 asset 等可识别单台机器的字段
+    pub fn identifiers() -> Self {
+        Self {
+            serials: true,
+            uuid: true,
+            asset_tags: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_replacement(mut self, replacement: &str) -> Self {
+        self.replacement = Some(replacement.to_string());
+        self
+    }
+
+    pub fn with_keep_placeholders(mut self, keep: bool) -> Self {
+        self.keep_placeholders = keep;
+        self
+    }
+
+    fn redacts(&self, file: &str) -> bool {
+        (self.serials && file.ends_with("_serial"))
+            || (self.uuid && file == "product_uuid")
+            || (self.asset_tags && file.ends_with("_asset_tag"))
+    }
+}
+
+/// 宿主机 DMI 信息，键为 /sys/class/dmi/id 下的文件名
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HostDmi {
+    pub values: BTreeMap<String, String>,
+}
+
+impl HostDmi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_value(mut self, file: &str, value: &str) -> Self {
+        self.values.insert(file.to_string(), value.to_string());
+        self
+    }
+
+    /// 读取本机的 /sys/class/dmi/id
+    pub fn from_host() -> Result<Self, String> {
+        Self::from_dir(DMI_DIR)
+    }
+
+    /// 从指定目录读取，便于使用测试夹具
+    ///
+    /// serial、uuid 等文件通常只有 root 可读，无法读取的文件会被跳过。
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, String> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(format!("DMI directory {} does not exist", dir.display()));
+        }
+
+        let mut dmi = Self::new();
+        for (file, _) in DMI_FIELDS {
+            if let Ok(value) = fs::read_to_string(dir.join(file)) {
+                let value = value.trim();
+                if !value.is_empty() {
+                    dmi.values.insert(file.to_string(), value.to_string());
+                }
+            }
+        }
+        Ok(dmi)
+    }
+
+    pub fn get(&self, file: &str) -> Option<&str> {
+        self.values.get(file).map(String::as_str)
+    }
+
+    /// 生成 SMBIOS sysinfo（bios、system、baseBoard、chassis）
+    pub fn to_smbios(&self, redaction: &DmiRedaction) -> SmbiosSysinfo {
+        let mut bios = BiosInfo::new();
+        let mut system = SystemInfo::new();
+        let mut base_board = BaseBoardInfo::new();
+        let mut chassis = ChassisInfo::new();
+
+        for (file, field) in DMI_FIELDS {
+            let Some(value) = self.get(file) else {
+                continue;
+            };
+            if !redaction.keep_placeholders && PLACEHOLDERS.contains(&value) {
+                continue;
+            }
+            let value = if redaction.redacts(file) {
+                match &redaction.replacement {
+                    Some(replacement) => replacement.as_str(),
+                    None => continue,
+                }
+            } else {
+                value
+            };
+            // 替代值不是合法的 UUID，只能省略
+            if *file == "product_uuid" && normalize_uuid(value).is_none() {
+                continue;
+            }
+
+            match field {
+                DmiField::Bios(entry) => bios = bios.with(*entry, value),
+                DmiField::System(entry) => system = system.with(*entry, value),
+                DmiField::BaseBoard(entry) => base_board = base_board.with(*entry, value),
+                DmiField::Chassis(entry) => chassis = chassis.with(*entry, value),
+            }
+        }
+
+        let mut smbios = SmbiosSysinfo::new();
+        if !bios.entries.is_empty() {
+            smbios = smbios.with_bios(bios);
+        }
+        if !system.entries.is_empty() {
+            smbios = smbios.with_system(system);
+        }
+        if !base_board.entries.is_empty() {
+            smbios = smbios.with_base_board(base_board);
+        }
+        if !chassis.entries.is_empty() {
+            smbios = smbios.with_chassis(chassis);
+        }
+        smbios
+    }
+}
+
+impl Domain {
+    /// 将宿主机 SMBIOS 信息交给客户机
+    ///
+    /// - `SmbiosMode::Sysinfo`：用 DMI 数据替换 `<sysinfo type='smbios'>`（保留其中的
+    ///   oemStrings），域未设置 uuid 时采用宿主机 uuid，已设置且不一致时报错
+    /// - `SmbiosMode::Host`：由 QEMU 直接复制宿主机的表 0 和表 1，
+    ///   移除不再生效的 `<sysinfo type='smbios'>`
+    /// - `SmbiosMode::Emulate`：移除 `<sysinfo type='smbios'>`
+    pub fn apply_host_smbios(
+        &mut self,
+        dmi: &HostDmi,
+        redaction: &DmiRedaction,
+        mode: SmbiosMode,
+    ) -> Result<(), String> {
+        // 先完成 uuid 检查，出错时域保持不变
+        let smbios = match mode {
+            SmbiosMode::Sysinfo => {
+                let smbios = dmi.to_smbios(redaction);
+                if let (Some(host_uuid), Some(uuid)) = (smbios.system_uuid(), &self.uuid)
+                    && normalize_uuid(uuid) != normalize_uuid(host_uuid)
+                {
+                    return Err(format!(
+                        "Host system uuid '{}' differs from domain uuid '{}', redact the uuid or clear the domain uuid",
+                        host_uuid, uuid
+                    ));
+                }
+                Some(smbios)
+            }
+            _ => None,
+        };
+
+        if let Some(host_uuid) = smbios.as_ref().and_then(|s| s.system_uuid())
+            && self.uuid.is_none()
+        {
+            self.uuid = Some(host_uuid.to_lowercase());
+        }
+
+        let sysinfo = self.sysinfo.get_or_insert_with(Vec::new);
+        let existing = sysinfo.iter().position(|s| matches!(s, Sysinfo::Smbios(_)));
+        match (smbios, existing) {
+            (Some(mut smbios), Some(i)) => {
+                if let Sysinfo::Smbios(old) = &mut sysinfo[i] {
+                    smbios.oem_strings = old.oem_strings.take();
+                }
+                sysinfo[i] = Sysinfo::Smbios(smbios);
+            }
+            (Some(smbios), None) => sysinfo.insert(0, Sysinfo::Smbios(smbios)),
+            (None, Some(i)) => {
+                sysinfo.remove(i);
+            }
+            (None, None) => {}
+        }
+
+        if sysinfo.is_empty() {
+            self.sysinfo = None;
+        }
+        self.os.smbios = Some(Smbios::new(mode));
+        Ok(())
+    }
+}
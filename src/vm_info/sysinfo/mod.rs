@@ -1,38 +1,159 @@
 mod fwcfg;
+mod host;
+mod provision;
 mod smbios;
 
-use crate::vm_info::sysinfo::smbios::{
-    BaseBoardInfo, BiosInfo, ChassisInfo, OemStringsInfo, SystemInfo,
-};
+pub use fwcfg::{FWCFG_NAME_MAX, FwcfgSysinfo};
+pub use host::{DMI_DIR, DmiRedaction, HostDmi};
+pub use provision::{IGNITION_FWCFG_NAME, NOCLOUD_SERIAL_PREFIX, ProvisionPayload, Provisioning};
 use serde::{Deserialize, Serialize};
+pub(crate) use smbios::normalize_uuid;
+pub use smbios::{
+    BaseBoardEntry, BaseBoardInfo, BiosEntry, BiosInfo, ChassisEntry, ChassisInfo, OemStringEntry,
+    OemStringsInfo, SmbiosEntryName, SmbiosSysinfo, SystemEntry, SystemInfo,
+};
+
+/// `<sysinfo>`，按 `@type` 区分布局
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "RawSysinfo", into = "RawSysinfo")]
+pub enum Sysinfo {
+    Smbios(SmbiosSysinfo),
+    Fwcfg(FwcfgSysinfo),
+}
+
+impl Sysinfo {
+    /// 创建空的 smbios 类型 sysinfo
+    pub fn smbios() -> Self {
+        Sysinfo::Smbios(SmbiosSysinfo::new())
+    }
+
+    /// 创建 fwcfg 类型 sysinfo
+    pub fn fwcfg(entries: Vec<SysinfoEntry>) -> Self {
+        Sysinfo::Fwcfg(FwcfgSysinfo { entries })
+    }
+
+    pub fn sysinfo_type(&self) -> &'static str {
+        match self {
+            Sysinfo::Smbios(_) => "smbios",
+            Sysinfo::Fwcfg(_) => "fwcfg",
+        }
+    }
+
+    pub fn as_smbios(&self) -> Option<&SmbiosSysinfo> {
+        match self {
+            Sysinfo::Smbios(smbios) => Some(smbios),
+            Sysinfo::Fwcfg(_) => None,
+        }
+    }
+
+    pub fn as_fwcfg(&self) -> Option<&FwcfgSysinfo> {
+        match self {
+            Sysinfo::Fwcfg(fwcfg) => Some(fwcfg),
+            Sysinfo::Smbios(_) => None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        match self {
+            Sysinfo::Smbios(smbios) => smbios.validate(),
+            Sysinfo::Fwcfg(fwcfg) => fwcfg.validate(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SysinfoEntry {
+    #[serde(rename = "@name")]
+    pub name: String,
+
+    #[serde(rename = "@file", skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    #[serde(rename = "$text", skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Sysinfo {
+impl SysinfoEntry {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            file: None,
+            value: Some(value.to_string()),
+        }
+    }
+
+    pub fn from_file(name: &str, file: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            file: Some(file.to_string()),
+            value: None,
+        }
+    }
+}
+
+// XML 中 <sysinfo> 的实际布局，用于在 Sysinfo 枚举之间转换
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct RawSysinfo {
     #[serde(rename = "@type")]
     sysinfo_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     bios: Option<BiosInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     system: Option<SystemInfo>,
-    #[serde(rename = "baseBoard", skip_serializing_if = "Option::is_none")]
-    base_board: Option<BaseBoardInfo>,
+    #[serde(rename = "baseBoard", default, skip_serializing_if = "Vec::is_empty")]
+    base_boards: Vec<BaseBoardInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     chassis: Option<ChassisInfo>,
     #[serde(rename = "oemStrings", skip_serializing_if = "Option::is_none")]
     oem_strings: Option<OemStringsInfo>,
-
-    #[serde(rename = "entry", default)]
+    #[serde(rename = "entry", default, skip_serializing_if = "Vec::is_empty")]
     entries: Vec<SysinfoEntry>,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
-pub struct SysinfoEntry {
-    #[serde(rename = "@name")]
-    pub name: String,
+impl TryFrom<RawSysinfo> for Sysinfo {
+    type Error = String;
 
-    #[serde(rename = "@file", skip_serializing_if = "Option::is_none")]
-    pub file: Option<String>,
+    fn try_from(raw: RawSysinfo) -> Result<Self, Self::Error> {
+        match raw.sysinfo_type.as_str() {
+            "smbios" => Ok(Sysinfo::Smbios(SmbiosSysinfo {
+                bios: raw.bios,
+                system: raw.system,
+                base_boards: raw.base_boards,
+                chassis: raw.chassis,
+                oem_strings: raw.oem_strings,
+            })),
+            "fwcfg" => Ok(Sysinfo::Fwcfg(FwcfgSysinfo {
+                entries: raw.entries,
+            })),
+            other => Err(format!(
+                "Unknown sysinfo type '{}', expected 'smbios' or 'fwcfg'",
+                other
+            )),
+        }
+    }
+}
 
-    #[serde(rename = "$text", skip_serializing_if = "Option::is_none")]
-    pub value: Option<String>,
+impl From<Sysinfo> for RawSysinfo {
+    fn from(sysinfo: Sysinfo) -> Self {
+        let mut raw = RawSysinfo {
+            sysinfo_type: sysinfo.sysinfo_type().to_string(),
+            bios: None,
+            system: None,
+            base_boards: Vec::new(),
+            chassis: None,
+            oem_strings: None,
+            entries: Vec::new(),
+        };
+        match sysinfo {
+            Sysinfo::Smbios(smbios) => {
+                raw.bios = smbios.bios;
+                raw.system = smbios.system;
+                raw.base_boards = smbios.base_boards;
+                raw.chassis = smbios.chassis;
+                raw.oem_strings = smbios.oem_strings;
+            }
+            Sysinfo::Fwcfg(fwcfg) => raw.entries = fwcfg.entries,
+        }
+        raw
+    }
 }
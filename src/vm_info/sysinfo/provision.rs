@@ -0,0 +1,199 @@
+use super::{FwcfgSysinfo, SmbiosSysinfo, Sysinfo, SysinfoEntry, SystemEntry};
+use crate::vm_info::Domain;
+use crate::vm_info::os::{Smbios, SmbiosMode};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Ignition 从 fw_cfg 读取配置的固定名称
+pub const IGNITION_FWCFG_NAME: &str = "opt/com.coreos/config";
+
+/// cloud-init NoCloud 在 SMBIOS system serial 中的数据源前缀
+pub const NOCLOUD_SERIAL_PREFIX: &str = "ds=nocloud";
+
+/// 首次启动时交给客户机的配置
+///
+/// Ignition 通过 fw_cfg 的 `opt/com.coreos/config` 读取配置；cloud-init NoCloud
+/// 通过 SMBIOS system serial `ds=nocloud;s=<seed_url>` 找到数据源，再从该地址读取
+/// user-data、meta-data。NoCloud 的内容不进入域 XML，调用方需用
+/// [`Provisioning::write_files`] 写出文件，并保证 `seed_url` 在客户机启动时可以访问。
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProvisionPayload {
+    /// Ignition 配置（JSON）
+    Ignition(String),
+    /// cloud-init NoCloud 数据
+    NoCloud {
+        /// 客户机可访问的数据源地址，以 `/` 结尾，如 `http://10.0.2.2:8000/`
+        seed_url: String,
+        user_data: String,
+        meta_data: String,
+        network_config: Option<String>,
+    },
+}
+
+impl ProvisionPayload {
+    pub fn ignition(config: &str) -> Self {
+        ProvisionPayload::Ignition(config.to_string())
+    }
+
+    pub fn nocloud(seed_url: &str, user_data: &str, meta_data: &str) -> Self {
+        ProvisionPayload::NoCloud {
+            seed_url: seed_url.to_string(),
+            user_data: user_data.to_string(),
+            meta_data: meta_data.to_string(),
+            network_config: None,
+        }
+    }
+
+    /// 仅对 NoCloud 有效
+    pub fn with_network_config(mut self, config: &str) -> Self {
+        if let ProvisionPayload::NoCloud { network_config, .. } = &mut self {
+            *network_config = Some(config.to_string());
+        }
+        self
+    }
+
+    // (文件名, 内容)
+    fn parts(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            ProvisionPayload::Ignition(config) => vec![("config.ign", config)],
+            ProvisionPayload::NoCloud {
+                user_data,
+                meta_data,
+                network_config,
+                ..
+            } => {
+                let mut parts = vec![("user-data", user_data.as_str()), ("meta-data", meta_data)];
+                if let Some(config) = network_config {
+                    parts.push(("network-config", config));
+                }
+                parts
+            }
+        }
+    }
+}
+
+/// 首次启动配置的注入方式
+#[derive(Debug, Clone, PartialEq)]
+pub struct Provisioning {
+    pub payload: ProvisionPayload,
+    /// 设置后内容写入该目录：Ignition 的 fw_cfg 条目改用 `file` 引用；
+    /// NoCloud 必须设置，该目录由调用方通过 `seed_url` 对外提供
+    pub file_dir: Option<PathBuf>,
+}
+
+impl Provisioning {
+    pub fn new(payload: ProvisionPayload) -> Self {
+        Self {
+            payload,
+            file_dir: None,
+        }
+    }
+
+    pub fn with_file_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.file_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// 写入 `file_dir` 的文件
+    pub fn files(&self) -> Vec<PathBuf> {
+        match &self.file_dir {
+            Some(dir) => self
+                .payload
+                .parts()
+                .into_iter()
+                .map(|(file, _)| dir.join(file))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// 写出配置文件，返回写入的路径
+    pub fn write_files(&self) -> Result<Vec<PathBuf>, String> {
+        let files = self.files();
+        if let Some(dir) = &self.file_dir {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        for (path, (_, content)) in files.iter().zip(self.payload.parts()) {
+            fs::write(path, content)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        }
+        Ok(files)
+    }
+
+    /// 把配置注入域，已有的同名条目会被替换，返回错误时域保持不变
+    ///
+    /// NoCloud 需要 `<os><smbios mode='sysinfo'/>`，未设置时会自动设置；
+    /// 域中只写入数据源地址，因此必须设置 `file_dir`，由调用方通过 `seed_url` 提供该目录。
+    pub fn apply(&self, domain: &mut Domain) -> Result<(), String> {
+        match &self.payload {
+            ProvisionPayload::Ignition(config) => {
+                let entry = match self.files().first() {
+                    Some(path) => {
+                        SysinfoEntry::from_file(IGNITION_FWCFG_NAME, &path.to_string_lossy())
+                    }
+                    None => SysinfoEntry::new(IGNITION_FWCFG_NAME, config),
+                };
+                let sysinfo = domain.sysinfo.get_or_insert_with(Vec::new);
+                let index = match sysinfo.iter().position(|s| s.as_fwcfg().is_some()) {
+                    Some(i) => i,
+                    None => {
+                        sysinfo.push(Sysinfo::Fwcfg(FwcfgSysinfo::new()));
+                        sysinfo.len() - 1
+                    }
+                };
+                let Sysinfo::Fwcfg(fwcfg) = &mut sysinfo[index] else {
+                    unreachable!()
+                };
+                fwcfg.set(entry);
+            }
+            ProvisionPayload::NoCloud { seed_url, .. } => {
+                if self.file_dir.is_none() {
+                    return Err(format!(
+                        "NoCloud data is fetched by the guest from '{}', set a file_dir, write the files and serve that directory at the seed URL",
+                        seed_url
+                    ));
+                }
+                if seed_url.is_empty() || seed_url.contains(';') || !seed_url.ends_with('/') {
+                    return Err(format!(
+                        "NoCloud seed URL '{}' must be non-empty, end with '/' and not contain ';'",
+                        seed_url
+                    ));
+                }
+                let mode = domain.os.smbios.as_ref().map(|s| s.mode.as_str());
+                if mode == Some("host") {
+                    return Err(
+                        "The SMBIOS system serial is taken from the host with <os><smbios mode='host'/>, NoCloud needs mode='sysinfo'"
+                            .to_string(),
+                    );
+                }
+
+                let sysinfo = domain.sysinfo.get_or_insert_with(Vec::new);
+                let index = match sysinfo.iter().position(|s| s.as_smbios().is_some()) {
+                    Some(i) => i,
+                    None => {
+                        sysinfo.insert(0, Sysinfo::Smbios(SmbiosSysinfo::new()));
+                        0
+                    }
+                };
+                let Sysinfo::Smbios(smbios) = &mut sysinfo[index] else {
+                    unreachable!()
+                };
+                let system = smbios.system.take().unwrap_or_default().with(
+                    SystemEntry::Serial,
+                    &format!("{};s={}", NOCLOUD_SERIAL_PREFIX, seed_url),
+                );
+                smbios.system = Some(system);
+                domain.os.smbios = Some(Smbios::new(SmbiosMode::Sysinfo));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Domain {
+    /// 注入 Ignition 或 cloud-init NoCloud 配置，见 [`Provisioning::apply`]
+    pub fn provision(&mut self, provisioning: &Provisioning) -> Result<(), String> {
+        provisioning.apply(self)
+    }
+}
@@ -2,56 +2,409 @@ use super::SysinfoEntry;
 use serde::{Deserialize, Serialize};
 
 // SMBIOS 类型的 sysinfo
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-#[serde(rename = "sysinfo")]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SmbiosSysinfo {
-    #[serde(rename = "@type")]
-    pub sysinfo_type: String, // "smbios"
     pub bios: Option<BiosInfo>,
     pub system: Option<SystemInfo>,
-    #[serde(rename = "baseBoard")]
-    pub base_board: Option<BaseBoardInfo>,
+    /// baseBoard 可以出现多次
+    pub base_boards: Vec<BaseBoardInfo>,
     pub chassis: Option<ChassisInfo>,
-    #[serde(rename = "oemStrings")]
     pub oem_strings: Option<OemStringsInfo>,
 }
 
+impl SmbiosSysinfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_bios(mut self, bios: BiosInfo) -> Self {
+        self.bios = Some(bios);
+        self
+    }
+
+    pub fn with_system(mut self, system: SystemInfo) -> Self {
+        self.system = Some(system);
+        self
+    }
+
+    pub fn with_base_board(mut self, base_board: BaseBoardInfo) -> Self {
+        self.base_boards.push(base_board);
+        self
+    }
+
+    pub fn with_chassis(mut self, chassis: ChassisInfo) -> Self {
+        self.chassis = Some(chassis);
+        self
+    }
+
+    pub fn with_oem_string(mut self, value: &str) -> Self {
+        self.oem_strings
+            .get_or_insert_with(OemStringsInfo::default)
+            .entries
+            .push(OemStringEntry {
+                value: value.to_string(),
+            });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bios.is_none()
+            && self.system.is_none()
+            && self.base_boards.is_empty()
+            && self.chassis.is_none()
+            && self.oem_strings.is_none()
+    }
+
+    /// `<system><entry name='uuid'>`
+    pub fn system_uuid(&self) -> Option<&str> {
+        self.system.as_ref()?.get(SystemEntry::Uuid)
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        if let Some(bios) = &self.bios {
+            validate_entries::<BiosEntry>("bios", &bios.entries, &mut errors);
+            if let Some(date) = bios.get(BiosEntry::Date)
+                && !is_valid_bios_date(date)
+            {
+                errors.push(format!(
+                    "SMBIOS bios date '{}' must be in mm/dd/yy or mm/dd/yyyy format",
+                    date
+                ));
+            }
+        }
+        if let Some(system) = &self.system {
+            validate_entries::<SystemEntry>("system", &system.entries, &mut errors);
+            if let Some(uuid) = system.get(SystemEntry::Uuid)
+                && normalize_uuid(uuid).is_none()
+            {
+                errors.push(format!("SMBIOS system uuid '{}' is not a valid UUID", uuid));
+            }
+        }
+        for base_board in &self.base_boards {
+            validate_entries::<BaseBoardEntry>("baseBoard", &base_board.entries, &mut errors);
+        }
+        if let Some(chassis) = &self.chassis {
+            validate_entries::<ChassisEntry>("chassis", &chassis.entries, &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// 各 SMBIOS 表中允许的条目名
+pub trait SmbiosEntryName: Copy + Sized + 'static {
+    const ALL: &'static [Self];
+
+    fn as_str(&self) -> &'static str;
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|e| e.as_str() == name)
+    }
+}
+
+/// SMBIOS 表 0（bios）的条目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BiosEntry {
+    Vendor,
+    Version,
+    /// mm/dd/yy 或 mm/dd/yyyy
+    Date,
+    /// 主次版本号，如 10.22
+    Release,
+}
+
+impl SmbiosEntryName for BiosEntry {
+    const ALL: &'static [Self] = &[Self::Vendor, Self::Version, Self::Date, Self::Release];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Vendor => "vendor",
+            Self::Version => "version",
+            Self::Date => "date",
+            Self::Release => "release",
+        }
+    }
+}
+
+/// SMBIOS 表 1（system）的条目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemEntry {
+    Manufacturer,
+    Product,
+    Version,
+    Serial,
+    /// 与 `<domain><uuid>` 同时出现时必须一致
+    Uuid,
+    Sku,
+    Family,
+}
+
+impl SmbiosEntryName for SystemEntry {
+    const ALL: &'static [Self] = &[
+        Self::Manufacturer,
+        Self::Product,
+        Self::Version,
+        Self::Serial,
+        Self::Uuid,
+        Self::Sku,
+        Self::Family,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manufacturer => "manufacturer",
+            Self::Product => "product",
+            Self::Version => "version",
+            Self::Serial => "serial",
+            Self::Uuid => "uuid",
+            Self::Sku => "sku",
+            Self::Family => "family",
+        }
+    }
+}
+
+/// SMBIOS 表 2（baseBoard）的条目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseBoardEntry {
+    Manufacturer,
+    Product,
+    Version,
+    Serial,
+    Asset,
+    Location,
+}
+
+impl SmbiosEntryName for BaseBoardEntry {
+    const ALL: &'static [Self] = &[
+        Self::Manufacturer,
+        Self::Product,
+        Self::Version,
+        Self::Serial,
+        Self::Asset,
+        Self::Location,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manufacturer => "manufacturer",
+            Self::Product => "product",
+            Self::Version => "version",
+            Self::Serial => "serial",
+            Self::Asset => "asset",
+            Self::Location => "location",
+        }
+    }
+}
+
+/// SMBIOS 表 3（chassis）的条目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChassisEntry {
+    Manufacturer,
+    Version,
+    Serial,
+    Asset,
+    Sku,
+}
+
+impl SmbiosEntryName for ChassisEntry {
+    const ALL: &'static [Self] = &[
+        Self::Manufacturer,
+        Self::Version,
+        Self::Serial,
+        Self::Asset,
+        Self::Sku,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manufacturer => "manufacturer",
+            Self::Version => "version",
+            Self::Serial => "serial",
+            Self::Asset => "asset",
+            Self::Sku => "sku",
+        }
+    }
+}
+
 // BIOS 信息
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct BiosInfo {
     #[serde(rename = "entry", default)]
     pub entries: Vec<SysinfoEntry>,
 }
 
+impl BiosInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: BiosEntry, value: &str) -> Self {
+        set_entry(&mut self.entries, name.as_str(), value);
+        self
+    }
+
+    pub fn get(&self, name: BiosEntry) -> Option<&str> {
+        get_entry(&self.entries, name.as_str())
+    }
+}
+
 // 系统信息
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct SystemInfo {
     #[serde(rename = "entry", default)]
     pub entries: Vec<SysinfoEntry>,
 }
 
+impl SystemInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: SystemEntry, value: &str) -> Self {
+        set_entry(&mut self.entries, name.as_str(), value);
+        self
+    }
+
+    pub fn get(&self, name: SystemEntry) -> Option<&str> {
+        get_entry(&self.entries, name.as_str())
+    }
+}
+
 // 主板信息
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct BaseBoardInfo {
     #[serde(rename = "entry", default)]
     pub entries: Vec<SysinfoEntry>,
 }
 
+impl BaseBoardInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: BaseBoardEntry, value: &str) -> Self {
+        set_entry(&mut self.entries, name.as_str(), value);
+        self
+    }
+
+    pub fn get(&self, name: BaseBoardEntry) -> Option<&str> {
+        get_entry(&self.entries, name.as_str())
+    }
+}
+
 // 机箱信息
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct ChassisInfo {
     #[serde(rename = "entry", default)]
     pub entries: Vec<SysinfoEntry>,
 }
 
+impl ChassisInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: ChassisEntry, value: &str) -> Self {
+        set_entry(&mut self.entries, name.as_str(), value);
+        self
+    }
+
+    pub fn get(&self, name: ChassisEntry) -> Option<&str> {
+        get_entry(&self.entries, name.as_str())
+    }
+}
+
 // OEM 字符串信息
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct OemStringsInfo {
     #[serde(rename = "entry", default)]
     pub entries: Vec<OemStringEntry>,
 }
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct OemStringEntry {
     #[serde(rename = "$text")]
     pub value: String,
 }
+
+// 同名条目只保留一个
+fn set_entry(entries: &mut Vec<SysinfoEntry>, name: &str, value: &str) {
+    entries.retain(|e| e.name != name);
+    entries.push(SysinfoEntry::new(name, value));
+}
+
+fn get_entry<'a>(entries: &'a [SysinfoEntry], name: &str) -> Option<&'a str> {
+    entries
+        .iter()
+        .find(|e| e.name == name)
+        .and_then(|e| e.value.as_deref())
+}
+
+fn validate_entries<N: SmbiosEntryName>(
+    block: &str,
+    entries: &[SysinfoEntry],
+    errors: &mut Vec<String>,
+) {
+    let mut seen = Vec::new();
+    for entry in entries {
+        if N::parse(&entry.name).is_none() {
+            let allowed: Vec<&str> = N::ALL.iter().map(|e| e.as_str()).collect();
+            errors.push(format!(
+                "Unknown SMBIOS {} entry '{}', expected one of: {}",
+                block,
+                entry.name,
+                allowed.join(", ")
+            ));
+        }
+        if seen.contains(&entry.name.as_str()) {
+            errors.push(format!("Duplicate SMBIOS {} entry '{}'", block, entry.name));
+        }
+        seen.push(&entry.name);
+        if entry.file.is_some() {
+            errors.push(format!(
+                "SMBIOS {} entry '{}' cannot use the file attribute",
+                block, entry.name
+            ));
+        }
+        if entry.value.as_deref().is_none_or(str::is_empty) {
+            errors.push(format!(
+                "SMBIOS {} entry '{}' has no value",
+                block, entry.name
+            ));
+        }
+    }
+}
+
+// mm/dd/yy 或 mm/dd/yyyy
+fn is_valid_bios_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('/').collect();
+    let [month, day, year] = parts.as_slice() else {
+        return false;
+    };
+    let numeric = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !numeric(month) || !numeric(day) || !numeric(year) {
+        return false;
+    }
+    let (Ok(m), Ok(d)) = (month.parse::<u32>(), day.parse::<u32>()) else {
+        return false;
+    };
+    month.len() <= 2
+        && day.len() <= 2
+        && (year.len() == 2 || year.len() == 4)
+        && (1..=12).contains(&m)
+        && (1..=31).contains(&d)
+}
+
+/// 将 UUID 规范化为不含连字符的小写形式，格式无效时返回 None
+pub(crate) fn normalize_uuid(uuid: &str) -> Option<String> {
+    let hex: String = uuid.chars().filter(|&c| c != '-').collect();
+    if hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(hex.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
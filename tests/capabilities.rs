@@ -0,0 +1,84 @@
+use vm_xml_tool::{
+    CapabilityRegistry, Domain, Hypervisor, LifecycleAction, LifecycleConfig, LifecycleEvent,
+};
+
+fn domain(domain_type: &str, lifecycle: &str) -> Domain {
+    let xml = format!(
+        r#"<domain type="{}"><name>guest</name><memory unit="GiB">2</memory><vcpu>2</vcpu><os><type arch="x86_64" machine="q35">hvm</type></os>{}<devices/></domain>"#,
+        domain_type, lifecycle
+    );
+    quick_xml::de::from_str(&xml).unwrap()
+}
+
+#[test]
+fn qemu_drivers_reject_preserve_and_rename_restart_on_poweroff_and_reboot() {
+    let registry = CapabilityRegistry::new();
+
+    for hypervisor in [Hypervisor::Kvm, Hypervisor::Qemu, Hypervisor::Hvf] {
+        let caps = registry.get(&hypervisor).unwrap();
+        for event in [LifecycleEvent::OnPoweroff, LifecycleEvent::OnReboot] {
+            assert_eq!(
+                caps.actions_for(event),
+                [LifecycleAction::Destroy, LifecycleAction::Restart]
+            );
+            for action in [LifecycleAction::Preserve, LifecycleAction::RenameRestart] {
+                let mut config = LifecycleConfig::new();
+                config.set_action(event, action);
+                let errors = registry
+                    .validate_lifecycle(&hypervisor, &config)
+                    .unwrap_err();
+                assert_eq!(errors.len(), 1, "{:?}", errors);
+                assert!(
+                    errors[0].contains("Supported actions: destroy, restart"),
+                    "{:?}",
+                    errors
+                );
+            }
+        }
+
+        // 崩溃时仍可保留现场
+        let config = LifecycleConfig::new().on_crash(LifecycleAction::Preserve);
+        assert_eq!(registry.validate_lifecycle(&hypervisor, &config), Ok(()));
+        let config = LifecycleConfig::new().on_crash(LifecycleAction::RenameRestart);
+        assert!(registry.validate_lifecycle(&hypervisor, &config).is_err());
+    }
+}
+
+#[test]
+fn domain_validate_and_lint_report_unsupported_actions() {
+    let preserve = "<on_poweroff>preserve</on_poweroff><on_reboot>preserve</on_reboot>";
+
+    let kvm = domain("kvm", preserve);
+    let errors = kvm.validate().unwrap_err();
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|e| e.contains("does not support action 'preserve'"))
+            .count(),
+        2,
+        "{:?}",
+        errors
+    );
+    assert!(kvm.lint().has_errors());
+
+    let xen = domain("xen", preserve);
+    assert_eq!(
+        CapabilityRegistry::new().validate_lifecycle(&xen.domain_type, &xen.lifecycle_config()),
+        Ok(())
+    );
+}
+
+#[test]
+fn event_actions_are_limited_to_what_the_event_allows() {
+    let registry = CapabilityRegistry::new();
+    let xen = registry.get(&Hypervisor::Xen).unwrap();
+
+    assert!(xen.supports_action(LifecycleEvent::OnPoweroff, LifecycleAction::RenameRestart));
+    assert!(!xen.supports_action(LifecycleEvent::OnPoweroff, LifecycleAction::Pause));
+    assert!(xen.supports_action(LifecycleEvent::OnLockfailure, LifecycleAction::Pause));
+    assert!(!xen.supports_action(LifecycleEvent::OnLockfailure, LifecycleAction::Destroy));
+
+    let lxc = registry.get(&Hypervisor::Lxc).unwrap();
+    assert!(!lxc.supports_event(LifecycleEvent::OnLockfailure));
+    assert!(lxc.actions_for(LifecycleEvent::OnLockfailure).is_empty());
+}
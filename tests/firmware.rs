@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+use vm_xml_tool::{Domain, FirmwareRequest, FirmwareResolver};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/firmware")
+        .join(name)
+}
+
+fn domain(os: &str, extra: &str) -> Domain {
+    let xml = format!(
+        r#"<domain type="kvm"><name>guest</name><memory unit="GiB">2</memory><vcpu>2</vcpu><os firmware="efi">{}</os>{}<devices/></domain>"#,
+        os, extra
+    );
+    quick_xml::de::from_str(&xml).unwrap()
+}
+
+const Q35: &str = r#"<type arch="x86_64" machine="pc-q35-9.0">hvm</type>"#;
+
+fn loader_path(domain: &Domain) -> Option<&str> {
+    domain.os.loader.as_ref().and_then(|l| l.path.as_deref())
+}
+
+#[test]
+fn from_dir_loads_descriptors_in_file_name_order() {
+    let resolver = FirmwareResolver::from_dir(fixture("usr")).unwrap();
+    let names: Vec<String> = resolver
+        .descriptors
+        .iter()
+        .map(|(path, _)| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names.len(), 7);
+    assert!(names.windows(2).all(|w| w[0] <= w[1]), "{:?}", names);
+}
+
+#[test]
+fn from_dir_reports_invalid_descriptors() {
+    let errors = FirmwareResolver::from_dir(fixture("broken")).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("10-truncated.json"), "{:?}", errors);
+}
+
+#[test]
+fn secure_boot_selects_enrolled_firmware_and_enables_smm() {
+    let resolver = FirmwareResolver::from_dir(fixture("usr")).unwrap();
+    let mut guest = domain(
+        &format!(
+            r#"{}<firmware><feature enabled="yes" name="secure-boot"/></firmware>"#,
+            Q35
+        ),
+        "",
+    );
+
+    let descriptor = guest.resolve_firmware(&resolver).unwrap();
+    assert!(descriptor.has_feature("enrolled-keys"));
+    assert_eq!(
+        loader_path(&guest),
+        Some("/usr/share/edk2/ovmf/OVMF_CODE.secboot.fd")
+    );
+    let loader = guest.os.loader.as_ref().unwrap();
+    assert_eq!(loader.secure.as_deref(), Some("yes"));
+    assert_eq!(loader.loader_type.as_deref(), Some("pflash"));
+    assert_eq!(
+        guest.os.nvram.as_ref().unwrap().template.as_deref(),
+        Some("/usr/share/edk2/ovmf/OVMF_VARS.secboot.fd")
+    );
+    assert_eq!(
+        guest.features.as_ref().unwrap().smm.as_ref().unwrap().state,
+        "on"
+    );
+    assert_eq!(guest.validate_firmware(), Ok(()));
+}
+
+#[test]
+fn excluded_features_skip_secure_boot_firmware() {
+    let resolver = FirmwareResolver::from_dir(fixture("usr")).unwrap();
+    let mut guest = domain(
+        &format!(
+            r#"{}<firmware><feature enabled="no" name="secure-boot"/></firmware>"#,
+            Q35
+        ),
+        "",
+    );
+
+    guest.resolve_firmware(&resolver).unwrap();
+    assert_eq!(
+        loader_path(&guest),
+        Some("/usr/share/edk2/ovmf/OVMF_CODE.fd")
+    );
+}
+
+#[test]
+fn launch_security_requests_confidential_firmware() {
+    let resolver = FirmwareResolver::from_dir(fixture("usr")).unwrap();
+
+    for (launch_security, feature, path) in [
+        (
+            r#"<launchSecurity type="sev"><policy>0x0003</policy></launchSecurity>"#,
+            "amd-sev",
+            "/usr/share/edk2/ovmf/OVMF.amdsev.fd",
+        ),
+        (
+            r#"<launchSecurity type="sev"><policy>0x0007</policy></launchSecurity>"#,
+            "amd-sev-es",
+            "/usr/share/edk2/ovmf/OVMF.amdsev.fd",
+        ),
+        (
+            r#"<launchSecurity type="sev-snp"><policy>0x00030000</policy></launchSecurity>"#,
+            "amd-sev-snp",
+            "/usr/share/edk2/ovmf/OVMF.amdsev.fd",
+        ),
+        (
+            r#"<launchSecurity type="tdx"/>"#,
+            "intel-tdx",
+            "/usr/share/edk2/ovmf/OVMF.inteltdx.fd",
+        ),
+    ] {
+        let mut guest = domain(Q35, launch_security);
+        let request = FirmwareRequest::from_domain(&guest).unwrap();
+        assert_eq!(request.features.get(feature), Some(&true), "{:?}", request);
+
+        guest.resolve_firmware(&resolver).unwrap();
+        assert_eq!(loader_path(&guest), Some(path));
+        let loader = guest.os.loader.as_ref().unwrap();
+        assert_eq!(loader.stateless.as_deref(), Some("yes"));
+        assert!(guest.os.nvram.is_none());
+    }
+}
+
+#[test]
+fn bios_and_other_architectures() {
+    let resolver = FirmwareResolver::from_dir(fixture("usr")).unwrap();
+
+    let mut bios: Domain = quick_xml::de::from_str(
+        r#"<domain type="kvm"><name>legacy</name><memory unit="GiB">1</memory><vcpu>1</vcpu><os firmware="bios"><type arch="x86_64" machine="pc">hvm</type></os><devices/></domain>"#,
+    )
+    .unwrap();
+    bios.resolve_firmware(&resolver).unwrap();
+    assert_eq!(loader_path(&bios), Some("/usr/share/seabios/bios-256k.bin"));
+    assert_eq!(
+        bios.os.loader.as_ref().unwrap().loader_type.as_deref(),
+        Some("rom")
+    );
+
+    let mut arm = domain(r#"<type arch="aarch64" machine="virt-9.0">hvm</type>"#, "");
+    arm.resolve_firmware(&resolver).unwrap();
+    assert_eq!(
+        arm.os.nvram.as_ref().unwrap().template.as_deref(),
+        Some("/usr/share/edk2/aarch64/vars-template-pflash.qcow2")
+    );
+
+    let mut s390 = domain(
+        r#"<type arch="s390x" machine="s390-ccw-virtio">hvm</type>"#,
+        "",
+    );
+    assert!(s390.resolve_firmware(&resolver).is_err());
+}
+
+#[test]
+fn earlier_directories_override_and_mask_descriptors() {
+    let resolver = FirmwareResolver::from_dirs(&[fixture("etc"), fixture("usr")]).unwrap();
+    let names: Vec<String> = resolver
+        .descriptors
+        .iter()
+        .map(|(path, _)| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert!(names.contains(&"45-custom-ovmf.json".to_string()));
+    // etc/ 下的空文件屏蔽了同名的打包描述文件
+    assert!(!names.contains(&"50-edk2-ovmf-x64-nosb.json".to_string()));
+
+    let mut guest = domain(r#"<type arch="x86_64" machine="pc">hvm</type>"#, "");
+    guest.resolve_firmware(&resolver).unwrap();
+    assert_eq!(loader_path(&guest), Some("/opt/ovmf/OVMF_CODE.fd"));
+}
+
+#[test]
+fn interface_follows_firmware_attribute_or_pflash_loader() {
+    let parse = |os: &str| -> Domain {
+        quick_xml::de::from_str(&format!(
+            r#"<domain type="kvm"><name>guest</name><memory unit="GiB">1</memory><vcpu>1</vcpu><os{}</os><devices/></domain>"#,
+            os
+        ))
+        .unwrap()
+    };
+    let interface = |os: &str| FirmwareRequest::from_domain(&parse(os)).map(|r| r.interface);
+
+    assert_eq!(
+        interface(&format!(r#" firmware="efi">{}"#, Q35)).unwrap(),
+        "uefi"
+    );
+    assert_eq!(
+        interface(&format!(r#" firmware="bios">{}"#, Q35)).unwrap(),
+        "bios"
+    );
+    assert_eq!(
+        interface(&format!(
+            r#">{}<loader readonly="yes" type="pflash">/usr/share/edk2/ovmf/OVMF_CODE.fd</loader>"#,
+            Q35
+        ))
+        .unwrap(),
+        "uefi"
+    );
+
+    // 没有 firmware 属性也没有 pflash loader 时 libvirt 不做自动选择
+    let resolver = FirmwareResolver::from_dir(fixture("usr")).unwrap();
+    let mut plain = parse(&format!(">{}", Q35));
+    assert!(FirmwareRequest::from_domain(&plain).is_err());
+    assert!(plain.resolve_firmware(&resolver).is_err());
+    assert!(plain.os.loader.is_none());
+}
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+use vm_xml_tool::{ChassisEntry, DmiRedaction, Domain, HostDmi, SmbiosMode, SystemEntry};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/dmi")
+        .join(name)
+}
+
+fn domain(uuid: &str) -> Domain {
+    let xml = format!(
+        r#"<domain type="kvm"><name>guest</name>{}<memory unit="GiB">2</memory><vcpu>2</vcpu><os><type arch="x86_64" machine="q35">hvm</type></os><devices/></domain>"#,
+        uuid
+    );
+    quick_xml::de::from_str(&xml).unwrap()
+}
+
+#[test]
+fn from_dir_reads_known_fields_and_skips_empty_files() {
+    let dmi = HostDmi::from_dir(fixture("server")).unwrap();
+
+    assert_eq!(dmi.get("bios_vendor"), Some("American Megatrends Inc."));
+    assert_eq!(
+        dmi.get("product_uuid"),
+        Some("4C4C4544-0042-3510-8058-B4C04F4A4B32")
+    );
+    assert_eq!(dmi.get("product_sku"), None);
+    assert_eq!(dmi.get("modalias"), None);
+    assert_eq!(dmi.values.len(), 15);
+
+    assert!(HostDmi::from_dir(fixture("missing")).is_err());
+}
+
+#[test]
+fn to_smbios_redacts_identifiers_and_placeholders() {
+    let dmi = HostDmi::from_dir(fixture("server")).unwrap();
+
+    let full = dmi.to_smbios(&DmiRedaction::new());
+    let system = full.system.as_ref().unwrap();
+    assert_eq!(system.get(SystemEntry::Serial), Some("S4242424242"));
+    assert_eq!(
+        full.system_uuid(),
+        Some("4C4C4544-0042-3510-8058-B4C04F4A4B32")
+    );
+    // "Default string" 是固件占位值
+    assert!(
+        full.base_boards[0]
+            .entries
+            .iter()
+            .all(|e| e.name != "serial")
+    );
+
+    let redacted = dmi.to_smbios(&DmiRedaction::identifiers().with_replacement("redacted"));
+    let system = redacted.system.as_ref().unwrap();
+    assert_eq!(system.get(SystemEntry::Serial), Some("redacted"));
+    assert_eq!(system.get(SystemEntry::Product), Some("SYS-120U-TNR"));
+    assert_eq!(redacted.system_uuid(), None);
+    let chassis = redacted.chassis.as_ref().unwrap();
+    assert_eq!(chassis.get(ChassisEntry::Serial), Some("redacted"));
+    assert_eq!(chassis.get(ChassisEntry::Asset), Some("redacted"));
+}
+
+#[test]
+fn apply_host_smbios_adopts_host_uuid() {
+    let dmi = HostDmi::from_dir(fixture("server")).unwrap();
+    let mut guest = domain("");
+
+    guest
+        .apply_host_smbios(&dmi, &DmiRedaction::new(), SmbiosMode::Sysinfo)
+        .unwrap();
+    assert_eq!(
+        guest.uuid.as_deref(),
+        Some("4c4c4544-0042-3510-8058-b4c04f4a4b32")
+    );
+    assert_eq!(guest.sysinfo.as_ref().unwrap().len(), 1);
+    assert_eq!(guest.os.smbios.as_ref().unwrap().mode, "sysinfo");
+
+    guest
+        .apply_host_smbios(&dmi, &DmiRedaction::new(), SmbiosMode::Host)
+        .unwrap();
+    assert!(guest.sysinfo.is_none());
+    assert_eq!(guest.os.smbios.as_ref().unwrap().mode, "host");
+}
+
+#[test]
+fn uuid_mismatch_leaves_domain_unchanged() {
+    let dmi = HostDmi::from_dir(fixture("server")).unwrap();
+    let mut guest = domain("<uuid>6b1b5b9e-2f4c-4d5e-9a1b-0c2d3e4f5a6b</uuid>");
+
+    let err = guest
+        .apply_host_smbios(&dmi, &DmiRedaction::new(), SmbiosMode::Sysinfo)
+        .unwrap_err();
+    assert!(err.contains("differs from domain uuid"), "{}", err);
+    assert!(guest.sysinfo.is_none());
+    assert!(guest.os.smbios.is_none());
+    assert_eq!(
+        guest.uuid.as_deref(),
+        Some("6b1b5b9e-2f4c-4d5e-9a1b-0c2d3e4f5a6b")
+    );
+
+    // 隐藏 uuid 后不再冲突
+    guest
+        .apply_host_smbios(&dmi, &DmiRedaction::identifiers(), SmbiosMode::Sysinfo)
+        .unwrap();
+    assert!(guest.sysinfo.is_some());
+}
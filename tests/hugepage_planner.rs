@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+use vm_xml_tool::{Domain, HostHugepages, HugepagePlan, HugepagePool};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/hugepages")
+        .join(name)
+}
+
+fn domain(memory_gib: u32, numatune: &str) -> Domain {
+    let xml = format!(
+        r#"<domain type="kvm"><name>guest</name><memory unit="GiB">{}</memory><vcpu>2</vcpu><os><type arch="x86_64" machine="q35">hvm</type></os><memoryBacking><hugepages><page size="2" unit="M"/></hugepages></memoryBacking>{}<devices/></domain>"#,
+        memory_gib, numatune
+    );
+    quick_xml::de::from_str(&xml).unwrap()
+}
+
+#[test]
+fn from_root_reads_per_node_pools() {
+    let host = HostHugepages::from_root(fixture("numa")).unwrap();
+
+    assert_eq!(host.default_size_kib, 2048);
+    assert_eq!(host.nodes.len(), 2);
+    assert_eq!(
+        host.pool(0, 2048),
+        Some(&HugepagePool {
+            total: 512,
+            free: 512,
+            surplus: 0
+        })
+    );
+    assert_eq!(host.pool(1, 2048).map(|p| p.free), Some(128));
+    assert_eq!(
+        host.sizes().into_iter().collect::<Vec<_>>(),
+        [2048, 1048576]
+    );
+}
+
+#[test]
+fn from_root_falls_back_to_global_pools() {
+    let host = HostHugepages::from_root(fixture("flat")).unwrap();
+
+    assert_eq!(host.nodes.keys().copied().collect::<Vec<_>>(), [0]);
+    assert_eq!(
+        host.pool(0, 2048),
+        Some(&HugepagePool {
+            total: 256,
+            free: 200,
+            surplus: 3
+        })
+    );
+}
+
+#[test]
+fn from_root_without_hugepages_is_an_error() {
+    assert!(HostHugepages::from_root(fixture("empty")).is_err());
+    assert!(HostHugepages::from_root(fixture("missing")).is_err());
+}
+
+#[test]
+fn plan_spreads_unbound_demand_across_nodes() {
+    let host = HostHugepages::from_root(fixture("numa")).unwrap();
+    let guest = domain(1, "");
+
+    let plan = HugepagePlan::compute(&[&guest], &host);
+    assert!(plan.is_feasible(), "{}", plan);
+    assert_eq!(plan.allocation.get(&(0, 2048)), Some(&512));
+}
+
+#[test]
+fn plan_reports_shortfall_on_bound_node() {
+    let host = HostHugepages::from_root(fixture("numa")).unwrap();
+    let guest = domain(
+        1,
+        r#"<numatune><memory mode="strict" nodeset="1"/></numatune>"#,
+    );
+
+    let plan = HugepagePlan::compute(&[&guest], &host);
+    assert!(!plan.is_feasible());
+    assert_eq!(plan.shortfalls.len(), 1);
+    let shortfall = &plan.shortfalls[0];
+    assert_eq!((shortfall.node, shortfall.size_kib), (1, 2048));
+    assert_eq!(shortfall.free, 128);
+    assert_eq!(shortfall.missing, 512 - 128);
+    assert!(!plan.suggestions.is_empty());
+}
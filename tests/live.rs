@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::PathBuf;
+use vm_xml_tool::{CanonicalOptions, Domain};
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/live")
+        .join(name);
+    fs::read_to_string(path).unwrap()
+}
+
+fn inactive(slot: &str) -> Domain {
+    Domain::from_xml(&format!(
+        r#"<domain type="kvm"><name>guest</name><memory unit="GiB">2</memory><vcpu>2</vcpu><os><type arch="x86_64" machine="q35">hvm</type></os><devices><interface type="bridge"><source bridge="br0"/><model type="virtio"/><address type="pci" domain="0x0000" bus="0x00" slot="{}" function="0x0"/></interface></devices></domain>"#,
+        slot
+    ))
+    .unwrap()
+}
+
+#[test]
+fn runtime_fields_of_live_xml() {
+    let live = Domain::from_xml(&fixture("running.xml")).unwrap();
+    assert!(live.is_live());
+
+    let fields = live.runtime_fields();
+    for field in [
+        "id",
+        "devices/interface[0]/target",
+        "devices/interface[0]/source/portid",
+        "devices/interface[0]/source/bridge",
+        "resource/partition",
+        "seclabel[0]/label",
+        "seclabel[0]/imagelabel",
+    ] {
+        assert!(
+            fields.iter().any(|f| f == field),
+            "{} in {:?}",
+            field,
+            fields
+        );
+    }
+    assert!(
+        fields.iter().any(|f| f.ends_with("/address")),
+        "{:?}",
+        fields
+    );
+}
+
+#[test]
+fn to_inactive_strips_runtime_fields() {
+    let live = Domain::from_xml(&fixture("running.xml")).unwrap();
+    let inactive = live.to_inactive().unwrap();
+
+    assert!(!inactive.is_live());
+    assert!(inactive.runtime_fields().is_empty());
+    assert!(inactive.resource.is_none());
+
+    let interfaces = inactive.devices.interfaces.as_ref().unwrap();
+    let network = interfaces[0].source.as_ref().unwrap();
+    assert_eq!(network.network.as_deref(), Some("default"));
+    assert!(network.portid.is_none() && network.bridge.is_none());
+    assert!(interfaces[0].target.is_none() && interfaces[0].alias.is_none());
+    // bridge 类型的 bridge 属于配置，用户别名保留
+    assert_eq!(
+        interfaces[1].source.as_ref().unwrap().bridge.as_deref(),
+        Some("br0")
+    );
+    assert!(interfaces[1].alias.is_some());
+    assert!(interfaces.iter().all(|i| i.address.is_none()));
+
+    let seclabel = &inactive.seclabels.as_ref().unwrap()[0];
+    assert!(seclabel.label.is_none() && seclabel.image_label.is_none());
+}
+
+#[test]
+fn live_xml_and_its_inactive_template_hash_equal() {
+    let live = Domain::from_xml(&fixture("running.xml")).unwrap();
+    let inactive = live.to_inactive().unwrap();
+
+    assert!(live.semantically_eq(&inactive).unwrap());
+    assert_eq!(
+        live.content_hash().unwrap(),
+        inactive.content_hash().unwrap()
+    );
+
+    // 比较地址时，活动 XML 中自动分配的地址构成差异
+    let keep = CanonicalOptions::new().with_keep_addresses(true);
+    assert!(!live.semantically_eq_with(&inactive, keep).unwrap());
+}
+
+#[test]
+fn inactive_domains_keep_pinned_addresses() {
+    let a = inactive("0x03");
+    let b = inactive("0x04");
+    assert!(a.runtime_fields().is_empty());
+    assert_eq!(a.content_hash().unwrap(), b.content_hash().unwrap());
+
+    let keep = CanonicalOptions::new().with_keep_addresses(true);
+    assert_ne!(
+        a.content_hash_with(keep).unwrap(),
+        b.content_hash_with(keep).unwrap()
+    );
+    assert_eq!(
+        a.content_hash_with(keep).unwrap(),
+        inactive("0x03").content_hash_with(keep).unwrap()
+    );
+
+    let mut pinned = inactive("0x03");
+    pinned.canonicalize_with(keep);
+    assert!(
+        pinned.devices.interfaces.as_ref().unwrap()[0]
+            .address
+            .is_some()
+    );
+    let copy = a.to_inactive().unwrap();
+    assert!(
+        copy.devices.interfaces.as_ref().unwrap()[0]
+            .address
+            .is_some()
+    );
+}
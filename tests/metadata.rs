@@ -0,0 +1,38 @@
+use vm_xml_tool::Domain;
+
+const FOREIGN: &str = r#"<app:info xmlns:app="http://example.org/app"><!-- owner --><item>a</item>text<app:x y="1"/></app:info>"#;
+
+fn domain(metadata: &str) -> String {
+    format!(
+        r#"<domain type="kvm"><name>guest</name><metadata>{}</metadata><memory unit="GiB">2</memory><vcpu>2</vcpu><os><type arch="x86_64" machine="q35">hvm</type></os><devices/></domain>"#,
+        metadata
+    )
+}
+
+#[test]
+fn unknown_namespace_round_trips_verbatim() {
+    let guest = Domain::from_xml(&domain(FOREIGN)).unwrap();
+    let xml = guest.to_xml().unwrap();
+    assert!(xml.contains(FOREIGN), "{}", xml);
+    assert_eq!(
+        guest.metadata.as_ref().unwrap().elements[0].raw(),
+        Some(FOREIGN)
+    );
+}
+
+#[test]
+fn modified_element_is_rebuilt() {
+    let other = r#"<other xmlns="http://example.org/o"><v>1</v></other>"#;
+    let mut guest = Domain::from_xml(&domain(&format!("{}{}", FOREIGN, other))).unwrap();
+    let elements = &mut guest.metadata.as_mut().unwrap().elements;
+    elements[1].children[0].text = Some("2".to_string());
+    assert!(elements[1].raw().is_none());
+
+    let xml = guest.to_xml().unwrap();
+    assert!(xml.contains(FOREIGN), "{}", xml);
+    assert!(
+        xml.contains(r#"<other xmlns="http://example.org/o"><v>2</v></other>"#),
+        "{}",
+        xml
+    );
+}
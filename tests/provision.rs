@@ -0,0 +1,54 @@
+use vm_xml_tool::{Domain, ProvisionPayload, Provisioning, SystemEntry};
+
+fn domain() -> Domain {
+    quick_xml::de::from_str(
+        r#"<domain type="kvm"><name>guest</name><memory unit="GiB">2</memory><vcpu>2</vcpu><os><type arch="x86_64" machine="q35">hvm</type></os><devices/></domain>"#,
+    )
+    .unwrap()
+}
+
+fn nocloud() -> ProvisionPayload {
+    ProvisionPayload::nocloud(
+        "http://10.0.2.2:8000/",
+        "#cloud-config\nhostname: guest\n",
+        "instance-id: guest\n",
+    )
+}
+
+#[test]
+fn nocloud_without_file_dir_is_rejected() {
+    let mut guest = domain();
+
+    let err = guest.provision(&Provisioning::new(nocloud())).unwrap_err();
+    assert!(err.contains("file_dir"), "{}", err);
+    assert!(guest.sysinfo.is_none());
+    assert!(guest.os.smbios.is_none());
+}
+
+#[test]
+fn nocloud_writes_seed_files_and_points_serial_at_seed_url() {
+    let dir = std::env::temp_dir().join(format!("vm_xml_tool-nocloud-{}", std::process::id()));
+    let provisioning =
+        Provisioning::new(nocloud().with_network_config("version: 2\n")).with_file_dir(&dir);
+    let mut guest = domain();
+
+    guest.provision(&provisioning).unwrap();
+    let files = provisioning.write_files().unwrap();
+    let names: Vec<_> = files
+        .iter()
+        .map(|f| f.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, ["user-data", "meta-data", "network-config"]);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("meta-data")).unwrap(),
+        "instance-id: guest\n"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let smbios = guest.sysinfo.as_ref().unwrap()[0].as_smbios().unwrap();
+    assert_eq!(
+        smbios.system.as_ref().unwrap().get(SystemEntry::Serial),
+        Some("ds=nocloud;s=http://10.0.2.2:8000/")
+    );
+    assert_eq!(guest.os.smbios.as_ref().unwrap().mode, "sysinfo");
+}
@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+use vm_xml_tool::{Hypervisor, QemuArgvImport};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/qemu_argv")
+        .join(name)
+}
+
+fn import(args: &[&str]) -> QemuArgvImport {
+    let mut argv = vec!["qemu-system-x86_64", "-name", "g"];
+    argv.extend_from_slice(args);
+    QemuArgvImport::from_argv(&argv).unwrap()
+}
+
+#[test]
+fn flags_do_not_consume_the_next_option() {
+    let import = import(&[
+        "-mem-prealloc",
+        "-m",
+        "4096",
+        "-no-user-config",
+        "-only-migratable",
+        "-no-quit",
+        "-enable-kvm",
+        "-no-reboot",
+        "-smp",
+        "2",
+    ]);
+
+    assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+    assert_eq!(import.domain.memory.value, 4096 * 1024);
+    assert_eq!(import.domain.vcpu.vcpu_count, 2);
+    assert_eq!(import.domain.domain_type, Hypervisor::Kvm);
+    assert!(
+        import
+            .domain
+            .to_xml()
+            .unwrap()
+            .contains(r#"<allocation mode="immediate"/>"#)
+    );
+}
+
+#[test]
+fn dash_prefixed_token_is_never_an_option_value() {
+    let import = import(&["-uuid", "-m", "1G"]);
+
+    assert!(import.domain.uuid.is_none());
+    assert_eq!(import.domain.memory.value, 1024 * 1024);
+    assert_eq!(
+        import.warnings,
+        ["Option -uuid is missing its argument, '-m' is treated as the next option"]
+    );
+
+    let errors = QemuArgvImport::from_argv(&["qemu-system-x86_64", "-m"]).unwrap_err();
+    assert_eq!(errors, ["Option -m requires an argument"]);
+}
+
+#[test]
+fn memory_sizes_use_qemu_suffixes() {
+    assert_eq!(import(&["-m", "2048"]).domain.memory.value, 2048 * 1024);
+    assert_eq!(import(&["-m", "4g"]).domain.memory.value, 4 * 1024 * 1024);
+    assert_eq!(import(&["-m", "1.5G"]).domain.memory.value, 1536 * 1024);
+
+    let import = import(&["-m", "size=1G,slots=4,maxmem=8G"]);
+    let max = import.domain.max_memory.as_ref().unwrap();
+    assert_eq!((max.slots, max.value), (4, 8 * 1024 * 1024));
+
+    for bad in ["4x", "G", "1.5b"] {
+        assert!(QemuArgvImport::from_argv(&["qemu-system-x86_64", "-m", bad]).is_err());
+    }
+}
+
+#[test]
+fn smp_topology_fills_missing_levels() {
+    let import = import(&["-smp", "8,sockets=2,threads=2,maxcpus=16"]);
+    assert_eq!(import.domain.vcpu.vcpu_count, 16);
+    assert_eq!(import.domain.vcpu.current, Some(8));
+    let topology = import
+        .domain
+        .cpu
+        .as_ref()
+        .unwrap()
+        .topology
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        (topology.sockets, topology.cores, topology.threads),
+        (2, 4, 2)
+    );
+
+    let import = self::import(&["-smp", "cpus=4"]);
+    assert_eq!(import.domain.vcpu.vcpu_count, 4);
+    assert!(import.domain.cpu.is_none());
+
+    for bad in ["0", "4,cores=0", "4,sockets=65536,cores=65536,threads=2"] {
+        assert!(QemuArgvImport::from_argv(&["qemu-system-x86_64", "-smp", bad]).is_err());
+    }
+}
+
+#[test]
+fn drives_become_disks() {
+    let import = import(&[
+        "-drive",
+        "file=/dev/sdb,if=virtio,format=raw",
+        "-drive",
+        "file=/images/a,,b.qcow2,if=none,id=d1,format=qcow2",
+        "-device",
+        "scsi-hd,drive=d1",
+        "-cdrom",
+        "/iso/install.iso",
+    ]);
+    assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+
+    let disks = import.domain.devices.disk.as_ref().unwrap();
+    assert_eq!(disks.len(), 3);
+    assert_eq!(disks[0].disk_type, "block");
+    assert_eq!(disks[0].source.dev.as_deref(), Some("/dev/sdb"));
+    assert_eq!(disks[0].target.dev, "vda");
+    assert_eq!(disks[1].source.file, "/images/a,b.qcow2");
+    assert_eq!(disks[1].driver.driver_type, "qcow2");
+    assert_eq!(
+        (disks[1].target.dev.as_str(), disks[1].target.bus.as_str()),
+        ("sda", "scsi")
+    );
+    assert_eq!(
+        (disks[2].device.as_str(), disks[2].target.dev.as_str()),
+        ("cdrom", "hdc")
+    );
+}
+
+#[test]
+fn blockdev_chains_resolve_to_a_disk() {
+    let import = import(&[
+        "-blockdev",
+        "driver=file,filename=/images/root.img,node-name=proto0",
+        "-blockdev",
+        "driver=qcow2,node-name=fmt0,file=proto0",
+        "-blockdev",
+        "driver=raw,node-name=fmt1,file.driver=file,file.filename=/images/data.raw",
+        "-blockdev",
+        "driver=nbd,node-name=remote,server.type=inet,server.host=nas",
+        "-device",
+        "virtio-blk-pci,drive=fmt0",
+        "-device",
+        "virtio-blk-pci,drive=fmt1",
+    ]);
+
+    let disks = import.domain.devices.disk.as_ref().unwrap();
+    assert_eq!(disks.len(), 2);
+    assert_eq!(disks[0].source.file, "/images/root.img");
+    assert_eq!(disks[0].driver.driver_type, "qcow2");
+    assert_eq!(disks[1].source.file, "/images/data.raw");
+    assert_eq!(disks[1].driver.driver_type, "raw");
+    assert_eq!(
+        import.warnings,
+        ["-blockdev node-name=remote is not attached to any device"]
+    );
+}
+
+#[test]
+fn proc_cmdline_from_libvirt_is_imported() {
+    let import = QemuArgvImport::from_proc_cmdline(fixture("libvirt.cmdline")).unwrap();
+    let domain = &import.domain;
+
+    assert_eq!(domain.name, "web01");
+    assert_eq!(domain.domain_type, Hypervisor::Kvm);
+    assert_eq!(domain.memory.value, 4 * 1024 * 1024);
+    assert_eq!(domain.vcpu.vcpu_count, 4);
+
+    let disks = domain.devices.disk.as_ref().unwrap();
+    assert_eq!(disks.len(), 1);
+    assert_eq!(disks[0].source.file, "/var/lib/libvirt/images/web01.qcow2");
+    assert_eq!(disks[0].driver.driver_type, "qcow2");
+    assert_eq!(disks[0].target.dev, "vda");
+
+    let interfaces = domain.devices.interfaces.as_ref().unwrap();
+    assert_eq!(interfaces.len(), 1);
+
+    assert!(
+        import.warnings.iter().all(|w| !w.contains("stray")),
+        "{:?}",
+        import.warnings
+    );
+
+    let missing = QemuArgvImport::from_proc_cmdline(fixture("missing.cmdline")).unwrap_err();
+    assert!(missing[0].starts_with("Failed to read"));
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+use vm_xml_tool::{CachePlan, Domain, ResctrlCacheType, ResctrlInfo};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/resctrl")
+        .join(name)
+}
+
+fn domain(name: &str, cachetunes: &str) -> Domain {
+    let xml = format!(
+        r#"<domain type="kvm"><name>{}</name><memory unit="GiB">2</memory><vcpu>4</vcpu><cputune>{}</cputune><os><type arch="x86_64" machine="q35">hvm</type></os><devices/></domain>"#,
+        name, cachetunes
+    );
+    quick_xml::de::from_str(&xml).unwrap()
+}
+
+#[test]
+fn from_root_reads_cache_bandwidth_and_monitoring() {
+    let info = ResctrlInfo::from_root(fixture("basic")).unwrap();
+
+    let l3 = info.cache(3, ResctrlCacheType::Both).unwrap();
+    assert_eq!(l3.cbm_bits, 12);
+    assert_eq!(l3.min_cbm_bits, 1);
+    assert_eq!(l3.cache_size_kib, 24576);
+    assert_eq!(l3.granularity_kib(), 2048);
+    assert_eq!(l3.ids.iter().copied().collect::<Vec<_>>(), [0, 1]);
+    assert!(info.cache(2, ResctrlCacheType::Both).is_none());
+
+    assert_eq!(info.memory_bandwidth.as_ref().unwrap().bandwidth_gran, 10);
+    assert_eq!(info.monitor.as_ref().unwrap().features.len(), 3);
+    assert_eq!(info.num_closids(), Some(8));
+}
+
+#[test]
+fn from_root_requires_mounted_resctrl() {
+    assert!(ResctrlInfo::from_root(fixture("unmounted")).is_err());
+}
+
+#[test]
+fn validate_checks_granularity_and_reserved_bits() {
+    let info = ResctrlInfo::from_root(fixture("basic")).unwrap();
+
+    let ok = domain(
+        "ok",
+        r#"<cachetune vcpus="0-1"><cache id="0" level="3" type="both" size="4" unit="MiB"/></cachetune>"#,
+    );
+    assert_eq!(ok.validate_resctrl(Some(&info)), Ok(()));
+
+    let bad = domain(
+        "bad",
+        r#"<cachetune vcpus="0"><cache id="0" level="3" type="both" size="3" unit="MiB"/></cachetune><cachetune vcpus="1"><cache id="0" level="3" type="both" size="24" unit="MiB"/></cachetune>"#,
+    );
+    let errors = bad.validate_resctrl(Some(&info)).unwrap_err();
+    assert!(
+        errors.iter().any(|e| e.contains("not a multiple")),
+        "{:?}",
+        errors
+    );
+    assert!(errors.iter().any(|e| e.contains("exceeds")), "{:?}", errors);
+}
+
+#[test]
+fn malformed_host_info_reports_error_instead_of_panicking() {
+    let info = ResctrlInfo::from_root(fixture("malformed")).unwrap();
+    let guest = domain(
+        "guest",
+        r#"<cachetune vcpus="0"><cache id="0" level="3" type="both" size="512" unit="KiB"/></cachetune>"#,
+    );
+
+    let errors = guest.validate_resctrl(Some(&info)).unwrap_err();
+    assert!(
+        errors.iter().any(|e| e.contains("min_cbm_bits")),
+        "{:?}",
+        errors
+    );
+    assert!(!CachePlan::compute(&[&guest], &info).is_feasible());
+}
+
+#[test]
+fn plan_packs_masks_from_the_top() {
+    let info = ResctrlInfo::from_root(fixture("basic")).unwrap();
+    let a = domain(
+        "a",
+        r#"<cachetune vcpus="0"><cache id="0" level="3" type="both" size="4" unit="MiB"/></cachetune>"#,
+    );
+    let b = domain(
+        "b",
+        r#"<cachetune vcpus="0"><cache id="0" level="3" type="both" size="2" unit="MiB"/></cachetune>"#,
+    );
+
+    let plan = CachePlan::compute(&[&a, &b], &info);
+    assert!(plan.is_feasible(), "{:?}", plan.errors);
+    let schemata: Vec<String> = plan.allocations.iter().map(|a| a.schemata()).collect();
+    assert_eq!(schemata, ["L3:0=c00", "L3:0=200"]);
+}
@@ -0,0 +1,246 @@
+use vm_xml_tool::{
+    Domain, DomainEvent, DomainState, DomainStateMachine, LifecycleAction, LifecycleConfig,
+    LifecycleEvent, SideEffect, Transition,
+};
+
+fn running(config: LifecycleConfig) -> DomainStateMachine {
+    DomainStateMachine::new("web", config, Default::default()).with_state(DomainState::Running)
+}
+
+fn domain(pm: &str) -> Domain {
+    let xml = format!(
+        r#"<domain type="kvm"><name>web</name><memory unit="GiB">2</memory><vcpu>2</vcpu><os><type arch="x86_64" machine="q35">hvm</type></os>{}<devices/></domain>"#,
+        pm
+    );
+    quick_xml::de::from_str(&xml).unwrap()
+}
+
+/// 在运行中的域上配置 `event=action` 并触发一次，返回 (目标状态, 副作用)
+fn fire(
+    event: DomainEvent,
+    action: LifecycleAction,
+) -> Result<(DomainState, Vec<SideEffect>), String> {
+    let lifecycle_event = event.lifecycle_event().unwrap();
+    let mut config = LifecycleConfig::new();
+    config.set_action(lifecycle_event, action);
+
+    let mut machine = running(config);
+    let transition = machine.apply(event)?;
+    assert_eq!(transition.from, DomainState::Running);
+    assert_eq!(transition.action, Some(action));
+    assert_eq!(machine.state(), transition.to);
+    Ok((transition.to, transition.effects))
+}
+
+#[test]
+fn poweroff_and_reboot_actions() {
+    use LifecycleAction::*;
+
+    for event in [DomainEvent::GuestPoweroff, DomainEvent::GuestReboot] {
+        assert_eq!(
+            fire(event, Destroy).unwrap(),
+            (DomainState::Shutoff, vec![SideEffect::ResourcesReleased])
+        );
+        assert_eq!(
+            fire(event, Restart).unwrap(),
+            (DomainState::Running, vec![SideEffect::Restarted])
+        );
+        assert_eq!(
+            fire(event, Preserve).unwrap(),
+            (DomainState::Shutdown, vec![SideEffect::ResourcesPreserved])
+        );
+        assert_eq!(
+            fire(event, RenameRestart).unwrap(),
+            (
+                DomainState::Running,
+                vec![
+                    SideEffect::RenameRestart {
+                        preserved_as: "web-1".to_string()
+                    },
+                    SideEffect::Restarted
+                ]
+            )
+        );
+
+        for action in [CoredumpDestroy, CoredumpRestart, Poweroff, Pause, Ignore] {
+            assert!(fire(event, action).is_err(), "{} {}", event, action);
+        }
+    }
+}
+
+#[test]
+fn crash_actions() {
+    use LifecycleAction::*;
+    let event = DomainEvent::GuestCrash;
+
+    assert_eq!(
+        fire(event, Destroy).unwrap(),
+        (DomainState::Shutoff, vec![SideEffect::ResourcesReleased])
+    );
+    assert_eq!(
+        fire(event, Restart).unwrap(),
+        (DomainState::Running, vec![SideEffect::Restarted])
+    );
+    assert_eq!(
+        fire(event, Preserve).unwrap(),
+        (DomainState::Crashed, vec![SideEffect::ResourcesPreserved])
+    );
+    assert_eq!(
+        fire(event, CoredumpDestroy).unwrap(),
+        (
+            DomainState::Shutoff,
+            vec![SideEffect::Coredump, SideEffect::ResourcesReleased]
+        )
+    );
+    assert_eq!(
+        fire(event, CoredumpRestart).unwrap(),
+        (
+            DomainState::Running,
+            vec![SideEffect::Coredump, SideEffect::Restarted]
+        )
+    );
+    assert_eq!(fire(event, RenameRestart).unwrap().0, DomainState::Running);
+
+    for action in [Poweroff, Pause, Ignore] {
+        assert!(fire(event, action).is_err(), "{}", action);
+    }
+}
+
+#[test]
+fn lockfailure_actions() {
+    use LifecycleAction::*;
+    let event = DomainEvent::LockFailure;
+
+    assert_eq!(
+        fire(event, Poweroff).unwrap(),
+        (DomainState::Shutoff, vec![SideEffect::ResourcesReleased])
+    );
+    assert_eq!(
+        fire(event, Restart).unwrap(),
+        (DomainState::Running, vec![SideEffect::Restarted])
+    );
+    assert_eq!(fire(event, Pause).unwrap(), (DomainState::Paused, vec![]));
+    assert_eq!(
+        fire(event, Ignore).unwrap(),
+        (DomainState::Running, vec![SideEffect::Ignored])
+    );
+
+    for action in [
+        Destroy,
+        Preserve,
+        RenameRestart,
+        CoredumpDestroy,
+        CoredumpRestart,
+    ] {
+        assert!(fire(event, action).is_err(), "{}", action);
+    }
+
+    // 锁失效在暂停状态下同样生效，其它 on_* 事件则不行
+    let mut machine =
+        running(LifecycleConfig::new().on_lockfailure(Ignore)).with_state(DomainState::Paused);
+    let transition = machine.apply(event).unwrap();
+    assert_eq!(transition.to, DomainState::Paused);
+    assert!(machine.apply(DomainEvent::GuestCrash).is_err());
+}
+
+#[test]
+fn unset_actions_use_hypervisor_defaults() {
+    let mut machine = running(LifecycleConfig::new());
+
+    let reboot = machine.apply(DomainEvent::GuestReboot).unwrap();
+    assert_eq!(reboot.action, Some(LifecycleAction::Restart));
+
+    let poweroff = machine.apply(DomainEvent::GuestPoweroff).unwrap();
+    assert_eq!(poweroff.action, Some(LifecycleAction::Destroy));
+    assert_eq!(machine.state(), DomainState::Shutoff);
+
+    assert_eq!(
+        DomainStateMachine::default_action(LifecycleEvent::OnCrash),
+        LifecycleAction::Destroy
+    );
+    assert_eq!(
+        DomainStateMachine::default_action(LifecycleEvent::OnLockfailure),
+        LifecycleAction::Poweroff
+    );
+}
+
+#[test]
+fn rename_restart_numbers_preserved_domains() {
+    let config = LifecycleConfig::new()
+        .on_crash(LifecycleAction::RenameRestart)
+        .on_reboot(LifecycleAction::RenameRestart);
+    let mut machine = running(config);
+
+    let transitions = machine
+        .run(&[
+            DomainEvent::GuestCrash,
+            DomainEvent::GuestReboot,
+            DomainEvent::GuestCrash,
+        ])
+        .unwrap();
+
+    let names: Vec<&str> = transitions
+        .iter()
+        .filter_map(|t| match &t.effects[0] {
+            SideEffect::RenameRestart { preserved_as } => Some(preserved_as.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, ["web-1", "web-2", "web-3"]);
+    assert_eq!(machine.history().len(), 3);
+    assert_eq!(machine.state(), DomainState::Running);
+}
+
+#[test]
+fn pm_suspend_requires_enabled_sleep_state() {
+    // 未配置 <pm> 时两种挂起都不可用
+    let mut machine = DomainStateMachine::from_domain(&domain("")).with_state(DomainState::Running);
+    assert!(machine.apply(DomainEvent::SuspendToMem).is_err());
+    assert!(machine.apply(DomainEvent::SuspendToDisk).is_err());
+    assert_eq!(machine.state(), DomainState::Running);
+    assert!(machine.history().is_empty());
+
+    let pm = r#"<pm><suspend-to-mem enabled="yes"/><suspend-to-disk enabled="no"/></pm>"#;
+    let mut machine = DomainStateMachine::from_domain(&domain(pm));
+    let transitions = machine
+        .run(&[
+            DomainEvent::Start,
+            DomainEvent::SuspendToMem,
+            DomainEvent::Wakeup,
+        ])
+        .unwrap();
+    assert_eq!(
+        transitions[1],
+        Transition {
+            event: DomainEvent::SuspendToMem,
+            from: DomainState::Running,
+            to: DomainState::PmSuspended,
+            action: None,
+            effects: vec![],
+        }
+    );
+    assert_eq!(machine.state(), DomainState::Running);
+    let err = machine.apply(DomainEvent::SuspendToDisk).unwrap_err();
+    assert!(err.contains("not enabled in <pm>"), "{}", err);
+
+    let pm = r#"<pm><suspend-to-disk enabled="yes"/></pm>"#;
+    let mut machine = DomainStateMachine::from_domain(&domain(pm)).with_state(DomainState::Running);
+    let transition = machine.apply(DomainEvent::SuspendToDisk).unwrap();
+    assert_eq!(transition.to, DomainState::Shutoff);
+    assert_eq!(transition.effects, [SideEffect::HibernationImageWritten]);
+}
+
+#[test]
+fn events_are_rejected_in_wrong_states() {
+    let mut machine = DomainStateMachine::new("web", LifecycleConfig::new(), Default::default());
+
+    assert!(machine.apply(DomainEvent::GuestCrash).is_err());
+    assert!(machine.apply(DomainEvent::Destroy).is_err());
+    assert!(machine.apply(DomainEvent::Wakeup).is_err());
+
+    machine.apply(DomainEvent::Start).unwrap();
+    assert!(machine.apply(DomainEvent::Start).is_err());
+    let destroy = machine.apply(DomainEvent::Destroy).unwrap();
+    assert_eq!(destroy.effects, [SideEffect::ResourcesReleased]);
+    assert_eq!(machine.state(), DomainState::Shutoff);
+}
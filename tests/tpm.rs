@@ -0,0 +1,37 @@
+use vm_xml_tool::{PcrBanks, Tpm};
+
+fn tpm(model: &str, version: &str) -> Tpm {
+    quick_xml::de::from_str(&format!(
+        r#"<tpm model="{}"><backend type="emulator"{}><active_pcr_banks><sha256/><sha384/></active_pcr_banks></backend></tpm>"#,
+        model, version
+    ))
+    .unwrap()
+}
+
+#[test]
+fn pcr_banks_accept_the_default_tpm_version() {
+    for model in ["tpm-crb", "tpm-tis", "tpm-spapr"] {
+        assert_eq!(tpm(model, "").validate(None), Ok(()), "{}", model);
+        assert_eq!(tpm(model, r#" version="2.0""#).validate(None), Ok(()));
+    }
+    assert_eq!(
+        tpm("tpm-crb", "").backend.active_pcr_banks.unwrap().names(),
+        ["sha256", "sha384"]
+    );
+}
+
+#[test]
+fn pcr_banks_reject_tpm_1_2_and_non_emulator_backends() {
+    let errors = tpm("tpm-tis", r#" version="1.2""#)
+        .validate(None)
+        .unwrap_err();
+    assert!(
+        errors.iter().any(|e| e.contains("<active_pcr_banks>")),
+        "{:?}",
+        errors
+    );
+
+    let banks = PcrBanks::from_names(&["sha256"]).unwrap();
+    let passthrough = Tpm::passthrough("tpm-tis", "/dev/tpm0").with_pcr_banks(banks);
+    assert!(passthrough.validate(None).is_err());
+}